@@ -0,0 +1,525 @@
+#![feature(error_iter)]
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, ValueEnum};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use kra_parser::kra_archive::KraArchive;
+use kra_parser::kra_keyframes::KraKeyframesDocument;
+use kra_parser::kra_main_doc::KraMainDocLayerContainer;
+
+/// Path git passes for the missing side of added and deleted files.
+const NULL_PATH: &str = "/dev/null";
+
+#[derive(Parser, Debug)]
+#[command(name = "kra-diff", version)]
+struct Args {
+    /// Output format
+    #[arg(long, short = 'F', value_name = "FORMAT", default_value = "plain-text")]
+    format: DiffFormat,
+
+    /// Old and new document paths, or the arguments git passes to an external diff driver
+    #[arg(num_args = 2..=9, required = true)]
+    paths: Vec<Utf8PathBuf>,
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug)]
+enum DiffFormat {
+    PlainText,
+    Json,
+}
+
+/// Changes between two documents, layers and masks are matched by their UUIDs.
+#[derive(Serialize)]
+struct DocumentDiff<'a> {
+    old_path: String,
+    new_path: String,
+    document_changes: Vec<AttributeChange>,
+    metadata_changes: Vec<AttributeChange>,
+    node_changes: Vec<NodeChange<'a>>,
+}
+
+#[derive(Serialize)]
+struct AttributeChange {
+    attribute: String,
+    old_value: Value,
+    new_value: Value,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+enum NodeChange<'a> {
+    Added {
+        uuid: &'a str,
+        name: &'a str,
+        node_type: String,
+    },
+    Removed {
+        uuid: &'a str,
+        name: &'a str,
+        node_type: String,
+    },
+    Renamed {
+        uuid: &'a str,
+        old_name: &'a str,
+        new_name: &'a str,
+    },
+    Moved {
+        uuid: &'a str,
+        name: &'a str,
+        old_parent: Option<&'a str>,
+        new_parent: Option<&'a str>,
+        old_index: usize,
+        new_index: usize,
+    },
+    AttributeChanged {
+        uuid: &'a str,
+        name: &'a str,
+        attribute: &'static str,
+        old_value: Value,
+        new_value: Value,
+    },
+    KeyframesChanged {
+        uuid: &'a str,
+        name: &'a str,
+        channel: String,
+        added_times: Vec<usize>,
+        removed_times: Vec<usize>,
+        changed_times: Vec<usize>,
+    },
+}
+
+/// Layer or mask of a document.
+struct DiffNode<'a> {
+    name: &'a str,
+    node_type: String,
+    parent: Option<&'a str>,
+    index: usize,
+    attributes: Vec<(&'static str, Value)>,
+    keyframes: Option<KraKeyframesDocument>,
+}
+
+/// Layers and masks of a document keyed by their UUIDs, along with their order in the layer stack.
+#[derive(Default)]
+struct DiffNodes<'a> {
+    nodes: BTreeMap<&'a str, DiffNode<'a>>,
+    children: BTreeMap<Option<&'a str>, Vec<&'a str>>,
+    order: Vec<&'a str>,
+}
+
+fn main() -> ExitCode {
+    match main_inner() {
+        Ok(exit_code) => exit_code,
+        Err(err) => {
+            for source in err.sources() {
+                eprintln!("kra-diff: {}", source);
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn main_inner() -> Result<ExitCode, Box<dyn Error>> {
+    let args = Args::try_parse()?;
+
+    // git calls external diff drivers with "path old-file old-hex old-mode new-file new-hex new-mode",
+    // followed by the new path and a similarity header for renamed files
+    let (old_path, old_label, new_path, new_label) = match args.paths.as_slice() {
+        [old_path, new_path] => (old_path, old_path.to_string(), new_path, new_path.to_string()),
+        [path, old_path, _, _, new_path, _, _, renamed_paths @ ..] if renamed_paths.len() != 1 => {
+            let new_name = renamed_paths.first().unwrap_or(path);
+            (old_path, format!("a/{}", path), new_path, format!("b/{}", new_name))
+        }
+        _ => Args::command()
+            .error(
+                ErrorKind::WrongNumberOfValues,
+                "expected two document paths, or the seven or nine arguments of a git external diff driver",
+            )
+            .exit(),
+    };
+
+    let old_kra_archive = load_kra_archive(old_path)?;
+    let new_kra_archive = load_kra_archive(new_path)?;
+
+    let document_diff = diff_documents(old_kra_archive.as_ref(), old_label, new_kra_archive.as_ref(), new_label)?;
+
+    let mut writer = io::stdout().lock();
+
+    match args.format {
+        DiffFormat::PlainText => write_document_diff(&mut writer, &document_diff)?,
+        DiffFormat::Json => {
+            serde_json::to_writer_pretty(&mut writer, &document_diff)?;
+            writeln!(writer)?;
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Loads a document, `/dev/null` stands for a document which does not exist.
+fn load_kra_archive(path: &Utf8Path) -> Result<Option<KraArchive>, Box<dyn Error>> {
+    if path == NULL_PATH {
+        Ok(None)
+    } else {
+        Ok(Some(KraArchive::from_path(path)?))
+    }
+}
+
+fn diff_documents<'a>(
+    old_kra_archive: Option<&'a KraArchive>,
+    old_path: String,
+    new_kra_archive: Option<&'a KraArchive>,
+    new_path: String,
+) -> Result<DocumentDiff<'a>, Box<dyn Error>> {
+    let mut document_changes = vec![];
+    let mut metadata_changes = vec![];
+
+    if let (Some(old_kra_archive), Some(new_kra_archive)) = (old_kra_archive, new_kra_archive) {
+        diff_values(
+            String::new(),
+            &document_value(old_kra_archive),
+            &document_value(new_kra_archive),
+            &mut document_changes,
+        );
+
+        diff_values(
+            String::new(),
+            &serde_json::to_value(&old_kra_archive.document_info)?,
+            &serde_json::to_value(&new_kra_archive.document_info)?,
+            &mut metadata_changes,
+        );
+    }
+
+    let old_nodes = old_kra_archive.map(collect_nodes).transpose()?.unwrap_or_default();
+    let new_nodes = new_kra_archive.map(collect_nodes).transpose()?.unwrap_or_default();
+
+    let mut node_changes = vec![];
+
+    for &uuid in &new_nodes.order {
+        let new_node = &new_nodes.nodes[uuid];
+
+        let Some(old_node) = old_nodes.nodes.get(uuid) else {
+            node_changes.push(NodeChange::Added { uuid, name: new_node.name, node_type: new_node.node_type.clone() });
+            continue;
+        };
+
+        if old_node.name != new_node.name {
+            node_changes.push(NodeChange::Renamed { uuid, old_name: old_node.name, new_name: new_node.name });
+        }
+
+        let moved = old_node.parent != new_node.parent
+            || old_nodes.common_index(uuid, &new_nodes) != new_nodes.common_index(uuid, &old_nodes);
+
+        if moved {
+            node_changes.push(NodeChange::Moved {
+                uuid,
+                name: new_node.name,
+                old_parent: old_node.parent,
+                new_parent: new_node.parent,
+                old_index: old_node.index,
+                new_index: new_node.index,
+            });
+        }
+
+        for ((attribute, old_value), (_, new_value)) in old_node.attributes.iter().zip(&new_node.attributes) {
+            if old_value != new_value {
+                node_changes.push(NodeChange::AttributeChanged {
+                    uuid,
+                    name: new_node.name,
+                    attribute,
+                    old_value: old_value.clone(),
+                    new_value: new_value.clone(),
+                });
+            }
+        }
+
+        diff_keyframes(uuid, old_node, new_node, &mut node_changes)?;
+    }
+
+    for &uuid in &old_nodes.order {
+        if !new_nodes.nodes.contains_key(uuid) {
+            let old_node = &old_nodes.nodes[uuid];
+            node_changes.push(NodeChange::Removed { uuid, name: old_node.name, node_type: old_node.node_type.clone() });
+        }
+    }
+
+    Ok(DocumentDiff { old_path, new_path, document_changes, metadata_changes, node_changes })
+}
+
+/// Image properties of a document which are compared.
+fn document_value(kra_archive: &KraArchive) -> Value {
+    let image = &kra_archive.main_doc.image;
+
+    json!({
+        "width": image.width,
+        "height": image.height,
+        "x_res": image.x_res,
+        "y_res": image.y_res,
+        "colorspace": image.colorspace_name,
+        "profile": image.profile,
+        "framerate": image.animation.as_ref().map(|animation| animation.framerate.value),
+        "frame_range": image.animation.as_ref().map(|animation| [animation.range.from, animation.range.to]),
+    })
+}
+
+/// Collects the differing fields of two values, nested object fields are joined with dots.
+fn diff_values(attribute: String, old_value: &Value, new_value: &Value, changes: &mut Vec<AttributeChange>) {
+    match (old_value, new_value) {
+        (Value::Object(old_fields), Value::Object(new_fields)) => {
+            let field_names = old_fields.keys().chain(new_fields.keys()).collect::<BTreeSet<_>>();
+
+            for field_name in field_names {
+                let field_attribute =
+                    if attribute.is_empty() { field_name.clone() } else { format!("{}.{}", attribute, field_name) };
+
+                diff_values(
+                    field_attribute,
+                    old_fields.get(field_name).unwrap_or(&Value::Null),
+                    new_fields.get(field_name).unwrap_or(&Value::Null),
+                    changes,
+                );
+            }
+        }
+        (old_value, new_value) if old_value != new_value => {
+            changes.push(AttributeChange { attribute, old_value: old_value.clone(), new_value: new_value.clone() });
+        }
+        _ => {}
+    }
+}
+
+fn diff_keyframes<'a>(
+    uuid: &'a str,
+    old_node: &DiffNode<'a>,
+    new_node: &DiffNode<'a>,
+    node_changes: &mut Vec<NodeChange<'a>>,
+) -> Result<(), serde_json::Error> {
+    let keyframe_channels =
+        |diff_node: &DiffNode| -> Result<BTreeMap<String, BTreeMap<usize, Value>>, serde_json::Error> {
+            let mut channels = BTreeMap::new();
+
+            for channel in diff_node.keyframes.iter().flatten() {
+                let keyframes = channel
+                    .keyframes
+                    .iter()
+                    .map(|keyframe| Ok((keyframe.time, serde_json::to_value(keyframe)?)))
+                    .collect::<Result<BTreeMap<_, _>, serde_json::Error>>()?;
+
+                channels.insert(channel.name.clone(), keyframes);
+            }
+
+            Ok(channels)
+        };
+
+    let old_channels = keyframe_channels(old_node)?;
+    let new_channels = keyframe_channels(new_node)?;
+
+    let empty_channel = BTreeMap::new();
+
+    for channel in old_channels.keys().chain(new_channels.keys()).collect::<BTreeSet<_>>() {
+        let old_keyframes = old_channels.get(channel).unwrap_or(&empty_channel);
+        let new_keyframes = new_channels.get(channel).unwrap_or(&empty_channel);
+
+        let added_times =
+            new_keyframes.keys().filter(|time| !old_keyframes.contains_key(time)).copied().collect::<Vec<_>>();
+        let removed_times =
+            old_keyframes.keys().filter(|time| !new_keyframes.contains_key(time)).copied().collect::<Vec<_>>();
+
+        let changed_times = new_keyframes
+            .iter()
+            .filter(|(time, keyframe)| old_keyframes.get(time).is_some_and(|old_keyframe| old_keyframe != *keyframe))
+            .map(|(time, _)| *time)
+            .collect::<Vec<_>>();
+
+        if !added_times.is_empty() || !removed_times.is_empty() || !changed_times.is_empty() {
+            node_changes.push(NodeChange::KeyframesChanged {
+                uuid,
+                name: new_node.name,
+                channel: channel.clone(),
+                added_times,
+                removed_times,
+                changed_times,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn collect_nodes(kra_archive: &KraArchive) -> Result<DiffNodes<'_>, Box<dyn Error>> {
+    let mut diff_nodes = DiffNodes::default();
+    collect_layer_nodes(kra_archive, &kra_archive.main_doc.image.layer_container, None, &mut diff_nodes)?;
+    Ok(diff_nodes)
+}
+
+fn collect_layer_nodes<'a>(
+    kra_archive: &'a KraArchive,
+    layer_container: &'a KraMainDocLayerContainer,
+    parent: Option<&'a str>,
+    diff_nodes: &mut DiffNodes<'a>,
+) -> Result<(), Box<dyn Error>> {
+    for (index, layer) in layer_container.into_iter().enumerate() {
+        diff_nodes.insert(
+            layer.uuid.as_str(),
+            DiffNode {
+                name: &layer.name,
+                node_type: layer.layer_type.to_string(),
+                parent,
+                index,
+                attributes: vec![
+                    ("blending", json!(layer.composite_op)),
+                    ("opacity", json!(layer.opacity)),
+                    ("visible", json!(layer.visible)),
+                    ("colorspace", json!(layer.colorspace_name)),
+                ],
+                keyframes: layer.keyframes(kra_archive)?,
+            },
+        );
+
+        for (index, mask) in layer.mask_container.iter().flatten().enumerate() {
+            diff_nodes.insert(
+                mask.uuid.as_str(),
+                DiffNode {
+                    name: &mask.name,
+                    node_type: mask.mask_type.to_string(),
+                    parent: Some(&layer.uuid),
+                    index,
+                    attributes: vec![
+                        ("blending", json!(mask.composite_op)),
+                        ("visible", json!(mask.visible)),
+                        ("colorspace", json!(mask.colorspace_name)),
+                    ],
+                    keyframes: mask.keyframes(kra_archive)?,
+                },
+            );
+        }
+
+        if let Some(layer_container) = layer.layer_container.as_ref() {
+            collect_layer_nodes(kra_archive, layer_container, Some(&layer.uuid), diff_nodes)?;
+        }
+    }
+
+    Ok(())
+}
+
+impl<'a> DiffNodes<'a> {
+    fn insert(&mut self, uuid: &'a str, diff_node: DiffNode<'a>) {
+        self.children.entry(diff_node.parent).or_default().push(uuid);
+        self.order.push(uuid);
+        self.nodes.insert(uuid, diff_node);
+    }
+
+    /// Position of a node among its siblings which have the same parent in the other document.
+    ///
+    /// Layers added or removed next to a node don't count as moving the node.
+    fn common_index(&self, uuid: &str, other_nodes: &DiffNodes) -> Option<usize> {
+        let parent = self.nodes.get(uuid)?.parent;
+
+        self.children
+            .get(&parent)?
+            .iter()
+            .filter(|sibling| other_nodes.nodes.get(*sibling).is_some_and(|other_node| other_node.parent == parent))
+            .position(|sibling| *sibling == uuid)
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+fn write_document_diff<W: Write>(writer: &mut W, document_diff: &DocumentDiff) -> io::Result<()> {
+    writeln!(writer, "--- {}", document_diff.old_path)?;
+    writeln!(writer, "+++ {}", document_diff.new_path)?;
+
+    if document_diff.document_changes.is_empty()
+        && document_diff.metadata_changes.is_empty()
+        && document_diff.node_changes.is_empty()
+    {
+        writeln!(writer, "no differences")?;
+        return Ok(());
+    }
+
+    for (section, changes) in
+        [("document", &document_diff.document_changes), ("metadata", &document_diff.metadata_changes)]
+    {
+        if !changes.is_empty() {
+            writeln!(writer, "{}:", section)?;
+        }
+
+        for change in changes {
+            writeln!(writer, "  {}: {} → {}", change.attribute, change.old_value, change.new_value)?;
+        }
+    }
+
+    if !document_diff.node_changes.is_empty() {
+        writeln!(writer, "layers:")?;
+    }
+
+    for node_change in &document_diff.node_changes {
+        match node_change {
+            NodeChange::Added { uuid, name, node_type } => {
+                writeln!(writer, "  + {} \"{}\" {}", node_type, name.escape_debug(), uuid)?;
+            }
+            NodeChange::Removed { uuid, name, node_type } => {
+                writeln!(writer, "  - {} \"{}\" {}", node_type, name.escape_debug(), uuid)?;
+            }
+            NodeChange::Renamed { uuid, old_name, new_name } => {
+                writeln!(
+                    writer,
+                    "  ~ \"{}\" {}: renamed from \"{}\"",
+                    new_name.escape_debug(),
+                    uuid,
+                    old_name.escape_debug()
+                )?;
+            }
+            NodeChange::Moved { uuid, name, old_parent, new_parent, old_index, new_index } => {
+                writeln!(
+                    writer,
+                    "  ~ \"{}\" {}: moved from position {} in {} to position {} in {}",
+                    name.escape_debug(),
+                    uuid,
+                    old_index,
+                    old_parent.unwrap_or("document root"),
+                    new_index,
+                    new_parent.unwrap_or("document root"),
+                )?;
+            }
+            NodeChange::AttributeChanged { uuid, name, attribute, old_value, new_value } => {
+                writeln!(
+                    writer,
+                    "  ~ \"{}\" {}: {} {} → {}",
+                    name.escape_debug(),
+                    uuid,
+                    attribute,
+                    old_value,
+                    new_value
+                )?;
+            }
+            NodeChange::KeyframesChanged { uuid, name, channel, added_times, removed_times, changed_times } => {
+                let mut keyframe_changes = vec![];
+
+                for (verb, times) in [("added", added_times), ("removed", removed_times), ("changed", changed_times)] {
+                    if !times.is_empty() {
+                        let times = times.iter().map(|time| time.to_string()).collect::<Vec<_>>();
+                        keyframe_changes.push(format!("{} {}", verb, times.join(", ")));
+                    }
+                }
+
+                writeln!(
+                    writer,
+                    "  ~ \"{}\" {}: keyframes \"{}\" {}",
+                    name.escape_debug(),
+                    uuid,
+                    channel,
+                    keyframe_changes.join("; ")
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
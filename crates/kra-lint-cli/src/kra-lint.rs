@@ -1,17 +1,33 @@
 #![feature(error_iter)]
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::error::Error;
 use std::io::IsTerminal;
+use std::num::NonZeroUsize;
 use std::process::ExitCode;
+use std::sync::mpsc;
+use std::time::Duration;
 
-use camino::Utf8PathBuf;
-use clap::Parser;
+use camino::{Utf8Path, Utf8PathBuf};
+use clap::{Parser, Subcommand};
+use notify_debouncer_mini::new_debouncer;
+use notify_debouncer_mini::notify::{RecursiveMode, Watcher};
 
-use kra_lint_impl::{LintConfigCollection, LintOutputFormat};
+use kra_lint_impl::{
+    LintBaseline, LintCache, LintConfig, LintConfigCollection, LintConfigError, LintGitRepository,
+    LintGitStagedDocuments, LintInspectFormat, LintInspectedDocuments, LintOutputFormat, LintSeverity,
+};
+
+const CACHE_DIRECTORY_NAME: &str = "kra-lint";
+
+const WATCH_DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(500);
 
 #[derive(Parser, Debug)]
-#[command(version)]
+#[command(version, args_conflicts_with_subcommands = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Config file paths
     #[arg(long, short = 'C', value_name = "PATH", env = "KRALINT_CONFIG_PATH")]
     config_paths: Vec<Utf8PathBuf>,
@@ -20,10 +36,101 @@ struct Args {
     #[arg(long, short = 'F', value_name = "FORMAT", env = "KRALINT_OUTPUT_FORMAT")]
     output_format: Option<LintOutputFormat>,
 
-    /// Document paths
+    /// Lowest message severity that makes the run fail
+    #[arg(long, value_name = "SEVERITY", env = "KRALINT_FAIL_ON", default_value = "error")]
+    fail_on: LintSeverity,
+
+    /// Number of documents to lint concurrently
+    #[arg(long, short = 'j', value_name = "N", env = "KRALINT_JOBS", default_value = "1")]
+    jobs: NonZeroUsize,
+
+    /// Apply proposed fixes to the documents before linting them
+    #[arg(long, conflicts_with = "fix_dry_run")]
+    fix: bool,
+
+    /// Print proposed fixes without modifying the documents
+    #[arg(long)]
+    fix_dry_run: bool,
+
+    /// Suppress issues recorded in a baseline file
+    #[arg(long, value_name = "PATH", env = "KRALINT_BASELINE", conflicts_with = "write_baseline")]
+    baseline: Option<Utf8PathBuf>,
+
+    /// Record all current issues in a baseline file
+    #[arg(long, value_name = "PATH")]
+    write_baseline: Option<Utf8PathBuf>,
+
+    /// Print the merged config files as TOML (or save them to PATH) and exit
+    #[arg(long, value_name = "PATH", num_args = 0..=1, require_equals = true)]
+    print_effective_config: Option<Option<Utf8PathBuf>>,
+
+    /// Reuse the results of documents which did not change since a previous run
+    #[arg(long, env = "KRALINT_CACHE")]
+    cache: bool,
+
+    /// Do not reuse results, even if caching was enabled with --cache or KRALINT_CACHE
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Directory of cached results (defaults to "kra-lint" in the user cache directory)
+    #[arg(long, value_name = "PATH", env = "KRALINT_CACHE_DIR")]
+    cache_dir: Option<Utf8PathBuf>,
+
+    /// Remove cached results before linting
+    #[arg(long)]
+    clear_cache: bool,
+
+    /// Keep running and lint documents again whenever they are saved
+    #[arg(long, conflicts_with_all = ["fix", "fix_dry_run", "write_baseline", "print_effective_config"])]
+    watch: bool,
+
+    /// Only lint documents added or modified since a git revision
+    #[arg(long, value_name = "REV", env = "KRALINT_CHANGED_SINCE", conflicts_with = "watch")]
+    changed_since: Option<String>,
+
+    /// Lint the contents staged in the git index instead of the working tree files
+    #[arg(long, requires = "changed_since", conflicts_with_all = ["fix", "fix_dry_run"])]
+    staged: bool,
+
+    /// Report suppressed messages, clean documents and a summary of the run
+    /// (serialized outputs become an object of the documents and the summary instead of an array)
+    #[arg(long, short = 'v')]
+    verbose: bool,
+
+    /// Document paths, directories are searched recursively for documents
+    /// (defaults to the current directory with --changed-since and --watch)
     paths: Vec<Utf8PathBuf>,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Write a config capturing what the reference documents have in common
+    Init {
+        /// Config file to write, its extension selects the config format
+        #[arg(long, short = 'o', value_name = "PATH", default_value = ".kra-lint")]
+        output: Utf8PathBuf,
+
+        /// Overwrite an existing config file
+        #[arg(long)]
+        force: bool,
+
+        /// Reference document paths, directories are searched recursively for documents
+        #[arg(required = true)]
+        paths: Vec<Utf8PathBuf>,
+    },
+
+    /// Print the parsed document model of documents
+    Inspect {
+        /// Output format
+        #[arg(long, short = 'F', value_name = "FORMAT", default_value = "tree")]
+        format: LintInspectFormat,
+
+        /// Document paths
+        #[arg(required = true)]
+        paths: Vec<Utf8PathBuf>,
+    },
+}
+
 fn main() -> ExitCode {
     match main_inner() {
         Ok(exit_code) => exit_code,
@@ -36,38 +143,93 @@ fn main() -> ExitCode {
     }
 }
 
+/// Loads the given config files, or the nearest `.kra-lint` file when none are given.
+fn load_config_collection(config_paths: &[Utf8PathBuf]) -> Result<Option<LintConfigCollection>, Box<dyn Error>> {
+    let mut lint_config_collection = LintConfigCollection::default();
+    let mut config_paths = config_paths.to_vec();
+
+    if config_paths.is_empty() {
+        let search_root: Utf8PathBuf = std::env::current_dir()?.try_into()?;
+
+        for search_directory in search_root.ancestors() {
+            let default_config_path = search_directory.to_owned().join(".kra-lint");
+
+            if default_config_path.is_file() {
+                config_paths.push(default_config_path);
+                break;
+            }
+        }
+    }
+
+    if config_paths.is_empty() {
+        return Ok(None);
+    }
+
+    for lint_config_path in config_paths {
+        eprintln!("kra-lint: Using config file \"{}\"", lint_config_path);
+        lint_config_collection.load_config(&lint_config_path)?
+    }
+
+    Ok(Some(lint_config_collection))
+}
+
+/// Cached results are stored in the user cache directory, outside of the linted document trees.
+fn cache_root(args: &Args) -> Option<Utf8PathBuf> {
+    if let Some(cache_dir) = args.cache_dir.as_ref() {
+        return Some(cache_dir.clone());
+    }
+
+    let user_cache_dir = Utf8PathBuf::from_path_buf(dirs::cache_dir()?).ok()?;
+    Some(user_cache_dir.join(CACHE_DIRECTORY_NAME))
+}
+
+/// Looks up and stores results in the cache, when it was enabled with `--cache` and not disabled with `--no-cache`.
+fn set_cache(args: &Args, lint_config_collection: &mut LintConfigCollection) {
+    if !args.cache || args.no_cache {
+        return;
+    }
+
+    match cache_root(args) {
+        Some(cache_root) => lint_config_collection.set_cache(LintCache::new(&cache_root, lint_config_collection)),
+        None => eprintln!("kra-lint: No user cache directory was found, use --cache-dir to cache results"),
+    }
+}
+
 fn main_inner() -> Result<ExitCode, Box<dyn Error>> {
     let args = Args::try_parse()?;
 
-    let lint_config_collection = {
-        let mut lint_config_collection = LintConfigCollection::default();
-        let mut config_paths = args.config_paths.clone();
+    match args.command.as_ref() {
+        Some(Command::Init { output, force, paths }) => return init_config(output, *force, paths),
+        Some(Command::Inspect { format, paths }) => return inspect_documents(*format, paths),
+        None => {}
+    }
 
-        if config_paths.is_empty() {
-            let search_root: Utf8PathBuf = std::env::current_dir()?.try_into()?;
+    let Some(mut lint_config_collection) = load_config_collection(&args.config_paths)? else {
+        eprintln!("kra-lint: No config files were found");
+        return Ok(ExitCode::FAILURE);
+    };
 
-            for search_directory in search_root.ancestors() {
-                let default_config_path = search_directory.to_owned().join(".kra-lint");
+    if let Some(effective_config_path) = args.print_effective_config.as_ref() {
+        let effective_config = lint_config_collection.effective_config();
 
-                if default_config_path.is_file() {
-                    config_paths.push(default_config_path);
-                    break;
-                }
-            }
+        match effective_config_path {
+            Some(effective_config_path) => effective_config.save_to_path(effective_config_path)?,
+            None => effective_config.save_to_writer(std::io::stdout(), "toml")?,
         }
 
-        if config_paths.is_empty() {
-            eprintln!("kra-lint: No config files were found");
-            return Ok(ExitCode::FAILURE);
-        } else {
-            for lint_config_path in config_paths {
-                eprintln!("kra-lint: Using config file \"{}\"", lint_config_path);
-                lint_config_collection.load_config(&lint_config_path)?
-            }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if args.clear_cache {
+        if let Some(cache_root) = cache_root(&args) {
+            LintCache::clear(&cache_root)?;
+            eprintln!("kra-lint: Cleared cache directory \"{}\"", cache_root);
         }
+    }
 
-        lint_config_collection
-    };
+    set_cache(&args, &mut lint_config_collection);
+
+    let (document_paths, lint_git_staged_documents) = collect_document_paths(&args, &lint_config_collection)?;
 
     let lint_output_format = {
         let default_output_format =
@@ -76,13 +238,341 @@ fn main_inner() -> Result<ExitCode, Box<dyn Error>> {
         args.output_format.unwrap_or(default_output_format)
     };
 
-    let lint_message_collection = lint_config_collection.lint_paths(&args.paths);
+    lint_config_collection.set_keep_previews(lint_output_format.includes_previews());
 
-    lint_message_collection.write_output(&mut std::io::stdout(), lint_output_format)?;
+    if args.fix || args.fix_dry_run {
+        let fix_summary = if args.fix_dry_run { "Proposed fixes" } else { "Applied fixes" };
 
-    if lint_message_collection.is_empty() {
-        Ok(ExitCode::SUCCESS)
+        for kra_path in &document_paths {
+            match lint_config_collection.fix_path(kra_path, args.fix_dry_run) {
+                Ok(lint_fixes) if lint_fixes.is_empty() => {}
+                Ok(lint_fixes) => {
+                    eprintln!("kra-lint: {} for \"{}\"", fix_summary, kra_path);
+                    for lint_fix in lint_fixes {
+                        eprintln!("kra-lint:   {}", lint_fix);
+                    }
+                }
+                Err(err) => {
+                    eprintln!("kra-lint: Failed to fix \"{}\"", kra_path);
+                    for source in (&err as &dyn Error).sources() {
+                        eprintln!("kra-lint: {}", source);
+                    }
+                }
+            }
+        }
+    }
+
+    if args.watch {
+        let lint_baseline = args
+            .baseline
+            .as_ref()
+            .map(|baseline_path| {
+                eprintln!("kra-lint: Using baseline file \"{}\"", baseline_path);
+                LintBaseline::load_from_path(baseline_path)
+            })
+            .transpose()?;
+
+        return watch_paths(&args, lint_config_collection, lint_baseline, lint_output_format);
+    }
+
+    let mut lint_message_collection = match lint_git_staged_documents.as_ref() {
+        Some(lint_git_staged_documents) => {
+            lint_config_collection.lint_staged_documents_parallel(lint_git_staged_documents.documents(), args.jobs)
+        }
+        None => lint_config_collection.lint_paths_parallel(&document_paths, args.jobs),
+    };
+
+    if args.verbose {
+        for entry in lint_message_collection.suppressed() {
+            for message in &entry.messages {
+                eprintln!(
+                    "kra-lint: Suppressed {}: {}[{}]: {}",
+                    entry.path, message.message_severity, message.message_code, message.message_title
+                );
+            }
+        }
+    }
+
+    let lint_baseline = if let Some(baseline_path) = args.write_baseline.as_ref() {
+        let lint_baseline = LintBaseline::from_messages(baseline_path, &lint_message_collection);
+        lint_baseline.save_to_path(baseline_path)?;
+
+        eprintln!("kra-lint: Wrote baseline file \"{}\" ({} issues)", baseline_path, lint_baseline.message_count());
+        Some(lint_baseline)
+    } else if let Some(baseline_path) = args.baseline.as_ref() {
+        eprintln!("kra-lint: Using baseline file \"{}\"", baseline_path);
+        Some(LintBaseline::load_from_path(baseline_path)?)
     } else {
+        None
+    };
+
+    if let Some(lint_baseline) = lint_baseline {
+        let suppressed_count = lint_baseline.suppress(&mut lint_message_collection);
+
+        if suppressed_count > 0 {
+            eprintln!("kra-lint: Suppressed {} issues recorded in the baseline", suppressed_count);
+        }
+    }
+
+    lint_message_collection.write_output(&mut std::io::stdout(), lint_output_format, args.verbose)?;
+
+    if args.verbose {
+        let summary = lint_message_collection.summary();
+
+        eprintln!(
+            "kra-lint: Linted {} documents ({} clean, {} with issues, {} failed to open)",
+            summary.documents, summary.clean_documents, summary.documents_with_issues, summary.failed_documents
+        );
+    }
+
+    if lint_message_collection.max_severity() >= Some(args.fail_on) {
         Ok(ExitCode::FAILURE)
+    } else {
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+/// Paths searched for documents, the current directory when none are given.
+fn document_roots(args: &Args) -> Vec<Utf8PathBuf> {
+    if args.paths.is_empty() {
+        vec![Utf8PathBuf::from(".")]
+    } else {
+        args.paths.clone()
+    }
+}
+
+/// Documents to lint, only the ones changed since the `--changed-since` revision when it is given.
+///
+/// With `--staged`, the staged contents of the changed documents are returned as well.
+fn collect_document_paths(
+    args: &Args,
+    lint_config_collection: &LintConfigCollection,
+) -> Result<(Vec<Utf8PathBuf>, Option<LintGitStagedDocuments>), Box<dyn Error>> {
+    let Some(revision) = args.changed_since.as_ref() else {
+        return Ok((lint_config_collection.collect_document_paths(&args.paths)?, None));
+    };
+
+    let lint_git_repository = LintGitRepository::discover(Utf8Path::new("."))?;
+
+    let document_roots = document_roots(args);
+    let document_paths = lint_config_collection.collect_document_paths(&document_roots)?;
+
+    if args.staged {
+        let lint_git_staged_documents =
+            lint_git_repository.staged_documents(revision, lint_config_collection, &document_roots, &document_paths)?;
+
+        let staged_paths =
+            lint_git_staged_documents.documents().iter().map(|staged_document| staged_document.path.clone()).collect();
+
+        Ok((staged_paths, Some(lint_git_staged_documents)))
+    } else {
+        Ok((lint_git_repository.changed_documents(revision, &document_paths)?, None))
+    }
+}
+
+/// Infers a config from the reference documents and saves it, in the format given by the file extension.
+fn init_config(lint_config_path: &Utf8Path, force: bool, paths: &[Utf8PathBuf]) -> Result<ExitCode, Box<dyn Error>> {
+    if lint_config_path.exists() && !force {
+        eprintln!("kra-lint: Config file \"{}\" already exists, use --force to overwrite it", lint_config_path);
+        return Ok(ExitCode::FAILURE);
     }
+
+    let Some((lint_config, reference_document_count)) = LintConfig::infer_from_paths(paths)? else {
+        eprintln!("kra-lint: No reference documents were found");
+        return Ok(ExitCode::FAILURE);
+    };
+
+    lint_config.save_to_path(lint_config_path)?;
+
+    eprintln!(
+        "kra-lint: Wrote config file \"{}\" ({} reference documents)",
+        lint_config_path, reference_document_count
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
+fn inspect_documents(inspect_format: LintInspectFormat, paths: &[Utf8PathBuf]) -> Result<ExitCode, Box<dyn Error>> {
+    LintInspectedDocuments::from_paths(paths)?.write_output(&mut std::io::stdout().lock(), inspect_format)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Lints documents again whenever they are saved, until interrupted.
+///
+/// Changes to any loaded config file (including included ones) or script reload the configs and lint every
+/// document again.
+fn watch_paths(
+    args: &Args,
+    mut lint_config_collection: LintConfigCollection,
+    lint_baseline: Option<LintBaseline>,
+    lint_output_format: LintOutputFormat,
+) -> Result<ExitCode, Box<dyn Error>> {
+    let (event_sender, event_receiver) = mpsc::channel();
+
+    // Krita saves into a temporary file first and renames it afterwards, these events arrive in quick succession
+    let mut debouncer = new_debouncer(WATCH_DEBOUNCE_TIMEOUT, event_sender)?;
+    let mut watched_directories = BTreeMap::new();
+
+    let document_roots = document_roots(args);
+
+    for document_root in &document_roots {
+        watch_path(debouncer.watcher(), &mut watched_directories, document_root)?;
+    }
+
+    let lint_documents = |lint_config_collection: &LintConfigCollection, document_paths: &[Utf8PathBuf]| {
+        let mut lint_message_collection = lint_config_collection.lint_paths_parallel(document_paths, args.jobs);
+
+        if let Some(lint_baseline) = lint_baseline.as_ref() {
+            lint_baseline.suppress(&mut lint_message_collection);
+        }
+
+        lint_message_collection.write_output(&mut std::io::stdout(), lint_output_format, args.verbose)?;
+
+        eprintln!("kra-lint: Watching for changes, press Ctrl+C to stop");
+        Ok::<_, Box<dyn Error>>(())
+    };
+
+    lint_documents(&lint_config_collection, &lint_config_collection.collect_document_paths(&document_roots)?)?;
+
+    // Errors while watching (e.g. a config file or document being replaced) are reported, the watch goes on
+    loop {
+        let config_paths = lint_config_collection
+            .config_paths()
+            .iter()
+            .map(Utf8PathBuf::as_path)
+            .chain(lint_config_collection.script_paths())
+            .filter_map(|config_path| {
+                watch_path(debouncer.watcher(), &mut watched_directories, config_path)
+                    .map_err(|err| report_watch_error(err.as_ref()))
+                    .ok()
+            })
+            .collect::<BTreeSet<_>>();
+
+        let Ok(events) = event_receiver.recv() else {
+            return Ok(ExitCode::SUCCESS);
+        };
+
+        let events = match events {
+            Ok(events) => events,
+            Err(err) => {
+                report_watch_error(&err);
+                continue;
+            }
+        };
+
+        let changed_paths =
+            events.into_iter().filter_map(|event| Utf8PathBuf::try_from(event.path).ok()).collect::<BTreeSet<_>>();
+
+        let config_changed = config_paths.iter().any(|path| changed_paths.contains(path));
+
+        let document_paths = if config_changed {
+            eprintln!("kra-lint: Config files changed, reloading");
+
+            match load_config_collection(&args.config_paths) {
+                Ok(Some(mut reloaded_config_collection)) => {
+                    set_cache(args, &mut reloaded_config_collection);
+                    reloaded_config_collection.set_keep_previews(lint_output_format.includes_previews());
+                    lint_config_collection = reloaded_config_collection;
+                }
+                Ok(None) => eprintln!("kra-lint: No config files were found, keeping the previous config"),
+                Err(err) => {
+                    for source in err.sources() {
+                        eprintln!("kra-lint: {}", source);
+                    }
+                    eprintln!("kra-lint: Keeping the previous config");
+                }
+            }
+
+            lint_config_collection.collect_document_paths(&document_roots)
+        } else {
+            changed_document_paths(&lint_config_collection, &document_roots, &changed_paths)
+        };
+
+        match document_paths {
+            Ok(document_paths) if document_paths.is_empty() => {}
+            Ok(document_paths) => lint_documents(&lint_config_collection, &document_paths)?,
+            Err(err) => report_watch_error(&err),
+        }
+    }
+}
+
+fn report_watch_error(err: &(dyn Error + 'static)) {
+    for source in err.sources() {
+        eprintln!("kra-lint: {}", source);
+    }
+}
+
+/// Documents among `changed_paths` which are collected from `document_roots`, by their collected paths.
+///
+/// Avoids walking the document roots again on every change, documents in directories are only linted
+/// if they exist and are not ignored by `.kra-lintignore` files.
+fn changed_document_paths(
+    lint_config_collection: &LintConfigCollection,
+    document_roots: &[Utf8PathBuf],
+    changed_paths: &BTreeSet<Utf8PathBuf>,
+) -> Result<Vec<Utf8PathBuf>, LintConfigError> {
+    let mut document_paths = vec![];
+
+    for document_root in document_roots {
+        if !document_root.is_dir() {
+            if watched_file_path(document_root).is_some_and(|path| changed_paths.contains(&path)) {
+                document_paths.push(document_root.clone());
+            }
+
+            continue;
+        }
+
+        let Ok(canonical_root) = document_root.canonicalize_utf8() else {
+            continue;
+        };
+
+        for changed_path in changed_paths {
+            let Ok(relative_path) = changed_path.strip_prefix(&canonical_root) else {
+                continue;
+            };
+
+            let document_path = document_root.join(relative_path);
+
+            if document_path.is_file() && lint_config_collection.is_walked_document(&document_path)? {
+                document_paths.push(document_path);
+            }
+        }
+    }
+
+    Ok(document_paths)
+}
+
+/// Watches a directory recursively, or a file through its parent directory, and returns the watched path.
+///
+/// Saving a file usually replaces it by renaming a temporary file over it, a watch on the file itself would
+/// stop at the first save, so changes to files are told apart by their path instead.
+fn watch_path(
+    watcher: &mut dyn Watcher,
+    watched_directories: &mut BTreeMap<Utf8PathBuf, RecursiveMode>,
+    path: &Utf8Path,
+) -> Result<Utf8PathBuf, Box<dyn Error>> {
+    let (watched_directory, watched_path, recursive_mode) = if path.is_dir() {
+        let watched_directory = path.canonicalize_utf8()?;
+        (watched_directory.clone(), watched_directory, RecursiveMode::Recursive)
+    } else {
+        let watched_path = watched_file_path(path).ok_or_else(|| format!("Failed to watch \"{}\"", path))?;
+        (watched_path.parent().unwrap_or(&watched_path).to_owned(), watched_path, RecursiveMode::NonRecursive)
+    };
+
+    // Directories watched recursively already cover the files they contain
+    let is_watched = watched_directories.get(&watched_directory).is_some_and(|watched_recursive_mode| {
+        *watched_recursive_mode == RecursiveMode::Recursive || recursive_mode == RecursiveMode::NonRecursive
+    });
+
+    if !is_watched {
+        watcher.watch(watched_directory.as_std_path(), recursive_mode)?;
+        watched_directories.insert(watched_directory, recursive_mode);
+    }
+
+    Ok(watched_path)
+}
+
+/// Path of a file through its canonical parent directory, which stays the same when the file is replaced.
+fn watched_file_path(path: &Utf8Path) -> Option<Utf8PathBuf> {
+    let parent = path.parent().filter(|parent| !parent.as_str().is_empty()).unwrap_or(Utf8Path::new("."));
+    Some(parent.canonicalize_utf8().ok()?.join(path.file_name()?))
 }
@@ -1,16 +1,32 @@
 #![allow(clippy::module_inception)]
 
+mod lint_baseline;
+mod lint_cache;
 mod lint_config;
 mod lint_config_fields;
+mod lint_fix;
+mod lint_git;
+mod lint_inspect;
 mod lint_output;
 mod lint_pass;
 
+pub use lint_baseline::LintBaseline;
+pub use lint_cache::LintCache;
 pub use lint_config::{LintConfig, LintConfigCollection};
+pub use lint_fix::LintFix;
+pub use lint_git::{LintGitRepository, LintGitStagedDocument, LintGitStagedDocuments};
+pub use lint_inspect::{LintInspectFormat, LintInspectedDocuments};
 pub use lint_output::{
-    LintMessages, LintMessagesCollection, LintMessagesCollectionEntry, LintMessagesEntry, LintMetadata,
-    LintOutputFormat,
+    LintCode, LintDocumentStatus, LintMessages, LintMessagesCollection, LintMessagesCollectionEntry, LintMessagesEntry,
+    LintMessagesWithSeverity, LintMetadata, LintOutputFormat, LintSeverity, LintSummary,
 };
+pub use lint_pass::{LintPass, LintPassRegistry, LintPassResult};
 
+pub use lint_baseline::LintBaselineError;
+pub use lint_cache::LintCacheError;
 pub use lint_config::LintConfigError;
+pub use lint_fix::LintFixError;
+pub use lint_git::LintGitError;
+pub use lint_inspect::LintInspectError;
 pub use lint_output::LintOutputError;
-pub use lint_pass::LintPassError;
+pub use lint_pass::{LintPassError, LintPassRegistryError};
@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::lint_baseline::LintBaselineError;
+use crate::lint_output::{LintMessagesCollection, LintMessagesEntry, LintMetadata};
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct LintBaselineEntry {
+    path: Utf8PathBuf,
+    code: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    layer_uuid: Option<String>,
+    count: usize,
+}
+
+type LintBaselineKey = (Utf8PathBuf, String, Option<String>);
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+/// Snapshot of known lint messages, keyed by document path, rule code and layer UUID.
+///
+/// Document paths are stored relative to the directory of the baseline file,
+/// so that the baseline stays valid regardless of the working directory.
+#[derive(Debug)]
+pub struct LintBaseline {
+    baseline_root: Utf8PathBuf,
+    entries: BTreeMap<LintBaselineKey, usize>,
+}
+
+impl LintBaseline {
+    pub fn from_messages(baseline_path: &Utf8Path, lint_message_collection: &LintMessagesCollection) -> Self {
+        let mut lint_baseline =
+            LintBaseline { baseline_root: Self::baseline_root(baseline_path), entries: BTreeMap::new() };
+
+        for entry in lint_message_collection {
+            for message in &entry.messages {
+                *lint_baseline.entries.entry(lint_baseline.key(&entry.path, message)).or_default() += 1;
+            }
+        }
+
+        lint_baseline
+    }
+
+    pub fn load_from_path(baseline_path: &Utf8Path) -> Result<Self, LintBaselineError> {
+        let reader = File::open(baseline_path)
+            .map_err(|source| LintBaselineError::FailedToOpenBaseline { path: baseline_path.into(), source })?;
+
+        let baseline_entries: Vec<LintBaselineEntry> = serde_json::from_reader(BufReader::new(reader))
+            .map_err(|source| LintBaselineError::FailedToParseBaseline { path: baseline_path.into(), source })?;
+
+        let mut lint_baseline =
+            LintBaseline { baseline_root: Self::baseline_root(baseline_path), entries: BTreeMap::new() };
+
+        for LintBaselineEntry { path, code, layer_uuid, count } in baseline_entries {
+            *lint_baseline.entries.entry((path, code, layer_uuid)).or_default() += count;
+        }
+
+        Ok(lint_baseline)
+    }
+
+    pub fn save_to_path(&self, baseline_path: &Utf8Path) -> Result<(), LintBaselineError> {
+        let mut writer = File::create(baseline_path)
+            .map(BufWriter::new)
+            .map_err(|source| LintBaselineError::FailedToCreateBaseline { path: baseline_path.into(), source })?;
+
+        let baseline_entries = self
+            .entries
+            .iter()
+            .map(|((path, code, layer_uuid), count)| LintBaselineEntry {
+                path: path.clone(),
+                code: code.clone(),
+                layer_uuid: layer_uuid.clone(),
+                count: *count,
+            })
+            .collect::<Vec<_>>();
+
+        serde_json::to_writer_pretty(&mut writer, &baseline_entries)
+            .map_err(LintBaselineError::FailedToSerializeBaseline)?;
+
+        writer.write_all(b"\n")?;
+        Ok(writer.flush()?)
+    }
+
+    pub fn message_count(&self) -> usize {
+        self.entries.values().sum()
+    }
+
+    /// Removes every message recorded in the baseline, returning the number of suppressed messages.
+    pub fn suppress(&self, lint_message_collection: &mut LintMessagesCollection) -> usize {
+        let mut remaining_entries = self.entries.clone();
+        let message_count = lint_message_collection.message_count();
+
+        lint_message_collection.retain_messages(|path, message| {
+            match remaining_entries.get_mut(&self.key(path, message)) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            }
+        });
+
+        message_count - lint_message_collection.message_count()
+    }
+
+    fn key(&self, kra_path: &Utf8Path, message: &LintMessagesEntry) -> LintBaselineKey {
+        let path = kra_path
+            .canonicalize_utf8()
+            .ok()
+            .and_then(|path| path.strip_prefix(&self.baseline_root).ok().map(Utf8Path::to_owned))
+            .unwrap_or_else(|| kra_path.to_owned());
+
+        let layer_uuid = message.message_metadata.iter().find_map(|metadata| match metadata {
+            LintMetadata::Layer { layer_uuid, .. } => Some(layer_uuid.clone()),
+            _ => None,
+        });
+
+        (path, message.message_code.code().to_owned(), layer_uuid)
+    }
+
+    fn baseline_root(baseline_path: &Utf8Path) -> Utf8PathBuf {
+        let baseline_directory =
+            baseline_path.parent().filter(|parent| !parent.as_str().is_empty()).unwrap_or(Utf8Path::new("."));
+
+        baseline_directory.canonicalize_utf8().unwrap_or_else(|_| baseline_directory.to_owned())
+    }
+}
@@ -0,0 +1,33 @@
+use std::io;
+
+use camino_ext::FormattedPathBuf;
+use derive_more::{Display, Error, From};
+
+#[rustfmt::skip]
+#[non_exhaustive]
+#[derive(Debug, Display, Error, From)]
+pub enum LintBaselineError {
+    #[display(fmt = "Failed to open baseline file \"{path:}\"")]
+    FailedToOpenBaseline {
+        path: FormattedPathBuf,
+        source: io::Error,
+    },
+
+    #[display(fmt = "Failed to create baseline file \"{path:}\"")]
+    FailedToCreateBaseline {
+        path: FormattedPathBuf,
+        source: io::Error,
+    },
+
+    #[display(fmt = "Failed to parse baseline file \"{path:}\"")]
+    FailedToParseBaseline {
+        path: FormattedPathBuf,
+        source: serde_json::Error,
+    },
+
+    #[display(fmt = "Failed to serialize baseline")]
+    FailedToSerializeBaseline(serde_json::Error),
+
+    #[from]
+    IoError(io::Error),
+}
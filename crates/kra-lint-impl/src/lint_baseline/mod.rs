@@ -0,0 +1,5 @@
+mod lint_baseline;
+mod lint_baseline_error;
+
+pub use lint_baseline::LintBaseline;
+pub use lint_baseline_error::LintBaselineError;
@@ -0,0 +1,182 @@
+use std::cmp::Reverse;
+use std::env;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
+use std::time::SystemTime;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tempfile::NamedTempFile;
+
+use crate::lint_cache::LintCacheError;
+use crate::lint_config::LintConfigCollection;
+use crate::lint_output::{LintCode, LintMessages, LintMessagesEntry};
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct LintCacheEntry {
+    messages: Vec<LintMessagesEntry>,
+    suppressed_messages: Vec<LintMessagesEntry>,
+    pass_names: Vec<String>,
+}
+
+const LINT_CACHE_MAX_SIZE: u64 = 64 * 1024 * 1024;
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+/// On-disk store of lint results, keyed by the document and the effective config.
+///
+/// Entries are looked up by a hash of the config files (and the scripts and script modules they reference),
+/// the document path, its permissions and its contents. Files referenced by the document itself (e.g. file
+/// layer sources) are not part of the key, results depending on them are only refreshed with a cleared cache.
+///
+/// The key also covers the running executable (its path, size and modification time), so that results
+/// are not reused by a different build, which may lint documents differently.
+///
+/// Results of configs with plugin passes are never cached, as their behavior is not captured by the config.
+///
+/// The cache is bounded to `LINT_CACHE_MAX_SIZE` bytes, the entries written longest ago are removed first.
+#[derive(Debug)]
+pub struct LintCache {
+    cache_root: Utf8PathBuf,
+    config_hash: Option<String>,
+}
+
+impl LintCache {
+    pub fn new(cache_root: &Utf8Path, lint_config_collection: &LintConfigCollection) -> Self {
+        let effective_config = lint_config_collection.effective_config();
+
+        let config_hash = if effective_config.has_plugins() {
+            None
+        } else {
+            let mut hasher = Sha256::new();
+            hasher.update(env!("CARGO_PKG_VERSION"));
+            hasher.update(executable_identity().unwrap_or_default());
+
+            for lint_config_path in lint_config_collection.config_paths() {
+                hasher.update(lint_config_path.as_str());
+                hasher.update(fs::read(lint_config_path).unwrap_or_default());
+            }
+
+            for script_path in effective_config.script_paths() {
+                hasher.update(script_path.as_str());
+                hasher.update(fs::read(script_path).unwrap_or_default());
+            }
+
+            Some(base16ct::lower::encode_string(&hasher.finalize()))
+        };
+
+        // Pruning is best effort as well, entries which cannot be removed are retried next time
+        let _ = Self::prune(cache_root, LINT_CACHE_MAX_SIZE);
+
+        LintCache { cache_root: cache_root.to_owned(), config_hash }
+    }
+
+    pub fn clear(cache_root: &Utf8Path) -> Result<(), LintCacheError> {
+        if cache_root.is_dir() {
+            fs::remove_dir_all(cache_root)
+                .map_err(|source| LintCacheError::FailedToClearCache { path: cache_root.into(), source })?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes the entries written longest ago until the cache is at most `max_size` bytes large.
+    pub fn prune(cache_root: &Utf8Path, max_size: u64) -> Result<(), LintCacheError> {
+        if !cache_root.is_dir() {
+            return Ok(());
+        }
+
+        let mut entries = vec![];
+
+        for dir_entry in cache_root.read_dir_utf8()? {
+            let dir_entry = dir_entry?;
+
+            if dir_entry.path().extension() == Some("json") {
+                let metadata = dir_entry.metadata()?;
+                entries.push((metadata.modified()?, metadata.len(), dir_entry.into_path()));
+            }
+        }
+
+        // Newest entries first, they are kept as long as they fit
+        entries.sort_by_key(|(modified, ..)| Reverse(*modified));
+
+        let mut cache_size = 0;
+
+        for (_, entry_size, entry_path) in entries {
+            cache_size += entry_size;
+
+            if cache_size > max_size {
+                fs::remove_file(entry_path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Cached results of the document, missing or unreadable entries are treated the same.
+    pub(crate) fn load(&self, kra_path: &Utf8Path) -> Option<LintMessages> {
+        let reader = File::open(self.entry_path(kra_path)?).ok()?;
+        let LintCacheEntry { messages, suppressed_messages, pass_names } =
+            serde_json::from_reader(BufReader::new(reader)).ok()?;
+
+        let pass_names =
+            pass_names.iter().map(|pass_name| LintCode::builtin_pass_name(pass_name)).collect::<Option<_>>()?;
+
+        Some(LintMessages::from_parts(messages, suppressed_messages, pass_names))
+    }
+
+    /// Caching is best effort, results which cannot be stored are linted again next time.
+    pub(crate) fn store(&self, kra_path: &Utf8Path, lint_messages: &LintMessages) {
+        let Some(entry_path) = self.entry_path(kra_path) else {
+            return;
+        };
+
+        let (messages, suppressed_messages, pass_names) = lint_messages.parts();
+
+        let lint_cache_entry = LintCacheEntry {
+            messages: messages.to_vec(),
+            suppressed_messages: suppressed_messages.to_vec(),
+            pass_names: pass_names.iter().map(|pass_name| pass_name.to_string()).collect(),
+        };
+
+        // Written to a temporary file and renamed afterwards, so that other runs never read a partial entry
+        let _ = fs::create_dir_all(&self.cache_root).and_then(|_| NamedTempFile::new_in(&self.cache_root)).and_then(
+            |temp_file| {
+                let mut writer = BufWriter::new(temp_file);
+                serde_json::to_writer(&mut writer, &lint_cache_entry)?;
+
+                let temp_file = writer.into_inner().map_err(|err| err.into_error())?;
+                temp_file.persist(&entry_path).map_err(|err| err.error)?;
+                Ok(())
+            },
+        );
+    }
+
+    fn entry_path(&self, kra_path: &Utf8Path) -> Option<Utf8PathBuf> {
+        let config_hash = self.config_hash.as_ref()?;
+        let canonical_path = kra_path.canonicalize_utf8().ok()?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(config_hash);
+        hasher.update(canonical_path.as_str());
+        #[cfg(unix)]
+        hasher.update(kra_path.metadata().ok()?.mode().to_le_bytes());
+        hasher.update(fs::read(kra_path).ok()?);
+
+        let entry_name = format!("{}.json", base16ct::lower::encode_string(&hasher.finalize()));
+        Some(self.cache_root.join(entry_name))
+    }
+}
+
+/// Identifies the build of the running executable, `None` if it cannot be told.
+fn executable_identity() -> Option<String> {
+    let executable_path = env::current_exe().ok()?;
+    let metadata = executable_path.metadata().ok()?;
+    let modified = metadata.modified().ok()?.duration_since(SystemTime::UNIX_EPOCH).ok()?;
+
+    Some(format!("{}:{}:{}", executable_path.display(), metadata.len(), modified.as_nanos()))
+}
@@ -0,0 +1,18 @@
+use std::io;
+
+use camino_ext::FormattedPathBuf;
+use derive_more::{Display, Error, From};
+
+#[rustfmt::skip]
+#[non_exhaustive]
+#[derive(Debug, Display, Error, From)]
+pub enum LintCacheError {
+    #[display(fmt = "Failed to clear cache directory \"{path:}\"")]
+    FailedToClearCache {
+        path: FormattedPathBuf,
+        source: io::Error,
+    },
+
+    #[from]
+    IoError(io::Error),
+}
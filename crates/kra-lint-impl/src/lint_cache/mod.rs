@@ -0,0 +1,5 @@
+mod lint_cache;
+mod lint_cache_error;
+
+pub use lint_cache::LintCache;
+pub use lint_cache_error::LintCacheError;
@@ -1,26 +1,36 @@
+use std::collections::BTreeSet;
 use std::fs::File;
+use std::io::Write;
 
 use camino::{Utf8Path, Utf8PathBuf};
-use serde::{Deserialize, Serialize};
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use kra_parser::kra_archive::KraArchive;
 
-use crate::lint_config::LintConfigError;
+use crate::lint_config::{LintConfigError, LintConfigMerge, LintConfigUnset, LintPlugin, LintPlugins};
+use crate::lint_config_fields::GlobPattern;
+use crate::lint_git::absolute_path;
 use crate::lint_output::lint_metadata_macros::meta_error;
-use crate::lint_output::LintMessages;
-use crate::lint_pass::{self, LintPass, LintPassResult};
+use crate::lint_output::{LintCode, LintMessages};
+use crate::lint_pass::{self, LintPass, LintPassError, LintPassRegistry, LintPassResult};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct LintIncludes {
     pub(crate) paths: Vec<Utf8PathBuf>,
 }
 
+/// Serialized by hand, see the end of this file.
 #[rustfmt::skip]
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Debug, Clone, Default)]
 pub struct LintConfig {
     pub(crate) includes:      Option<LintIncludes>,
+    pub(crate) extends:       Option<Vec<Utf8PathBuf>>,
+    pub(crate) unset:         Option<Vec<String>>,
+    pub(crate) paths:         Option<Vec<GlobPattern>>,
+    pub(crate) overrides:     Option<Vec<LintConfig>>,
 
     animation:                Option<lint_pass::lint_pass_animation               ::LintPassAnimation             >,
     audio_track:              Option<lint_pass::lint_pass_audio_track             ::LintPassAudioTrack            >,
@@ -41,19 +51,35 @@ pub struct LintConfig {
     prohibit_compositions:    Option<lint_pass::lint_pass_prohibit_compositions   ::LintPassProhibitCompositions  >,
     prohibit_custom_palettes: Option<lint_pass::lint_pass_prohibit_custom_palettes::LintPassProhibitCustomPalettes>,
     prohibit_kseexpr:         Option<lint_pass::lint_pass_prohibit_kseexpr        ::LintPassProhibitKSeExpr       >,
+    script:                   Option<lint_pass::lint_pass_script                  ::LintPassScript                >,
     software_version:         Option<lint_pass::lint_pass_software_version        ::LintPassSoftwareVersion       >,
     surface_names:            Option<lint_pass::lint_pass_surface_names           ::LintPassSurfaceNames          >,
     surface_type:             Option<lint_pass::lint_pass_surface_type            ::LintPassSurfaceType           >,
     vector_layers:            Option<lint_pass::lint_pass_vector_layers           ::LintPassVectorLayers          >,
+
+    // Sections of passes registered through `LintPassRegistry`
+    pub(crate) plugins:       LintPlugins,
+
+    // Config file the config was loaded from, reported when layering overrides fails
+    pub(crate) config_path:   Utf8PathBuf,
 }
 
 impl LintPass for LintConfig {
     fn lint(&self, kra_archive: &KraArchive, lint_messages: &mut LintMessages) -> LintPassResult {
+        if !self.matches_path(&kra_archive.zip_path) {
+            return Ok(());
+        }
+
+        let lint_config =
+            self.layered_config(Some(&kra_archive.zip_path)).map_err(|err| LintPassError::Custom(err.to_string()))?;
+
         macro_rules! lint_pass {
             ($lint_name:ident) => {{
-                if let Some($lint_name) = self.$lint_name.as_ref() {
+                if let Some($lint_name) = lint_config.$lint_name.as_ref() {
+                    lint_messages.record_pass(stringify!($lint_name));
+
                     if let Err(err) = $lint_name.lint(kra_archive, lint_messages) {
-                        lint_messages.push("Error", &[meta_error!(err)]);
+                        lint_messages.push(LintCode::PassError, "Error", &[meta_error!(err)]);
                     }
                 }
             }};
@@ -78,18 +104,91 @@ impl LintPass for LintConfig {
         lint_pass!(prohibit_compositions);
         lint_pass!(prohibit_custom_palettes);
         lint_pass!(prohibit_kseexpr);
+        lint_pass!(script);
         lint_pass!(software_version);
         lint_pass!(surface_names);
         lint_pass!(surface_type);
         lint_pass!(vector_layers);
 
+        for (plugin_name, lint_plugin) in lint_config.plugins.iter() {
+            lint_messages.record_pass(plugin_name);
+
+            if let Err(err) = lint_plugin.lint_pass.lint(kra_archive, lint_messages) {
+                lint_messages.push(LintCode::PassError, "Error", &[meta_error!(err)]);
+            }
+        }
+
         Ok(())
     }
 }
 
 impl LintConfig {
-    #[rustfmt::skip]
+    /// Whether the config applies to the document, `paths` are resolved when the config is loaded.
+    pub(crate) fn matches_path(&self, kra_path: &Utf8Path) -> bool {
+        let Some(path_patterns) = self.paths.as_ref() else {
+            return true;
+        };
+
+        // Staged documents may have been removed from the working tree, they still match by their path
+        let Some(kra_path) = absolute_path(kra_path) else {
+            return false;
+        };
+
+        path_patterns.iter().any(|path_pattern| path_pattern.matches_path(&kra_path))
+    }
+
+    /// Resolves relative `paths` and script paths against the directory of the config file.
+    fn resolve_paths(&mut self, config_root: &Utf8Path) -> Result<(), LintConfigError> {
+        if let Some(path_patterns) = self.paths.as_mut() {
+            for path_pattern in path_patterns {
+                *path_pattern = path_pattern.resolve(config_root);
+            }
+        }
+
+        if let Some(script) = self.script.as_mut() {
+            script.resolve_paths(config_root)?;
+        }
+
+        Ok(())
+    }
+
+    /// Whether the config or any of its overrides has a section of a plugin pass.
+    pub(crate) fn has_plugins(&self) -> bool {
+        !self.plugins.is_empty() || self.overrides.iter().flatten().any(LintConfig::has_plugins)
+    }
+
+    /// Scripts of the config and its overrides, the lint results depend on their contents.
+    pub(crate) fn script_paths(&self) -> impl Iterator<Item = &Utf8Path> {
+        let override_scripts =
+            self.overrides.iter().flatten().filter_map(|lint_override| lint_override.script.as_ref());
+        self.script.iter().chain(override_scripts).flat_map(|script| script.script_paths())
+    }
+
     pub fn load_from_path(lint_config_path: &Utf8Path) -> Result<LintConfig, LintConfigError> {
+        let mut lint_config = Self::parse_from_path(lint_config_path)?;
+
+        // Relative paths are relative to the config file they are defined in
+        let config_root = lint_config_path.parent().filter(|parent| !parent.as_str().is_empty());
+        let config_root = config_root.unwrap_or(Utf8Path::new(".")).canonicalize_utf8()?;
+
+        for lint_override in lint_config.overrides.iter_mut().flatten() {
+            if lint_override.includes.is_some() || lint_override.extends.is_some() || lint_override.overrides.is_some()
+            {
+                return Err(LintConfigError::InvalidConfigOverride { path: lint_config_path.into() });
+            }
+
+            lint_override.resolve_paths(&config_root)?;
+            lint_override.config_path = lint_config_path.into();
+        }
+
+        lint_config.resolve_paths(&config_root)?;
+        lint_config.config_path = lint_config_path.into();
+
+        Ok(lint_config)
+    }
+
+    #[rustfmt::skip]
+    fn parse_from_path(lint_config_path: &Utf8Path) -> Result<LintConfig, LintConfigError> {
         if !lint_config_path.is_file() {
             return Err(LintConfigError::ConfigNotFound { path: lint_config_path.into()});
         }
@@ -155,15 +254,27 @@ impl LintConfig {
         }
     }
 
-    #[rustfmt::skip]
     pub fn save_to_path(&self, lint_config_path: &Utf8Path) -> Result<(), LintConfigError> {
-        #[allow(unused_variables, unused_mut)]
-        let mut writer = File::create(lint_config_path)
+        let writer = File::create(lint_config_path)
             .map_err(|source| LintConfigError::FailedToCreateConfig { path: lint_config_path.into(), source })?;
 
         let lint_config_extension = lint_config_path.extension().unwrap_or("toml").to_lowercase();
 
-        match lint_config_extension.as_str() {
+        self.save_to_writer(writer, &lint_config_extension).map_err(|err| match err {
+            LintConfigError::UnknownConfigOutputFormat { format } => {
+                LintConfigError::UnknownConfigFormat { path: lint_config_path.into(), extension: format }
+            }
+            err => err,
+        })
+    }
+
+    /// Serializes the config in the format of the given file extension, e.g. `toml`.
+    #[rustfmt::skip]
+    pub fn save_to_writer<W>(&self, writer: W, config_format: &str) -> Result<(), LintConfigError> where W: Write {
+        #[allow(unused_variables, unused_mut)]
+        let mut writer = writer;
+
+        match config_format {
             #[cfg(feature = "config-toml")]
             "toml" => {
                 toml_ext::to_writer(writer, self)
@@ -206,9 +317,219 @@ impl LintConfig {
                     .map_err(LintConfigError::FailedToSerializeGuraConfig)
             }
 
-            extension => {
-                Err(LintConfigError::UnknownConfigFormat { path: lint_config_path.into(), extension: extension.to_owned() })
+            config_format => {
+                Err(LintConfigError::UnknownConfigOutputFormat { format: config_format.to_owned() })
             }
         }
     }
 }
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+// Derived implementations cannot combine `deny_unknown_fields` with sections of registered passes,
+// and `#[serde(flatten)]` would require map syntax in RON configs
+/// Config field name, RON only accepts struct fields deserialized as identifiers.
+struct LintConfigFieldName(String);
+
+impl<'de> Deserialize<'de> for LintConfigFieldName {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct LintConfigFieldNameVisitor;
+
+        impl<'de> Visitor<'de> for LintConfigFieldNameVisitor {
+            type Value = LintConfigFieldName;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "field identifier")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(LintConfigFieldName(value.to_owned()))
+            }
+
+            fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(LintConfigFieldName(String::from_utf8_lossy(value).into_owned()))
+            }
+        }
+
+        deserializer.deserialize_identifier(LintConfigFieldNameVisitor)
+    }
+}
+
+macro_rules! lint_config_serde {
+    (meta_fields: [$($meta_field:ident),* $(,)?], pass_fields: [$($pass_field:ident),* $(,)?] $(,)?) => {
+        const LINT_CONFIG_FIELDS: &[&str] = &[$(stringify!($meta_field),)* $(stringify!($pass_field),)*];
+
+        impl Serialize for LintConfig {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                let meta_field_count = [$(self.$meta_field.is_some(),)*].into_iter().filter(|is_some| *is_some).count();
+                let pass_field_count = [$(stringify!($pass_field),)*].len();
+
+                let mut state = serializer
+                    .serialize_struct("LintConfig", meta_field_count + pass_field_count + self.plugins.len())?;
+
+                $(
+                    match self.$meta_field.as_ref() {
+                        Some(value) => state.serialize_field(stringify!($meta_field), value)?,
+                        None => state.skip_field(stringify!($meta_field))?,
+                    }
+                )*
+
+                $(state.serialize_field(stringify!($pass_field), &self.$pass_field)?;)*
+
+                for (pass_name, lint_plugin) in self.plugins.iter() {
+                    state.serialize_field(pass_name, &lint_plugin.pass_config)?;
+                }
+
+                state.end()
+            }
+        }
+
+        impl LintConfig {
+            /// Whether the config has a section of any pass.
+            pub(crate) fn has_passes(&self) -> bool {
+                $(self.$pass_field.is_some() ||)* !self.plugins.is_empty()
+            }
+
+            /// Layers the passes of `lint_config` on top of the passes of this config.
+            pub(crate) fn merge_passes(&mut self, lint_config: LintConfig, lint_config_path: &Utf8Path) -> Result<(), LintConfigError> {
+                $(
+                    match (self.$pass_field.as_mut(), lint_config.$pass_field) {
+                        (Some(merged_pass), Some(lint_pass)) => merged_pass.merge(lint_pass),
+                        (None, Some(lint_pass)) => self.$pass_field = Some(lint_pass),
+                        (_, None) => {}
+                    }
+                )*
+
+                for (pass_name, lint_plugin) in lint_config.plugins {
+                    self.merge_plugin(pass_name, lint_plugin, lint_config_path)?;
+                }
+
+                Ok(())
+            }
+
+            /// Unsets a built-in pass or one of its options, `None` if there is no such pass.
+            pub(crate) fn unset_pass_field(&mut self, pass_name: &str, option_name: Option<&str>) -> Option<LintConfigUnset> {
+                match (pass_name, option_name) {
+                    $(
+                        (stringify!($pass_field), None) => {
+                            self.$pass_field = None;
+                            Some(LintConfigUnset::Removed)
+                        }
+                        (stringify!($pass_field), Some(option_name)) => Some(match self.$pass_field.as_mut() {
+                            Some(lint_pass) => lint_pass.unset_option(option_name),
+                            None => LintConfigUnset::Removed,
+                        }),
+                    )*
+                    _ => None,
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for LintConfig {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                struct LintConfigVisitor;
+
+                impl<'de> Visitor<'de> for LintConfigVisitor {
+                    type Value = LintConfig;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(f, "struct LintConfig")
+                    }
+
+                    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: MapAccess<'de>,
+                    {
+                        let mut lint_config = LintConfig::default();
+                        let mut field_names = BTreeSet::new();
+
+                        while let Some(LintConfigFieldName(field_name)) = map.next_key()? {
+                            if !field_names.insert(field_name.clone()) {
+                                return Err(de::Error::custom(format_args!("duplicate field `{}`", field_name)));
+                            }
+
+                            match field_name.as_str() {
+                                $(stringify!($meta_field) => lint_config.$meta_field = map.next_value()?,)*
+                                $(stringify!($pass_field) => lint_config.$pass_field = map.next_value()?,)*
+                                _ => {
+                                    if !LintPassRegistry::is_registered(&field_name) {
+                                        return Err(de::Error::unknown_field(&field_name, LINT_CONFIG_FIELDS));
+                                    }
+
+                                    let pass_config = map.next_value::<serde_json::Value>()?;
+                                    if pass_config.is_null() {
+                                        continue;
+                                    }
+
+                                    let (pass_name, lint_pass) = LintPassRegistry::create(&field_name, pass_config.clone())
+                                        .expect("Lint pass is registered");
+
+                                    let lint_pass = lint_pass.map_err(|err| {
+                                        de::Error::custom(format_args!("invalid config of pass `{}`: {}", pass_name, err))
+                                    })?;
+
+                                    lint_config.plugins.insert(pass_name, LintPlugin { pass_config, lint_pass: lint_pass.into() });
+                                }
+                            }
+                        }
+
+                        Ok(lint_config)
+                    }
+                }
+
+                deserializer.deserialize_struct("LintConfig", LINT_CONFIG_FIELDS, LintConfigVisitor)
+            }
+        }
+    };
+}
+
+lint_config_serde! {
+    meta_fields: [
+        includes,
+        extends,
+        unset,
+        paths,
+        overrides,
+    ],
+    pass_fields: [
+        animation,
+        audio_track,
+        colorize_mask,
+        colorspace,
+        copyright,
+        document_name,
+        document_size,
+        document_structure,
+        file_layers,
+        file_name,
+        file_permissions,
+        filters,
+        hidden_surface,
+        layer_styles,
+        malformed_document,
+        non_default_blending,
+        prohibit_compositions,
+        prohibit_custom_palettes,
+        prohibit_kseexpr,
+        script,
+        software_version,
+        surface_names,
+        surface_type,
+        vector_layers,
+    ],
+}
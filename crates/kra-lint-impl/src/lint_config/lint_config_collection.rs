@@ -1,16 +1,29 @@
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
 use camino::{Utf8Path, Utf8PathBuf};
+use ignore::WalkBuilder;
 
 use kra_parser::kra_archive::KraArchive;
+use kra_parser::kra_error::KraError;
 
-use crate::lint_config::{LintConfig, LintConfigError};
+use crate::lint_cache::LintCache;
+use crate::lint_config::{LintConfig, LintConfigError, LintIgnore, LintSuppressions, LINT_IGNORE_FILE_NAME};
+use crate::lint_fix::{LintFix, LintFixError};
+use crate::lint_git::{absolute_path, LintGitStagedDocument};
 use crate::lint_output::lint_metadata_macros::meta_error;
-use crate::lint_output::{LintMessages, LintMessagesCollection};
+use crate::lint_output::{LintCode, LintMessages, LintMessagesCollection};
 use crate::lint_pass::{LintPass, LintPassResult};
 
+/// Loaded config files, merged into a single effective config in load order.
 #[derive(Default)]
 pub struct LintConfigCollection {
     lint_config_paths: Vec<Utf8PathBuf>,
-    lint_configs: Vec<LintConfig>,
+    lint_config: LintConfig,
+    lint_cache: Option<LintCache>,
+    keep_previews: bool,
 }
 
 impl LintConfigCollection {
@@ -29,57 +42,170 @@ impl LintConfigCollection {
         let lint_config = LintConfig::load_from_path(&lint_config_path)?;
         self.lint_config_paths.push(lint_config_path.clone());
 
-        if let Some(lint_includes) = lint_config.includes.as_ref() {
-            for include_path in &lint_includes.paths {
-                if include_path.is_absolute() {
-                    if !include_path.is_file() {
-                        return Err(LintConfigError::ConfigIncludeNotFound {
-                            path: include_path.into(),
-                            included_from: lint_config_path.into(),
-                        });
-                    }
-
-                    self.load_config(include_path)?;
-                } else {
-                    // Relative paths are relative to the config file they are defined in
-                    let resolved_include_path =
-                        lint_config_path.parent().expect("Failed to get parent directory").join(include_path);
-
-                    if !resolved_include_path.is_file() {
-                        return Err(LintConfigError::ConfigIncludeNotFound {
-                            path: resolved_include_path.into(),
-                            included_from: lint_config_path.into(),
-                        });
-                    }
-
-                    let resolved_include_path = resolved_include_path.canonicalize_utf8()?;
-                    self.load_config(&resolved_include_path)?;
+        // Included configs are merged first, so that the including config overrides them
+        let include_paths = lint_config.includes.iter().flat_map(|lint_includes| &lint_includes.paths);
+        let extends_paths = lint_config.extends.iter().flatten();
+
+        for include_path in include_paths.chain(extends_paths) {
+            if include_path.is_absolute() {
+                if !include_path.is_file() {
+                    return Err(LintConfigError::ConfigIncludeNotFound {
+                        path: include_path.into(),
+                        included_from: lint_config_path.into(),
+                    });
+                }
+
+                self.load_config(include_path)?;
+            } else {
+                // Relative paths are relative to the config file they are defined in
+                let resolved_include_path =
+                    lint_config_path.parent().expect("Failed to get parent directory").join(include_path);
+
+                if !resolved_include_path.is_file() {
+                    return Err(LintConfigError::ConfigIncludeNotFound {
+                        path: resolved_include_path.into(),
+                        included_from: lint_config_path.into(),
+                    });
+                }
+
+                let resolved_include_path = resolved_include_path.canonicalize_utf8()?;
+                self.load_config(&resolved_include_path)?;
+            }
+        }
+
+        self.lint_config.merge(lint_config, &lint_config_path)
+    }
+
+    /// Every loaded config file, including included ones, in load order.
+    pub fn config_paths(&self) -> &[Utf8PathBuf] {
+        &self.lint_config_paths
+    }
+
+    /// Scripts of the loaded config files and the modules they import, which are compiled when loading them.
+    pub fn script_paths(&self) -> impl Iterator<Item = &Utf8Path> {
+        self.lint_config.script_paths()
+    }
+
+    /// Results are looked up in and stored to `lint_cache`, which must be created after every config was loaded.
+    pub fn set_cache(&mut self, lint_cache: LintCache) {
+        self.lint_cache = Some(lint_cache);
+    }
+
+    /// Keeps the preview image of every linted document along with its messages, e.g. for HTML reports.
+    pub fn set_keep_previews(&mut self, keep_previews: bool) {
+        self.keep_previews = keep_previews;
+    }
+
+    /// The result of merging every loaded config file.
+    pub fn effective_config(&self) -> &LintConfig {
+        &self.lint_config
+    }
+
+    /// Expands directories into the documents they contain, explicitly listed files are kept as they are.
+    ///
+    /// Directories are walked recursively for `*.kra` and `*.krz` documents, skipping Krita autosaves
+    /// and paths matched by `.kra-lintignore` files (inside the walked directories, their parents,
+    /// or next to any loaded config file).
+    pub fn collect_document_paths(&self, paths: &[Utf8PathBuf]) -> Result<Vec<Utf8PathBuf>, LintConfigError> {
+        let lint_ignore = LintIgnore::from_config_paths(&self.lint_config_paths)?;
+        let mut document_paths = vec![];
+
+        for path in paths {
+            if !path.is_dir() {
+                document_paths.push(path.clone());
+                continue;
+            }
+
+            // Config ignore files are matched against canonical paths, walked paths keep the form of `path`
+            let walk_root = path.clone();
+            let canonical_root = path.canonicalize_utf8()?;
+            let lint_ignore = lint_ignore.clone();
+
+            let mut walk_builder = WalkBuilder::new(path);
+            walk_builder
+                .standard_filters(false)
+                .parents(true)
+                .add_custom_ignore_filename(LINT_IGNORE_FILE_NAME)
+                .sort_by_file_name(|file_name_a, file_name_b| file_name_a.cmp(file_name_b))
+                .filter_entry(move |dir_entry| {
+                    let relative_path = dir_entry.path().strip_prefix(&walk_root).unwrap_or(dir_entry.path());
+                    let is_dir = dir_entry.file_type().is_some_and(|file_type| file_type.is_dir());
+
+                    !lint_ignore.is_ignored_by_configs(&canonical_root.as_std_path().join(relative_path), is_dir)
+                });
+
+            for dir_entry in walk_builder.build() {
+                let dir_entry =
+                    dir_entry.map_err(|source| LintConfigError::FailedToWalkDirectory { path: path.into(), source })?;
+
+                if !dir_entry.file_type().is_some_and(|file_type| file_type.is_file()) {
+                    continue;
+                }
+
+                let document_path = Utf8PathBuf::try_from(dir_entry.into_path()).map_err(|err| err.into_io_error())?;
+
+                if is_document_path(&document_path) {
+                    document_paths.push(document_path);
                 }
             }
         }
 
-        self.lint_configs.push(lint_config);
-        Ok(())
+        Ok(document_paths)
+    }
+
+    /// Whether walking a directory would collect `path`, i.e. it is a document which is not ignored
+    /// by any `.kra-lintignore` file. The document does not have to exist (e.g. when it was removed).
+    pub fn is_walked_document(&self, path: &Utf8Path) -> Result<bool, LintConfigError> {
+        if !is_document_path(path) {
+            return Ok(false);
+        }
+
+        let Some(absolute_path) = absolute_path(path) else {
+            return Ok(true);
+        };
+
+        let lint_ignore = LintIgnore::from_config_paths(&self.lint_config_paths)?;
+        Ok(!lint_ignore.is_ignored(&absolute_path)?)
     }
 
     pub fn lint_path<P>(&self, kra_path: &P) -> LintMessages
     where
         P: AsRef<Utf8Path> + Into<Utf8PathBuf>,
     {
-        let mut lint_messages = LintMessages::default();
+        if let Some(mut lint_messages) =
+            self.lint_cache.as_ref().and_then(|lint_cache| lint_cache.load(kra_path.as_ref()))
+        {
+            if self.keep_previews {
+                let kra_archive = KraArchive::from_path(kra_path.as_ref());
+                lint_messages
+                    .set_preview_data(kra_archive.ok().and_then(|kra_archive| kra_archive.preview_image_data().ok()));
+            }
 
-        match KraArchive::from_path(kra_path.as_ref()) {
-            Ok(kra_archive) => match self.lint(&kra_archive, &mut lint_messages) {
-                Ok(()) => {}
-                Err(err) => lint_messages.push("Error", &[meta_error!(err)]),
-            },
-            Err(err) => lint_messages.push("Error", &[meta_error!(err)]),
+            return lint_messages;
+        }
+
+        let lint_messages = self.lint_archive(KraArchive::from_path(kra_path.as_ref()));
+
+        if let Some(lint_cache) = self.lint_cache.as_ref() {
+            lint_cache.store(kra_path.as_ref(), &lint_messages);
         }
 
-        lint_messages.sort_and_dedup();
         lint_messages
     }
 
+    /// Lints the staged contents of a document as if they were found at its path in the working tree.
+    ///
+    /// Path-based checks (e.g. file permissions and file layer sources) still look at the working tree,
+    /// staged contents are never cached.
+    pub fn lint_staged_document(&self, staged_document: &LintGitStagedDocument) -> LintMessages {
+        let kra_archive = KraArchive::from_path(&staged_document.staged_path).map(|mut kra_archive| {
+            kra_archive.zip_path = staged_document.path.clone();
+            kra_archive
+        });
+
+        self.lint_archive(kra_archive)
+    }
+
     pub fn lint_paths<P>(&self, kra_paths: &[P]) -> LintMessagesCollection
     where
         P: AsRef<Utf8Path> + Into<Utf8PathBuf>,
@@ -87,23 +213,151 @@ impl LintConfigCollection {
         let mut lint_message_collection = LintMessagesCollection::default();
 
         for kra_path in kra_paths {
-            let lint_messages = self.lint_path(kra_path);
+            lint_message_collection.push(kra_path.as_ref(), self.lint_path(kra_path));
+        }
+
+        lint_message_collection
+    }
 
-            if !lint_messages.is_empty() {
-                lint_message_collection.push(kra_path.as_ref(), lint_messages);
+    /// Same as `lint_paths`, linting up to `jobs` documents at the same time.
+    ///
+    /// Results are collected in the order of `kra_paths`, regardless of which document finished first.
+    pub fn lint_paths_parallel<P>(&self, kra_paths: &[P], jobs: NonZeroUsize) -> LintMessagesCollection
+    where
+        P: AsRef<Utf8Path> + Into<Utf8PathBuf> + Sync,
+    {
+        lint_parallel(kra_paths, jobs, |kra_path| kra_path.as_ref(), |kra_path| self.lint_path(kra_path))
+    }
+
+    /// Same as `lint_paths_parallel`, linting the staged contents of the documents.
+    pub fn lint_staged_documents_parallel(
+        &self,
+        staged_documents: &[LintGitStagedDocument],
+        jobs: NonZeroUsize,
+    ) -> LintMessagesCollection {
+        lint_parallel(
+            staged_documents,
+            jobs,
+            |staged_document| &staged_document.path,
+            |staged_document| self.lint_staged_document(staged_document),
+        )
+    }
+
+    /// Applies every fix proposed for the document, returning the applied fixes.
+    ///
+    /// Fixes are applied to an in-memory copy of the document, which replaces the
+    /// original file only once it was written out in full (and never with `dry_run`).
+    pub fn fix_path<P>(&self, kra_path: &P, dry_run: bool) -> Result<Vec<LintFix>, LintFixError>
+    where
+        P: AsRef<Utf8Path> + Into<Utf8PathBuf>,
+    {
+        // Documents which cannot be opened are reported by the lint run itself
+        let Ok(mut kra_archive) = KraArchive::from_path(kra_path.as_ref()) else {
+            return Ok(vec![]);
+        };
+
+        let mut lint_messages = LintMessages::default();
+
+        // Pass errors are reported by the following lint run, no fixes are proposed for them
+        let _ = self.lint(&kra_archive, &mut lint_messages);
+        LintSuppressions::from_archive(&kra_archive).suppress(&mut lint_messages);
+        lint_messages.sort_and_dedup();
+
+        let mut lint_fixes = lint_messages.fixes().cloned().collect::<Vec<_>>();
+        lint_fixes.sort();
+        lint_fixes.dedup();
+
+        if lint_fixes.is_empty() {
+            return Ok(lint_fixes);
+        }
+
+        for lint_fix in &lint_fixes {
+            lint_fix.apply(&mut kra_archive)?;
+        }
+
+        if !dry_run {
+            kra_archive.save_to_path(kra_path.as_ref())?;
+        }
+
+        Ok(lint_fixes)
+    }
+
+    fn lint_archive(&self, kra_archive: Result<KraArchive, KraError>) -> LintMessages {
+        let mut lint_messages = LintMessages::default();
+
+        match kra_archive {
+            Ok(kra_archive) => {
+                if let Err(err) = self.lint(&kra_archive, &mut lint_messages) {
+                    lint_messages.push(LintCode::PassError, "Error", &[meta_error!(err)]);
+                }
+
+                LintSuppressions::from_archive(&kra_archive).suppress(&mut lint_messages);
+
+                if self.keep_previews {
+                    lint_messages.set_preview_data(kra_archive.preview_image_data().ok());
+                }
             }
+            Err(err) => lint_messages.push(LintCode::UnreadableDocument, "Error", &[meta_error!(err)]),
         }
 
-        lint_message_collection
+        lint_messages.sort_and_dedup();
+        lint_messages
     }
 }
 
-impl LintPass for LintConfigCollection {
-    fn lint(&self, kra_archive: &KraArchive, lint_messages: &mut LintMessages) -> LintPassResult {
-        for lint_config in &self.lint_configs {
-            lint_config.lint(kra_archive, lint_messages)?;
+/// Krita documents, excluding backups (`*.kra~`) and autosaves (`*-autosave.kra`).
+pub(crate) fn is_document_path(path: &Utf8Path) -> bool {
+    let is_document = matches!(path.extension(), Some("kra" | "krz"));
+    let is_autosave = path.file_stem().is_some_and(|file_stem| file_stem.ends_with("-autosave"));
+
+    is_document && !is_autosave
+}
+
+/// Lints up to `jobs` items at the same time, collecting the results in the order of `items`.
+fn lint_parallel<T, P, L>(items: &[T], jobs: NonZeroUsize, item_path: P, lint_item: L) -> LintMessagesCollection
+where
+    T: Sync,
+    P: Fn(&T) -> &Utf8Path,
+    L: Fn(&T) -> LintMessages + Sync,
+{
+    let next_item_index = AtomicUsize::new(0);
+    let (result_sender, result_receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.get().min(items.len()) {
+            let next_item_index = &next_item_index;
+            let lint_item = &lint_item;
+            let result_sender = result_sender.clone();
+
+            scope.spawn(move || loop {
+                let item_index = next_item_index.fetch_add(1, Ordering::Relaxed);
+
+                let Some(item) = items.get(item_index) else {
+                    break;
+                };
+
+                // Receiver outlives the scope, sending cannot fail
+                let _ = result_sender.send((item_index, lint_item(item)));
+            });
         }
+    });
+
+    drop(result_sender);
+
+    let mut lint_results = result_receiver.into_iter().collect::<Vec<_>>();
+    lint_results.sort_by_key(|(item_index, _)| *item_index);
+
+    let mut lint_message_collection = LintMessagesCollection::default();
 
-        Ok(())
+    for (item_index, lint_messages) in lint_results {
+        lint_message_collection.push(item_path(&items[item_index]), lint_messages);
+    }
+
+    lint_message_collection
+}
+
+impl LintPass for LintConfigCollection {
+    fn lint(&self, kra_archive: &KraArchive, lint_messages: &mut LintMessages) -> LintPassResult {
+        self.lint_config.lint(kra_archive, lint_messages)
     }
 }
@@ -13,6 +13,12 @@ pub enum LintConfigError {
         extension: String,
     },
 
+    #[display(fmt = "Unknown config format \"{format:}\"")]
+    UnknownConfigOutputFormat {
+        #[error(not(source))]
+        format: String,
+    },
+
     #[display(fmt = "Config file not found \"{path:}\"")]
     ConfigNotFound {
         #[error(not(source))]
@@ -25,6 +31,63 @@ pub enum LintConfigError {
         included_from: FormattedPathBuf,
     },
 
+    #[display(fmt = "Config overrides cannot contain includes, extends or nested overrides in config file \"{path:}\"")]
+    InvalidConfigOverride {
+        #[error(not(source))]
+        path: FormattedPathBuf,
+    },
+
+    #[display(fmt = "Failed to read script \"{path:}\"")]
+    FailedToReadScript {
+        path: FormattedPathBuf,
+        source: io::Error,
+    },
+
+    #[display(fmt = "Failed to compile script \"{path:}\"")]
+    FailedToCompileScript {
+        path: FormattedPathBuf,
+        source: Box<rhai::EvalAltResult>,
+    },
+
+    #[display(fmt = "Unknown config field \"{field:}\" to unset in config file \"{path:}\"")]
+    UnknownUnsetField {
+        path: FormattedPathBuf,
+        field: String,
+    },
+
+    #[display(fmt = "Required config field \"{field:}\" cannot be unset in config file \"{path:}\"")]
+    RequiredUnsetField {
+        path: FormattedPathBuf,
+        field: String,
+    },
+
+    #[display(fmt = "Failed to merge config file \"{path:}\"")]
+    FailedToMergeConfig {
+        path: FormattedPathBuf,
+        source: serde_json::Error,
+    },
+
+    #[display(fmt = "Failed to infer config from the reference documents")]
+    FailedToInferConfig(serde_json::Error),
+
+    #[display(fmt = "Failed to open reference document \"{path:}\"")]
+    FailedToOpenReferenceDocument {
+        path: FormattedPathBuf,
+        source: Box<kra_parser::kra_error::KraError>,
+    },
+
+    #[display(fmt = "Failed to read ignore file \"{path:}\"")]
+    FailedToReadIgnoreFile {
+        path: FormattedPathBuf,
+        source: ignore::Error,
+    },
+
+    #[display(fmt = "Failed to walk directory \"{path:}\"")]
+    FailedToWalkDirectory {
+        path: FormattedPathBuf,
+        source: ignore::Error,
+    },
+
     #[display(fmt = "Failed to open config file \"{path:}\"")]
     FailedToOpenConfig {
         path: FormattedPathBuf,
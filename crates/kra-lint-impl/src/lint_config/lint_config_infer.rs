@@ -0,0 +1,216 @@
+use std::collections::BTreeSet;
+
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+
+use camino::Utf8PathBuf;
+
+use kra_parser::kra_archive::KraArchive;
+
+use crate::lint_config::{LintConfig, LintConfigCollection, LintConfigError};
+
+#[rustfmt::skip]
+const LAYER_TYPE_FIELDS: &[&str] = &[
+    "paint_layers", "group_layers", "clone_layers", "vector_layers", "filter_layers", "fill_layers", "file_layers",
+];
+
+#[rustfmt::skip]
+const MASK_TYPE_FIELDS: &[&str] = &[
+    "transparency_masks", "filter_masks", "colorize_masks", "transform_masks", "local_selections",
+];
+
+impl LintConfig {
+    /// Config capturing what the reference documents found in `paths` have in common, along with the
+    /// number of reference documents. `None` if no documents were found.
+    pub fn infer_from_paths(paths: &[Utf8PathBuf]) -> Result<Option<(LintConfig, usize)>, LintConfigError> {
+        let document_paths = LintConfigCollection::default().collect_document_paths(paths)?;
+
+        let kra_archives = document_paths
+            .iter()
+            .map(|kra_path| {
+                KraArchive::from_path(kra_path).map_err(|source| LintConfigError::FailedToOpenReferenceDocument {
+                    path: kra_path.into(),
+                    source: source.into(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if kra_archives.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some((LintConfig::infer_from_archives(&kra_archives)?, kra_archives.len())))
+    }
+
+    /// Config capturing what the reference documents have in common.
+    ///
+    /// Options are only set when every document agrees on their value, except for the document sizes
+    /// and the surface types, which allow every size and type found in any of the documents.
+    pub fn infer_from_archives(kra_archives: &[KraArchive]) -> Result<LintConfig, LintConfigError> {
+        let mut inferred_passes = Map::new();
+
+        // Pass "colorspace"
+        {
+            let mut colorspace = Map::new();
+
+            insert_common(&mut colorspace, "colorspace", kra_archives, |kra_archive| {
+                Some(kra_archive.main_doc.image.colorspace_name.clone())
+            });
+
+            insert_common(&mut colorspace, "profile", kra_archives, |kra_archive| {
+                Some(kra_archive.main_doc.image.profile.clone())
+            });
+
+            insert_common(&mut colorspace, "profile_checksum", kra_archives, |kra_archive| {
+                let document_color_profile = kra_archive.main_doc.image.color_profile(kra_archive).ok()?;
+                Some(base16ct::lower::encode_string(&Sha256::digest(document_color_profile)))
+            });
+
+            insert_section(&mut inferred_passes, "colorspace", colorspace);
+        }
+
+        // Pass "copyright"
+        {
+            let mut copyright = Map::new();
+
+            insert_common(&mut copyright, "copyright_line", kra_archives, |kra_archive| {
+                Some(kra_archive.document_info.about.license.clone())
+            });
+
+            insert_common(&mut copyright, "copyright_disclaimer", kra_archives, |kra_archive| {
+                Some(kra_archive.document_info.about.r#abstract.clone())
+            });
+
+            insert_common(&mut copyright, "studio_name", kra_archives, |kra_archive| {
+                Some(kra_archive.document_info.author.company.clone())
+            });
+
+            insert_common(&mut copyright, "initial_author", kra_archives, |kra_archive| {
+                Some(kra_archive.document_info.about.initial_creator.clone())
+            });
+
+            if !kra_archives.is_empty()
+                && kra_archives.iter().all(|kra_archive| !kra_archive.document_info.author.full_name.is_empty())
+            {
+                copyright.insert("ensure_author_exists".into(), Value::Bool(true));
+            }
+
+            insert_section(&mut inferred_passes, "copyright", copyright);
+        }
+
+        // Pass "document_size"
+        {
+            let mut document_sizes = vec![];
+
+            for kra_archive in kra_archives {
+                let image = &kra_archive.main_doc.image;
+                let document_size = json!({ "width": image.width, "height": image.height, "resolution": image.x_res });
+
+                if !document_sizes.contains(&document_size) {
+                    document_sizes.push(document_size);
+                }
+            }
+
+            if !document_sizes.is_empty() {
+                inferred_passes.insert("document_size".into(), json!({ "document_sizes": document_sizes }));
+            }
+        }
+
+        // Pass "software_version"
+        {
+            let mut software_version = Map::new();
+
+            insert_common(&mut software_version, "software_name", kra_archives, |kra_archive| {
+                Some(kra_archive.main_doc.editor.clone())
+            });
+
+            insert_common(&mut software_version, "software_version", kra_archives, |kra_archive| {
+                Some(kra_archive.main_doc.software_version.clone())
+            });
+
+            insert_common(&mut software_version, "syntax_version", kra_archives, |kra_archive| {
+                Some(kra_archive.main_doc.syntax_version.clone())
+            });
+
+            insert_section(&mut inferred_passes, "software_version", software_version);
+        }
+
+        // Pass "animation"
+        {
+            let mut animation = Map::new();
+
+            let animated_archives =
+                kra_archives.iter().filter(|kra_archive| kra_archive.main_doc.image.animation.is_some());
+
+            insert_common(&mut animation, "framerate", animated_archives, |kra_archive| {
+                kra_archive.main_doc.image.animation.as_ref().map(|animation| animation.framerate.value)
+            });
+
+            insert_section(&mut inferred_passes, "animation", animation);
+        }
+
+        // Pass "surface_type"
+        {
+            let layer_types = kra_archives
+                .iter()
+                .flat_map(|kra_archive| kra_archive.all_layers())
+                .map(|layer| format!("{}s", layer.layer_type))
+                .collect::<BTreeSet<_>>();
+
+            let mask_types = kra_archives
+                .iter()
+                .flat_map(|kra_archive| kra_archive.all_masks())
+                .map(|(_, mask)| format!("{}s", mask.mask_type))
+                .collect::<BTreeSet<_>>();
+
+            if !kra_archives.is_empty() {
+                let allowed_types = |type_fields: &[&str], used_types: &BTreeSet<String>| {
+                    type_fields
+                        .iter()
+                        .map(|type_field| (type_field.to_string(), Value::Bool(used_types.contains(*type_field))))
+                        .collect::<Map<_, _>>()
+                };
+
+                inferred_passes.insert(
+                    "surface_type".into(),
+                    json!({
+                        "layer_types": allowed_types(LAYER_TYPE_FIELDS, &layer_types),
+                        "mask_types": allowed_types(MASK_TYPE_FIELDS, &mask_types),
+                    }),
+                );
+            }
+        }
+
+        serde_json::from_value(Value::Object(inferred_passes)).map_err(LintConfigError::FailedToInferConfig)
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+/// Sets `field` to the value shared by every document, empty strings are treated as unset fields.
+fn insert_common<'a, I, T, F>(section: &mut Map<String, Value>, field: &str, kra_archives: I, f: F)
+where
+    I: IntoIterator<Item = &'a KraArchive>,
+    T: Into<Value> + PartialEq,
+    F: Fn(&KraArchive) -> Option<T>,
+{
+    let mut values = kra_archives.into_iter().map(f);
+
+    let Some(Some(common_value)) = values.next() else {
+        return;
+    };
+
+    if values.all(|value| value.as_ref() == Some(&common_value)) {
+        let common_value = common_value.into();
+
+        if common_value != Value::String(String::new()) {
+            section.insert(field.into(), common_value);
+        }
+    }
+}
+
+fn insert_section(inferred_passes: &mut Map<String, Value>, pass_name: &str, section: Map<String, Value>) {
+    if !section.is_empty() {
+        inferred_passes.insert(pass_name.into(), Value::Object(section));
+    }
+}
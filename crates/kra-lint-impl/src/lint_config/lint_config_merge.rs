@@ -0,0 +1,229 @@
+use std::borrow::Cow;
+
+use camino::Utf8Path;
+use serde_json::Value;
+
+use crate::lint_config::{LintConfig, LintConfigError, LintPlugin};
+use crate::lint_pass::LintPassRegistry;
+
+/// Result of unsetting a single pass option.
+pub(crate) enum LintConfigUnset {
+    Removed,
+    Required,
+    Unknown,
+}
+
+/// Option structs of passes, which are layered option by option when merging config files.
+pub(crate) trait LintConfigMerge {
+    /// Replaces every option of this pass which is set in `other`.
+    fn merge(&mut self, other: Self);
+
+    /// Removes the option `option_name`, as done by `unset = ["pass.option"]`.
+    fn unset_option(&mut self, option_name: &str) -> LintConfigUnset;
+}
+
+/// Implements `LintConfigMerge` for a pass, required options are always replaced and cannot be unset.
+macro_rules! impl_lint_config_merge {
+    ($pass_type:ty, optional: [$($optional:ident),* $(,)?], required: [$($required:ident),* $(,)?] $(,)?) => {
+        impl $crate::lint_config::LintConfigMerge for $pass_type {
+            fn merge(&mut self, other: Self) {
+                $(
+                    if other.$optional.is_some() {
+                        self.$optional = other.$optional;
+                    }
+                )*
+                $(self.$required = other.$required;)*
+            }
+
+            fn unset_option(&mut self, option_name: &str) -> $crate::lint_config::LintConfigUnset {
+                match option_name {
+                    $(stringify!($optional) => {
+                        self.$optional = None;
+                        $crate::lint_config::LintConfigUnset::Removed
+                    })*
+                    $(stringify!($required) => $crate::lint_config::LintConfigUnset::Required,)*
+                    _ => $crate::lint_config::LintConfigUnset::Unknown,
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use impl_lint_config_merge;
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+impl LintConfig {
+    /// Layers `lint_config` on top of this config.
+    ///
+    /// Every pass option set in `lint_config` replaces the current value of that option, `unset`
+    /// removes passes (`"pass"`) or pass options (`"pass.option"`) set by earlier configs.
+    /// Configs restricted by `paths` only apply to matching documents, so they are added as overrides
+    /// along with their `unset`. Every config loaded after the first override is added as an override
+    /// as well, so that overrides and later configs are layered in load order, see `layered_config`.
+    pub(crate) fn merge(
+        &mut self,
+        mut lint_config: LintConfig,
+        lint_config_path: &Utf8Path,
+    ) -> Result<(), LintConfigError> {
+        let config_overrides = lint_config.overrides.take().into_iter().flatten();
+
+        lint_config.includes = None;
+        lint_config.extends = None;
+
+        // Configs which only extend others or only define overrides do not add a layer of their own
+        if lint_config.unset.is_some() || lint_config.has_passes() {
+            if lint_config.paths.is_none() && self.overrides.is_none() {
+                self.merge_layer(lint_config, lint_config_path)?;
+            } else {
+                self.push_override(lint_config, lint_config_path)?;
+            }
+        }
+
+        for config_override in config_overrides {
+            self.push_override(config_override, lint_config_path)?;
+        }
+
+        Ok(())
+    }
+
+    /// The config used for the document at `kra_path`, every override matching the document
+    /// is layered on top of this config in load order (every override if `kra_path` is `None`).
+    pub(crate) fn layered_config(&self, kra_path: Option<&Utf8Path>) -> Result<Cow<'_, LintConfig>, LintConfigError> {
+        let Some(lint_overrides) = self.overrides.as_ref() else {
+            return Ok(Cow::Borrowed(self));
+        };
+
+        let mut lint_config = self.clone();
+        lint_config.overrides = None;
+
+        let lint_overrides = lint_overrides
+            .iter()
+            .filter(|lint_override| kra_path.map_or(true, |kra_path| lint_override.matches_path(kra_path)));
+
+        for lint_override in lint_overrides {
+            let mut lint_override = lint_override.clone();
+            let lint_config_path = std::mem::take(&mut lint_override.config_path);
+
+            // Unset fields were checked when loading the override, plugin options may be missing
+            // in this combination of overrides though
+            for unset_field in lint_override.unset.take().into_iter().flatten() {
+                let _ = lint_config.unset_field(&unset_field, &lint_config_path);
+            }
+
+            lint_config.merge_passes(lint_override, &lint_config_path)?;
+        }
+
+        Ok(Cow::Owned(lint_config))
+    }
+
+    /// Applies `unset` of `lint_layer`, then merges its passes.
+    fn merge_layer(&mut self, mut lint_layer: LintConfig, lint_config_path: &Utf8Path) -> Result<(), LintConfigError> {
+        for unset_field in lint_layer.unset.take().into_iter().flatten() {
+            self.unset_field(&unset_field, lint_config_path)?;
+        }
+
+        self.merge_passes(lint_layer, lint_config_path)
+    }
+
+    /// Adds `lint_override` after the current overrides, once it was checked to merge on top of them.
+    fn push_override(&mut self, lint_override: LintConfig, lint_config_path: &Utf8Path) -> Result<(), LintConfigError> {
+        let mut lint_config = self.layered_config(None)?.into_owned();
+        lint_config.merge_layer(lint_override.clone(), lint_config_path)?;
+
+        self.overrides.get_or_insert_with(Vec::new).push(lint_override);
+        Ok(())
+    }
+
+    fn unset_field(&mut self, unset_field: &str, lint_config_path: &Utf8Path) -> Result<(), LintConfigError> {
+        let (pass_name, option_name) = match unset_field.split_once('.') {
+            Some((pass_name, option_name)) => (pass_name, Some(option_name)),
+            None => (unset_field, None),
+        };
+
+        let lint_config_unset = match self.unset_pass_field(pass_name, option_name) {
+            Some(lint_config_unset) => lint_config_unset,
+            None if LintPassRegistry::is_registered(pass_name) => {
+                self.unset_plugin_field(pass_name, option_name, lint_config_path)?
+            }
+            None => LintConfigUnset::Unknown,
+        };
+
+        match lint_config_unset {
+            LintConfigUnset::Removed => Ok(()),
+            LintConfigUnset::Required => Err(LintConfigError::RequiredUnsetField {
+                path: lint_config_path.into(),
+                field: unset_field.to_owned(),
+            }),
+            LintConfigUnset::Unknown => {
+                Err(LintConfigError::UnknownUnsetField { path: lint_config_path.into(), field: unset_field.to_owned() })
+            }
+        }
+    }
+
+    /// Plugin passes only keep their raw config, so their options are merged as JSON
+    /// and the pass is created again from the merged options.
+    fn unset_plugin_field(
+        &mut self,
+        pass_name: &str,
+        option_name: Option<&str>,
+        lint_config_path: &Utf8Path,
+    ) -> Result<LintConfigUnset, LintConfigError> {
+        let Some(option_name) = option_name else {
+            self.plugins.remove(pass_name);
+            return Ok(LintConfigUnset::Removed);
+        };
+
+        let Some(lint_plugin) = self.plugins.get(pass_name) else {
+            return Ok(LintConfigUnset::Removed);
+        };
+
+        let mut pass_config = lint_plugin.pass_config.clone();
+
+        if !pass_config.as_object_mut().is_some_and(|options| options.remove(option_name).is_some()) {
+            return Ok(LintConfigUnset::Unknown);
+        }
+
+        self.insert_plugin(pass_name, pass_config, lint_config_path)?;
+        Ok(LintConfigUnset::Removed)
+    }
+
+    pub(crate) fn merge_plugin(
+        &mut self,
+        pass_name: &'static str,
+        lint_plugin: LintPlugin,
+        lint_config_path: &Utf8Path,
+    ) -> Result<(), LintConfigError> {
+        let Some(merged_plugin) = self.plugins.remove(pass_name) else {
+            self.plugins.insert(pass_name, lint_plugin);
+            return Ok(());
+        };
+
+        let mut pass_config = merged_plugin.pass_config;
+
+        match (pass_config.as_object_mut(), lint_plugin.pass_config) {
+            (Some(merged_options), Value::Object(options)) => {
+                merged_options.extend(options.into_iter().filter(|(_, option_value)| !option_value.is_null()));
+            }
+            (_, plugin_config) => pass_config = plugin_config,
+        }
+
+        self.insert_plugin(pass_name, pass_config, lint_config_path)
+    }
+
+    fn insert_plugin(
+        &mut self,
+        pass_name: &str,
+        pass_config: Value,
+        lint_config_path: &Utf8Path,
+    ) -> Result<(), LintConfigError> {
+        let (pass_name, lint_pass) =
+            LintPassRegistry::create(pass_name, pass_config.clone()).expect("Lint pass is registered");
+
+        let lint_pass = lint_pass
+            .map_err(|source| LintConfigError::FailedToMergeConfig { path: lint_config_path.into(), source })?;
+
+        self.plugins.insert(pass_name, LintPlugin { pass_config, lint_pass: lint_pass.into() });
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use crate::lint_pass::LintPass;
+
+/// Config section of a registered plugin pass, keeping its raw value for merging and printing.
+#[derive(Clone)]
+pub(crate) struct LintPlugin {
+    pub(crate) pass_config: serde_json::Value,
+    pub(crate) lint_pass: Arc<dyn LintPass>,
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+/// Config sections of passes registered through `LintPassRegistry`, by pass name.
+#[derive(Clone, Default)]
+pub(crate) struct LintPlugins(BTreeMap<&'static str, LintPlugin>);
+
+impl LintPlugins {
+    pub(crate) fn get(&self, pass_name: &str) -> Option<&LintPlugin> {
+        self.0.get(pass_name)
+    }
+
+    pub(crate) fn insert(&mut self, pass_name: &'static str, lint_plugin: LintPlugin) -> Option<LintPlugin> {
+        self.0.insert(pass_name, lint_plugin)
+    }
+
+    pub(crate) fn remove(&mut self, pass_name: &str) -> Option<LintPlugin> {
+        self.0.remove(pass_name)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&'static str, &LintPlugin)> {
+        self.0.iter().map(|(pass_name, lint_plugin)| (*pass_name, lint_plugin))
+    }
+}
+
+impl IntoIterator for LintPlugins {
+    type Item = (&'static str, LintPlugin);
+    type IntoIter = std::collections::btree_map::IntoIter<&'static str, LintPlugin>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl Debug for LintPlugins {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.0.iter().map(|(pass_name, plugin)| (pass_name, &plugin.pass_config))).finish()
+    }
+}
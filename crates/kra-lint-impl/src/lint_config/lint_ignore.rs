@@ -0,0 +1,81 @@
+use std::path::Path;
+
+use camino::Utf8Path;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+
+use crate::lint_config::LintConfigError;
+
+pub(crate) const LINT_IGNORE_FILE_NAME: &str = ".kra-lintignore";
+
+/// Patterns of the `.kra-lintignore` files next to the loaded config files.
+///
+/// Patterns are relative to the directory of their file, regardless of the working directory.
+#[derive(Clone, Default)]
+pub(crate) struct LintIgnore {
+    config_ignores: Vec<Gitignore>,
+}
+
+impl LintIgnore {
+    pub(crate) fn from_config_paths<P>(lint_config_paths: &[P]) -> Result<Self, LintConfigError>
+    where
+        P: AsRef<Utf8Path>,
+    {
+        let mut config_ignores = vec![];
+
+        for lint_config_path in lint_config_paths {
+            let lint_ignore_path = lint_config_path.as_ref().with_file_name(LINT_IGNORE_FILE_NAME);
+
+            if lint_ignore_path.is_file() {
+                config_ignores.push(load_ignore_file(&lint_ignore_path)?);
+            }
+        }
+
+        Ok(LintIgnore { config_ignores })
+    }
+
+    /// Whether the config ignore files match `absolute_path` or one of its parent directories.
+    pub(crate) fn is_ignored_by_configs(&self, absolute_path: &Path, is_dir: bool) -> bool {
+        self.config_ignores.iter().any(|config_ignore| {
+            // Anchored patterns cannot match outside of the directory of their file
+            let ignore_match = if absolute_path.starts_with(config_ignore.path()) {
+                config_ignore.matched_path_or_any_parents(absolute_path, is_dir)
+            } else {
+                config_ignore.matched(absolute_path, is_dir)
+            };
+
+            ignore_match.is_ignore()
+        })
+    }
+
+    /// Whether the file at `absolute_path` is ignored, by the ignore files of its parent directories
+    /// (the closest one with a matching pattern taking precedence) or by the config ignore files.
+    pub(crate) fn is_ignored(&self, absolute_path: &Utf8Path) -> Result<bool, LintConfigError> {
+        for parent_path in absolute_path.ancestors().skip(1) {
+            let lint_ignore_path = parent_path.join(LINT_IGNORE_FILE_NAME);
+
+            if !lint_ignore_path.is_file() {
+                continue;
+            }
+
+            match load_ignore_file(&lint_ignore_path)?.matched_path_or_any_parents(absolute_path, false) {
+                Match::Ignore(_) => return Ok(true),
+                Match::Whitelist(_) => break,
+                Match::None => {}
+            }
+        }
+
+        Ok(self.is_ignored_by_configs(absolute_path.as_std_path(), false))
+    }
+}
+
+fn load_ignore_file(lint_ignore_path: &Utf8Path) -> Result<Gitignore, LintConfigError> {
+    let mut gitignore_builder = GitignoreBuilder::new(lint_ignore_path.parent().unwrap_or(Utf8Path::new("/")));
+
+    let gitignore = match gitignore_builder.add(lint_ignore_path) {
+        Some(err) => Err(err),
+        None => gitignore_builder.build(),
+    };
+
+    gitignore.map_err(|source| LintConfigError::FailedToReadIgnoreFile { path: lint_ignore_path.into(), source })
+}
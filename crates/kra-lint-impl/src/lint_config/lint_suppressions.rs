@@ -0,0 +1,74 @@
+use kra_parser::kra_archive::KraArchive;
+
+use crate::lint_output::{LintMessages, LintMessagesEntry, LintMetadata};
+
+const DIRECTIVE_PREFIX: &str = "kra-lint: allow";
+
+/// Rules allowed by `kra-lint: allow <rule>, ...` directives inside of a document.
+///
+/// Directives in the document keywords or description apply to the whole document,
+/// directives in a layer or mask name only apply to messages about that layer or mask.
+pub(crate) struct LintSuppressions {
+    document_rules: Vec<String>,
+    surface_rules: Vec<(String, Vec<String>)>,
+}
+
+impl LintSuppressions {
+    pub(crate) fn from_archive(kra_archive: &KraArchive) -> Self {
+        let about = &kra_archive.document_info.about;
+
+        let document_rules =
+            [&about.keyword, &about.description].into_iter().flat_map(|text| parse_directives(text)).collect();
+
+        let layer_rules = kra_archive.all_layers().map(|layer| (layer.uuid.clone(), parse_directives(&layer.name)));
+        let mask_rules = kra_archive.all_masks().map(|(_, mask)| (mask.uuid.clone(), parse_directives(&mask.name)));
+
+        let surface_rules = layer_rules.chain(mask_rules).filter(|(_, rules)| !rules.is_empty()).collect();
+
+        LintSuppressions { document_rules, surface_rules }
+    }
+
+    pub(crate) fn suppress(&self, lint_messages: &mut LintMessages) {
+        if self.document_rules.is_empty() && self.surface_rules.is_empty() {
+            return;
+        }
+
+        lint_messages.suppress(|message| self.is_suppressed(message));
+    }
+
+    fn is_suppressed(&self, message: &LintMessagesEntry) -> bool {
+        let matches_any = |rules: &[String]| rules.iter().any(|rule| message.message_code.matches_rule(rule));
+
+        if matches_any(&self.document_rules) {
+            return true;
+        }
+
+        message.message_metadata.iter().any(|metadata| {
+            let surface_uuid = match metadata {
+                LintMetadata::Layer { layer_uuid, .. } => layer_uuid,
+                LintMetadata::Mask { mask_uuid, .. } => mask_uuid,
+                _ => return false,
+            };
+
+            self.surface_rules.iter().any(|(uuid, rules)| uuid == surface_uuid && matches_any(rules))
+        })
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+/// Rules listed after every directive prefix, up to the end of the line, a `;` or a closing bracket.
+///
+/// The prefix has to be followed by whitespace, a `(` or the end of the text,
+/// so that words like `allowed` do not start a directive.
+fn parse_directives(text: &str) -> Vec<String> {
+    text.match_indices(DIRECTIVE_PREFIX)
+        .map(|(index, _)| &text[index + DIRECTIVE_PREFIX.len()..])
+        .filter(|directive| directive.chars().next().map_or(true, |next| next.is_whitespace() || next == '('))
+        .flat_map(|directive| {
+            let directive = directive.split(['\n', ';', ']', ')']).next().unwrap_or_default();
+
+            directive.split([',', '(', ' ', '\t']).filter(|rule| !rule.is_empty()).map(str::to_owned)
+        })
+        .collect()
+}
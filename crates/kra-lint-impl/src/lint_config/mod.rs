@@ -1,7 +1,17 @@
 mod lint_config;
 mod lint_config_collection;
 mod lint_config_error;
+mod lint_config_infer;
+mod lint_config_merge;
+mod lint_config_plugins;
+mod lint_ignore;
+mod lint_suppressions;
 
 pub use lint_config::LintConfig;
+pub(crate) use lint_config_collection::is_document_path;
 pub use lint_config_collection::LintConfigCollection;
 pub use lint_config_error::LintConfigError;
+pub(crate) use lint_config_merge::{impl_lint_config_merge, LintConfigMerge, LintConfigUnset};
+pub(crate) use lint_config_plugins::{LintPlugin, LintPlugins};
+pub(crate) use lint_ignore::{LintIgnore, LINT_IGNORE_FILE_NAME};
+pub(crate) use lint_suppressions::LintSuppressions;
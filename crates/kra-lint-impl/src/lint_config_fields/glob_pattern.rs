@@ -0,0 +1,62 @@
+use std::fmt::{Display, Formatter, Result};
+
+use camino::Utf8Path;
+use glob::{MatchOptions, Pattern, PatternError};
+use serde::{Deserialize, Serialize};
+
+/// Shell-style path pattern, `*` matches within a single path component and `**` across components.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub(crate) struct GlobPattern(Pattern);
+
+impl GlobPattern {
+    pub(crate) fn matches_path(&self, path: &Utf8Path) -> bool {
+        let match_options = MatchOptions { require_literal_separator: true, ..MatchOptions::default() };
+
+        self.0.matches_with(path.as_str(), match_options)
+    }
+
+    /// Pattern matching absolute paths, relative patterns are relative to `root`.
+    pub(crate) fn resolve(&self, root: &Utf8Path) -> GlobPattern {
+        if Utf8Path::new(self.0.as_str()).is_absolute() {
+            return self.clone();
+        }
+
+        // Documents are matched by their canonical paths, which never contain `.` or `..`
+        let (mut root, mut pattern) = (root.to_owned(), self.0.as_str());
+
+        loop {
+            if let Some(rest) = pattern.strip_prefix("./") {
+                pattern = rest;
+            } else if let Some(rest) = pattern.strip_prefix("../") {
+                root.pop();
+                pattern = rest;
+            } else {
+                break;
+            }
+        }
+
+        let pattern = format!("{}/{}", Pattern::escape(root.as_str()), pattern);
+        GlobPattern(Pattern::new(&pattern).expect("Escaped path is a valid pattern"))
+    }
+}
+
+impl TryFrom<String> for GlobPattern {
+    type Error = PatternError;
+
+    fn try_from(pattern: String) -> std::result::Result<Self, Self::Error> {
+        Pattern::new(&pattern).map(GlobPattern)
+    }
+}
+
+impl From<GlobPattern> for String {
+    fn from(pattern: GlobPattern) -> Self {
+        pattern.0.as_str().to_owned()
+    }
+}
+
+impl Display for GlobPattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self.0)
+    }
+}
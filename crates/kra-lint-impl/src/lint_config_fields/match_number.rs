@@ -4,7 +4,7 @@ use std::ops::{Div, Rem};
 use num_traits::{One, Zero};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 #[serde(untagged)]
 pub(crate) enum NumberMatchExpression<T>
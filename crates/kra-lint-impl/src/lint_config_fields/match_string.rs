@@ -1,15 +1,17 @@
 use std::fmt::{Display, Formatter, Result};
 
-use serde::{Deserialize, Serialize};
+use serde::de::{Error, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(deny_unknown_fields)]
+use crate::lint_config_fields::RegexPattern;
+
+#[derive(Debug, Clone, Serialize)]
 #[serde(untagged)]
 pub(crate) enum StringMatchExpression {
     FullMatch(String),
     Regex {
         #[serde(rename = "regex")]
-        pattern: String,
+        pattern: RegexPattern,
     },
     StartsWith {
         #[serde(rename = "starts_with")]
@@ -42,9 +44,7 @@ impl StringMatchExpression {
                 input == pattern
             }
             StringMatchExpression::Regex { pattern } => {
-                let compiled_regex = regex::Regex::new(pattern)
-                    .expect("Failed to compile regular expression");
-                compiled_regex.is_match(input)
+                pattern.is_match(input)
             }
             StringMatchExpression::StartsWith { pattern } => {
                 input.starts_with(pattern)
@@ -66,6 +66,14 @@ impl StringMatchExpression {
             }
         }
     }
+
+    /// The only string matching this expression, if there is exactly one.
+    pub(crate) fn exact_value(&self) -> Option<&str> {
+        match self {
+            StringMatchExpression::FullMatch(pattern) => Some(pattern),
+            _ => None,
+        }
+    }
 }
 
 impl Display for StringMatchExpression {
@@ -102,3 +110,73 @@ impl Display for StringMatchExpression {
         }
     }
 }
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+// Not `#[serde(untagged)]`, which would report an invalid regular expression
+// as the expression matching no variant
+impl<'de> Deserialize<'de> for StringMatchExpression {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(StringMatchExpressionVisitor)
+    }
+}
+
+struct StringMatchExpressionVisitor;
+
+impl StringMatchExpressionVisitor {
+    const KEYS: &'static [&'static str] = &["regex", "starts_with", "ends_with", "contains", "and", "not"];
+}
+
+impl<'de> Visitor<'de> for StringMatchExpressionVisitor {
+    type Value = StringMatchExpression;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result {
+        formatter.write_str("a string, a list of string match expressions, or a map with a single key")
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> std::result::Result<Self::Value, E> {
+        Ok(StringMatchExpression::FullMatch(value.to_owned()))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut expressions = vec![];
+
+        while let Some(expression) = seq.next_element()? {
+            expressions.push(expression);
+        }
+
+        Ok(StringMatchExpression::BinaryOr(expressions))
+    }
+
+    #[rustfmt::skip]
+    fn visit_map<A>(self, mut map: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let Some(key) = map.next_key::<String>()? else {
+            return Err(A::Error::invalid_length(0, &self));
+        };
+
+        let expression = match key.as_str() {
+            "regex"       => StringMatchExpression::Regex      { pattern:     map.next_value()? },
+            "starts_with" => StringMatchExpression::StartsWith { pattern:     map.next_value()? },
+            "ends_with"   => StringMatchExpression::EndsWith   { pattern:     map.next_value()? },
+            "contains"    => StringMatchExpression::Contains   { pattern:     map.next_value()? },
+            "and"         => StringMatchExpression::BinaryAnd  { expressions: map.next_value()? },
+            "not"         => StringMatchExpression::BinaryNot  { expression:  map.next_value()? },
+            key => return Err(A::Error::unknown_field(key, Self::KEYS)),
+        };
+
+        if let Some(key) = map.next_key::<String>()? {
+            return Err(A::Error::custom(format!("unexpected key `{}`, expected a map with a single key", key)));
+        }
+
+        Ok(expression)
+    }
+}
@@ -1,11 +1,18 @@
+mod glob_pattern;
 mod match_generic;
 mod match_number;
 mod match_string;
+mod regex_pattern;
+mod untagged_value;
 mod value_by_layer_type;
 mod value_by_mask_type;
+mod value_with_severity;
 
+pub(crate) use glob_pattern::GlobPattern;
 pub(crate) use match_generic::GenericMatchExpression;
 pub(crate) use match_number::NumberMatchExpression;
 pub(crate) use match_string::StringMatchExpression;
+pub(crate) use regex_pattern::RegexPattern;
 pub(crate) use value_by_layer_type::ValueByLayerType;
 pub(crate) use value_by_mask_type::ValueByMaskType;
+pub(crate) use value_with_severity::ValueWithSeverity;
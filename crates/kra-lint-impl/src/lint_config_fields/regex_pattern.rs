@@ -0,0 +1,40 @@
+use std::fmt::{Display, Formatter, Result};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Regular expression compiled once when the config is loaded,
+/// invalid patterns fail the deserialization of the config.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub(crate) struct RegexPattern {
+    pattern: String,
+    compiled_regex: Regex,
+}
+
+impl RegexPattern {
+    pub(crate) fn is_match(&self, input: &str) -> bool {
+        self.compiled_regex.is_match(input)
+    }
+}
+
+impl TryFrom<String> for RegexPattern {
+    type Error = regex::Error;
+
+    fn try_from(pattern: String) -> std::result::Result<Self, Self::Error> {
+        let compiled_regex = Regex::new(&pattern)?;
+        Ok(RegexPattern { pattern, compiled_regex })
+    }
+}
+
+impl From<RegexPattern> for String {
+    fn from(regex_pattern: RegexPattern) -> Self {
+        regex_pattern.pattern
+    }
+}
+
+impl Display for RegexPattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self.pattern)
+    }
+}
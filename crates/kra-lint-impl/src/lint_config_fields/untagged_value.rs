@@ -0,0 +1,266 @@
+use std::fmt::{Formatter, Result as FmtResult};
+use std::marker::PhantomData;
+
+use serde::de::value::{EnumAccessDeserializer, MapAccessDeserializer, SeqAccessDeserializer};
+use serde::de::{DeserializeSeed, EnumAccess, Error, IntoDeserializer, MapAccess, SeqAccess, Unexpected, Visitor};
+use serde::{Deserialize, Deserializer};
+
+// With `#[serde(untagged)]`, an error in a nested value (such as an invalid regular
+// expression) is replaced by "data did not match any variant", without telling what
+// was wrong. Config fields wrapping any value are told apart by the first key of maps
+// instead, so errors of the wrapped value are reported as they are.
+
+/// Config field wrapping a value, or written as a map with known keys.
+pub(crate) trait UntaggedValue<'de>: Sized {
+    type Value: Deserialize<'de>;
+
+    const EXPECTING: &'static str;
+
+    /// Keys telling a map of this field apart from a wrapped value written as a map.
+    const KEYS: &'static [&'static str];
+
+    fn from_value(value: Self::Value) -> Self;
+
+    /// Deserializes the map of this field, when its first key is one of `KEYS`.
+    fn from_map<A>(map: A) -> Result<Self, A::Error>
+    where
+        A: MapAccess<'de>;
+
+    /// Field without a value, written as `null` or `()` in formats which have it.
+    fn from_none() -> Option<Self> {
+        None
+    }
+}
+
+pub(crate) fn deserialize_untagged_value<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: UntaggedValue<'de>,
+{
+    deserializer.deserialize_any(UntaggedValueVisitor(PhantomData))
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+struct UntaggedValueVisitor<T>(PhantomData<T>);
+
+impl<'de, T> UntaggedValueVisitor<T>
+where
+    T: UntaggedValue<'de>,
+{
+    fn value<D>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::Value::deserialize(WrappedValueDeserializer(deserializer)).map(T::from_value)
+    }
+}
+
+impl<'de, T> Visitor<'de> for UntaggedValueVisitor<T>
+where
+    T: UntaggedValue<'de>,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut Formatter) -> FmtResult {
+        formatter.write_str(T::EXPECTING)
+    }
+
+    fn visit_bool<E: Error>(self, value: bool) -> Result<T, E> {
+        Self::value(value.into_deserializer())
+    }
+
+    fn visit_i64<E: Error>(self, value: i64) -> Result<T, E> {
+        Self::value(value.into_deserializer())
+    }
+
+    fn visit_u64<E: Error>(self, value: u64) -> Result<T, E> {
+        Self::value(value.into_deserializer())
+    }
+
+    fn visit_f64<E: Error>(self, value: f64) -> Result<T, E> {
+        Self::value(value.into_deserializer())
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<T, E> {
+        Self::value(value.into_deserializer())
+    }
+
+    fn visit_string<E: Error>(self, value: String) -> Result<T, E> {
+        Self::value(value.into_deserializer())
+    }
+
+    fn visit_none<E: Error>(self) -> Result<T, E> {
+        T::from_none().ok_or_else(|| E::invalid_type(Unexpected::Option, &self))
+    }
+
+    fn visit_unit<E: Error>(self) -> Result<T, E> {
+        T::from_none().ok_or_else(|| E::invalid_type(Unexpected::Unit, &self))
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<T, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        Self::value(SeqAccessDeserializer::new(seq))
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<T, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        Self::value(EnumAccessDeserializer::new(data))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<T, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let first_key = map.next_key::<String>()?;
+        let is_field_map = first_key.as_deref().is_some_and(|first_key| T::KEYS.contains(&first_key));
+        let map = PeekedMapAccess { first_key, map };
+
+        if is_field_map {
+            T::from_map(map)
+        } else {
+            Self::value(MapAccessDeserializer::new(map))
+        }
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+/// Map of which the first key was already read to tell which variant it is.
+struct PeekedMapAccess<A> {
+    first_key: Option<String>,
+    map: A,
+}
+
+impl<'de, A> MapAccess<'de> for PeekedMapAccess<A>
+where
+    A: MapAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.first_key.take() {
+            Some(first_key) => seed.deserialize(first_key.into_deserializer()).map(Some),
+            None => self.map.next_key_seed(seed),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        self.map.next_value_seed(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.map.size_hint()
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+macro_rules! forward_deserialize {
+    ($($method:ident),* $(,)?) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                self.0.$method(visitor)
+            }
+        )*
+    };
+}
+
+/// Deserializer of a wrapped value which was already read by `UntaggedValueVisitor`.
+///
+/// The deserializers of already read values do not tell newtype structs and options apart
+/// from the value they contain, as the deserializers of config formats do.
+struct WrappedValueDeserializer<D>(D);
+
+impl<'de, D> Deserializer<'de> for WrappedValueDeserializer<D>
+where
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    forward_deserialize! {
+        deserialize_any, deserialize_bool, deserialize_i8, deserialize_i16, deserialize_i32, deserialize_i64,
+        deserialize_u8, deserialize_u16, deserialize_u32, deserialize_u64, deserialize_f32, deserialize_f64,
+        deserialize_char, deserialize_str, deserialize_string, deserialize_bytes, deserialize_byte_buf,
+        deserialize_unit, deserialize_seq, deserialize_map, deserialize_identifier, deserialize_ignored_any,
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_unit_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(self, name: &'static str, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_tuple_struct(name, len, visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_struct(name, fields, visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_enum(name, variants, visitor)
+    }
+}
@@ -1,46 +1,90 @@
-use serde::{Deserialize, Serialize};
+use serde::de::value::MapAccessDeserializer;
+use serde::de::MapAccess;
+use serde::{Deserialize, Deserializer, Serialize};
 
 use kra_parser::kra_main_doc::{KraLayerType, KraMainDocLayer};
 
-#[rustfmt::skip]
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(deny_unknown_fields)]
+use crate::lint_config_fields::untagged_value::{deserialize_untagged_value, UntaggedValue};
+
+#[derive(Debug, Clone, Serialize)]
 #[serde(untagged)]
 pub(crate) enum ValueByLayerType<T> {
     All(Option<T>),
-    ByType {
-        paint_layers:  Option<T>,
-        group_layers:  Option<T>,
-        clone_layers:  Option<T>,
-        vector_layers: Option<T>,
-        filter_layers: Option<T>,
-        fill_layers:   Option<T>,
-        file_layers:   Option<T>,
-    },
+    ByType(ValuesByLayerType<T>),
+}
+
+#[rustfmt::skip]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ValuesByLayerType<T> {
+    paint_layers:  Option<T>,
+    group_layers:  Option<T>,
+    clone_layers:  Option<T>,
+    vector_layers: Option<T>,
+    filter_layers: Option<T>,
+    fill_layers:   Option<T>,
+    file_layers:   Option<T>,
 }
 
 impl<T> ValueByLayerType<T> {
     #[rustfmt::skip]
-    pub(crate) fn get<'a>(&'a self, layer: &KraMainDocLayer) -> (&'a Option<T>, &str) {
-        match *self {
-            ValueByLayerType::All(ref all_layers) => (all_layers, "layer"),
-            ValueByLayerType::ByType {
-                ref paint_layers,
-                ref group_layers,
-                ref clone_layers,
-                ref vector_layers,
-                ref filter_layers,
-                ref fill_layers,
-                ref file_layers,
-            } => match layer.layer_type {
-                KraLayerType::PaintLayer  => (paint_layers,  "paint layer" ),
-                KraLayerType::GroupLayer  => (group_layers,  "group layer" ),
-                KraLayerType::CloneLayer  => (clone_layers,  "clone layer" ),
-                KraLayerType::VectorLayer => (vector_layers, "vector layer"),
-                KraLayerType::FilterLayer => (filter_layers, "filter layer"),
-                KraLayerType::FillLayer   => (fill_layers,   "fill layer"  ),
-                KraLayerType::FileLayer   => (file_layers,   "file layer"  ),
+    pub(crate) fn get<'a>(&'a self, layer: &KraMainDocLayer) -> (&'a Option<T>, &'a str) {
+        match self {
+            ValueByLayerType::All(all_layers) => (all_layers, "layer"),
+            ValueByLayerType::ByType(by_type) => match layer.layer_type {
+                KraLayerType::PaintLayer  => (&by_type.paint_layers,  "paint layer" ),
+                KraLayerType::GroupLayer  => (&by_type.group_layers,  "group layer" ),
+                KraLayerType::CloneLayer  => (&by_type.clone_layers,  "clone layer" ),
+                KraLayerType::VectorLayer => (&by_type.vector_layers, "vector layer"),
+                KraLayerType::FilterLayer => (&by_type.filter_layers, "filter layer"),
+                KraLayerType::FillLayer   => (&by_type.fill_layers,   "fill layer"  ),
+                KraLayerType::FileLayer   => (&by_type.file_layers,   "file layer"  ),
             },
         }
     }
 }
+
+impl<'de, T> UntaggedValue<'de> for ValueByLayerType<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = T;
+
+    const EXPECTING: &'static str = "a value, or a map of values by layer type";
+    const KEYS: &'static [&'static str] = &[
+        "paint_layers",
+        "group_layers",
+        "clone_layers",
+        "vector_layers",
+        "filter_layers",
+        "fill_layers",
+        "file_layers",
+    ];
+
+    fn from_value(value: T) -> Self {
+        ValueByLayerType::All(Some(value))
+    }
+
+    fn from_map<A>(map: A) -> Result<Self, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        ValuesByLayerType::deserialize(MapAccessDeserializer::new(map)).map(ValueByLayerType::ByType)
+    }
+
+    fn from_none() -> Option<Self> {
+        Some(ValueByLayerType::All(None))
+    }
+}
+
+impl<'de, T> Deserialize<'de> for ValueByLayerType<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_untagged_value(deserializer)
+    }
+}
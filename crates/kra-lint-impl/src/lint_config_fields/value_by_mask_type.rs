@@ -1,40 +1,79 @@
-use serde::{Deserialize, Serialize};
+use serde::de::value::MapAccessDeserializer;
+use serde::de::MapAccess;
+use serde::{Deserialize, Deserializer, Serialize};
 
 use kra_parser::kra_main_doc::{KraMainDocMask, KraMaskType};
 
-#[rustfmt::skip]
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(deny_unknown_fields)]
+use crate::lint_config_fields::untagged_value::{deserialize_untagged_value, UntaggedValue};
+
+#[derive(Debug, Clone, Serialize)]
 #[serde(untagged)]
 pub(crate) enum ValueByMaskType<T> {
     All(Option<T>),
-    ByType {
-        transparency_masks: Option<T>,
-        filter_masks:       Option<T>,
-        colorize_masks:     Option<T>,
-        transform_masks:    Option<T>,
-        local_selections:   Option<T>,
-    },
+    ByType(ValuesByMaskType<T>),
+}
+
+#[rustfmt::skip]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ValuesByMaskType<T> {
+    transparency_masks: Option<T>,
+    filter_masks:       Option<T>,
+    colorize_masks:     Option<T>,
+    transform_masks:    Option<T>,
+    local_selections:   Option<T>,
 }
 
 impl<T> ValueByMaskType<T> {
     #[rustfmt::skip]
-    pub(crate) fn get<'a>(&'a self, mask: &KraMainDocMask) -> (&'a Option<T>, &str) {
-        match *self {
-            ValueByMaskType::All(ref all_masks) => (all_masks, "mask"),
-            ValueByMaskType::ByType {
-                ref transparency_masks,
-                ref filter_masks,
-                ref colorize_masks,
-                ref transform_masks,
-                ref local_selections,
-            } => match mask.mask_type {
-                KraMaskType::TransparencyMask => (transparency_masks, "transparency mask"),
-                KraMaskType::FilterMask       => (filter_masks,       "filter mask"      ),
-                KraMaskType::ColorizeMask     => (colorize_masks,     "colorize mask"    ),
-                KraMaskType::TransformMask    => (transform_masks,    "transform mask"   ),
-                KraMaskType::LocalSelection   => (local_selections,   "local selection"  ),
+    pub(crate) fn get<'a>(&'a self, mask: &KraMainDocMask) -> (&'a Option<T>, &'a str) {
+        match self {
+            ValueByMaskType::All(all_masks) => (all_masks, "mask"),
+            ValueByMaskType::ByType(by_type) => match mask.mask_type {
+                KraMaskType::TransparencyMask => (&by_type.transparency_masks, "transparency mask"),
+                KraMaskType::FilterMask       => (&by_type.filter_masks,       "filter mask"      ),
+                KraMaskType::ColorizeMask     => (&by_type.colorize_masks,     "colorize mask"    ),
+                KraMaskType::TransformMask    => (&by_type.transform_masks,    "transform mask"   ),
+                KraMaskType::LocalSelection   => (&by_type.local_selections,   "local selection"  ),
             },
         }
     }
 }
+
+impl<'de, T> UntaggedValue<'de> for ValueByMaskType<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = T;
+
+    const EXPECTING: &'static str = "a value, or a map of values by mask type";
+    const KEYS: &'static [&'static str] =
+        &["transparency_masks", "filter_masks", "colorize_masks", "transform_masks", "local_selections"];
+
+    fn from_value(value: T) -> Self {
+        ValueByMaskType::All(Some(value))
+    }
+
+    fn from_map<A>(map: A) -> Result<Self, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        ValuesByMaskType::deserialize(MapAccessDeserializer::new(map)).map(ValueByMaskType::ByType)
+    }
+
+    fn from_none() -> Option<Self> {
+        Some(ValueByMaskType::All(None))
+    }
+}
+
+impl<'de, T> Deserialize<'de> for ValueByMaskType<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_untagged_value(deserializer)
+    }
+}
@@ -0,0 +1,99 @@
+use std::fmt::{Display, Formatter, Result};
+use std::ops::Deref;
+
+use serde::de::value::MapAccessDeserializer;
+use serde::de::MapAccess;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::lint_config_fields::untagged_value::{deserialize_untagged_value, UntaggedValue};
+use crate::lint_output::LintSeverity;
+
+/// Config value optionally overriding the severity of the messages it results in,
+/// written either as the bare value or as `{ value = ..., severity = "warning" }`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub(crate) enum ValueWithSeverity<T> {
+    WithSeverity { value: T, severity: LintSeverity },
+    Value(T),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WithSeverity<T> {
+    value: T,
+    severity: LintSeverity,
+}
+
+impl<T> ValueWithSeverity<T> {
+    pub(crate) fn value(&self) -> &T {
+        match self {
+            ValueWithSeverity::WithSeverity { value, .. } => value,
+            ValueWithSeverity::Value(value) => value,
+        }
+    }
+
+    pub(crate) fn severity(&self) -> LintSeverity {
+        match self {
+            ValueWithSeverity::WithSeverity { severity, .. } => *severity,
+            ValueWithSeverity::Value(_) => LintSeverity::default(),
+        }
+    }
+}
+
+impl ValueWithSeverity<bool> {
+    /// Severity of the messages of an enabled flag, `None` if the flag is disabled.
+    pub(crate) fn severity_if_enabled(&self) -> Option<LintSeverity> {
+        self.value().then_some(self.severity())
+    }
+}
+
+impl<T> Deref for ValueWithSeverity<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.value()
+    }
+}
+
+impl<T> Display for ValueWithSeverity<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.value().fmt(f)
+    }
+}
+
+impl<'de, T> UntaggedValue<'de> for ValueWithSeverity<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = T;
+
+    const EXPECTING: &'static str = "a value, or a map with a value and a severity";
+    const KEYS: &'static [&'static str] = &["value", "severity"];
+
+    fn from_value(value: T) -> Self {
+        ValueWithSeverity::Value(value)
+    }
+
+    fn from_map<A>(map: A) -> std::result::Result<Self, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let WithSeverity { value, severity } = WithSeverity::deserialize(MapAccessDeserializer::new(map))?;
+        Ok(ValueWithSeverity::WithSeverity { value, severity })
+    }
+}
+
+impl<'de, T> Deserialize<'de> for ValueWithSeverity<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_untagged_value(deserializer)
+    }
+}
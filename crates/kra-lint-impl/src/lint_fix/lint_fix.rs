@@ -0,0 +1,72 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use kra_parser::kra_archive::KraArchive;
+
+use crate::lint_fix::LintFixError;
+
+/// Single correction of a document, proposed by a lint pass alongside its message.
+#[non_exhaustive]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(tag = "fix_type", content = "fix_content")]
+#[serde(rename_all = "snake_case")]
+pub enum LintFix {
+    SetCopyrightLine(String),
+    SetDocumentName(String),
+    DisableOnionSkin { layer_name: String, layer_uuid: String },
+    DisableKeystrokesEditMode { mask_name: String, mask_uuid: String },
+    ResetLayerOpacity { layer_name: String, layer_uuid: String },
+}
+
+impl LintFix {
+    pub fn apply(&self, kra_archive: &mut KraArchive) -> Result<(), LintFixError> {
+        let layer_not_found = |layer_uuid: &String| LintFixError::LayerNotFound { layer_uuid: layer_uuid.clone() };
+        let mask_not_found = |mask_uuid: &String| LintFixError::MaskNotFound { mask_uuid: mask_uuid.clone() };
+
+        match self {
+            LintFix::SetCopyrightLine(copyright_line) => {
+                kra_archive.document_info.about.license = copyright_line.clone();
+            }
+            LintFix::SetDocumentName(document_name) => {
+                kra_archive.document_info.about.title = document_name.clone();
+            }
+            LintFix::DisableOnionSkin { layer_uuid, .. } => {
+                let layer = kra_archive.layer_by_uuid_mut(layer_uuid).ok_or_else(|| layer_not_found(layer_uuid))?;
+                layer.onion_skin = Some(false);
+            }
+            LintFix::DisableKeystrokesEditMode { mask_uuid, .. } => {
+                let mask = kra_archive.mask_by_uuid_mut(mask_uuid).ok_or_else(|| mask_not_found(mask_uuid))?;
+                mask.edit_keystrokes = Some(false);
+            }
+            LintFix::ResetLayerOpacity { layer_uuid, .. } => {
+                let layer = kra_archive.layer_by_uuid_mut(layer_uuid).ok_or_else(|| layer_not_found(layer_uuid))?;
+                layer.opacity = 255;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for LintFix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            LintFix::SetCopyrightLine(ref copyright_line) => {
+                write!(f, "Set copyright line to \"{}\"", copyright_line.escape_debug())
+            }
+            LintFix::SetDocumentName(ref document_name) => {
+                write!(f, "Set document name to \"{}\"", document_name.escape_debug())
+            }
+            LintFix::DisableOnionSkin { ref layer_name, .. } => {
+                write!(f, "Disable onion skin on layer \"{}\"", layer_name.escape_debug())
+            }
+            LintFix::DisableKeystrokesEditMode { ref mask_name, .. } => {
+                write!(f, "Disable key stroke edit mode on mask \"{}\"", mask_name.escape_debug())
+            }
+            LintFix::ResetLayerOpacity { ref layer_name, .. } => {
+                write!(f, "Reset opacity of layer \"{}\" to 100%", layer_name.escape_debug())
+            }
+        }
+    }
+}
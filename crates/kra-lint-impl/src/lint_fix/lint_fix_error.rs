@@ -0,0 +1,21 @@
+use derive_more::{Display, Error, From};
+
+#[rustfmt::skip]
+#[non_exhaustive]
+#[derive(Debug, Display, Error, From)]
+pub enum LintFixError {
+    #[display(fmt = "Layer to fix not found (uuid: {layer_uuid:})")]
+    LayerNotFound {
+        #[error(not(source))]
+        layer_uuid: String,
+    },
+
+    #[display(fmt = "Mask to fix not found (uuid: {mask_uuid:})")]
+    MaskNotFound {
+        #[error(not(source))]
+        mask_uuid: String,
+    },
+
+    #[from]
+    KraError(kra_parser::kra_error::KraError),
+}
@@ -0,0 +1,5 @@
+mod lint_fix;
+mod lint_fix_error;
+
+pub use lint_fix::LintFix;
+pub use lint_fix_error::LintFixError;
@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+use std::fs;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use git2::{Delta, Diff, DiffOptions, Oid, Repository, Tree};
+use tempfile::TempDir;
+
+use crate::lint_config::{is_document_path, LintConfigCollection};
+use crate::lint_git::LintGitError;
+
+/// Local git repository, used to only lint documents which changed since a revision.
+///
+/// Only the repository on disk is read, remotes are never fetched from.
+pub struct LintGitRepository {
+    repository: Repository,
+    workdir: Utf8PathBuf,
+}
+
+/// Document whose staged contents were copied out of the index, so that they can be linted.
+#[derive(Debug)]
+pub struct LintGitStagedDocument {
+    pub path: Utf8PathBuf,
+    pub staged_path: Utf8PathBuf,
+}
+
+/// Staged documents, their staging directory is removed once they are dropped.
+#[derive(Debug)]
+pub struct LintGitStagedDocuments {
+    _staging_dir: TempDir,
+    documents: Vec<LintGitStagedDocument>,
+}
+
+impl LintGitRepository {
+    /// Opens the repository containing `path`, searching its parent directories.
+    pub fn discover(path: &Utf8Path) -> Result<Self, LintGitError> {
+        let repository = Repository::discover(path)
+            .map_err(|source| LintGitError::FailedToOpenRepository { path: path.into(), source })?;
+
+        let workdir = repository
+            .workdir()
+            .ok_or_else(|| LintGitError::MissingWorkingTree { path: path.into() })?
+            .canonicalize()?;
+
+        let workdir = Utf8PathBuf::try_from(workdir).map_err(|err| err.into_io_error())?;
+
+        Ok(LintGitRepository { repository, workdir })
+    }
+
+    /// Documents of `document_paths` which were added or modified in the working tree since `revision`.
+    ///
+    /// Untracked documents count as added, unless they are ignored by git.
+    pub fn changed_documents(
+        &self,
+        revision: &str,
+        document_paths: &[Utf8PathBuf],
+    ) -> Result<Vec<Utf8PathBuf>, LintGitError> {
+        let tree = self.revision_tree(revision)?;
+
+        let mut diff_options = DiffOptions::new();
+        diff_options.include_untracked(true).recurse_untracked_dirs(true);
+
+        let diff = self
+            .repository
+            .diff_tree_to_workdir_with_index(Some(&tree), Some(&mut diff_options))
+            .map_err(|source| LintGitError::FailedToDiffRevision { revision: revision.into(), source })?;
+
+        let changed_files = self.changed_files(&diff)?;
+
+        Ok(select_documents(document_paths, &changed_files).map(|(document_path, _)| document_path.clone()).collect())
+    }
+
+    /// Documents of `document_paths` which were added or modified in the index since `revision`.
+    ///
+    /// Staged documents under `document_roots` which were since removed from the working tree are
+    /// included as well, unless `.kra-lintignore` files of `lint_config_collection` ignore them.
+    /// The staged contents of the documents are written to a temporary staging directory.
+    pub fn staged_documents(
+        &self,
+        revision: &str,
+        lint_config_collection: &LintConfigCollection,
+        document_roots: &[Utf8PathBuf],
+        document_paths: &[Utf8PathBuf],
+    ) -> Result<LintGitStagedDocuments, LintGitError> {
+        let tree = self.revision_tree(revision)?;
+
+        let diff = self
+            .repository
+            .diff_tree_to_index(Some(&tree), None, None)
+            .map_err(|source| LintGitError::FailedToDiffRevision { revision: revision.into(), source })?;
+
+        let changed_files = self.changed_files(&diff)?;
+
+        let selected_documents = select_documents(document_paths, &changed_files)
+            .map(|(document_path, blob_id)| (document_path.clone(), blob_id))
+            .chain(select_removed_documents(lint_config_collection, document_roots, &changed_files)?)
+            .collect::<Vec<_>>();
+
+        let staging_dir = tempfile::Builder::new()
+            .prefix("kra-lint-staged-")
+            .tempdir()
+            .map_err(|source| LintGitError::FailedToCreateStagingDirectory { source })?;
+
+        let staging_root = Utf8PathBuf::try_from(staging_dir.path().to_owned()).map_err(|err| err.into_io_error())?;
+
+        let mut documents = vec![];
+
+        for (index, (document_path, blob_id)) in selected_documents.into_iter().enumerate() {
+            let blob = self.repository.find_blob(*blob_id).map_err(|source| {
+                LintGitError::FailedToReadStagedDocument { path: document_path.as_path().into(), source }
+            })?;
+
+            // Keeping the file name, which is part of some error messages
+            let staged_path = staging_root.join(index.to_string()).join(document_path.file_name().unwrap_or_default());
+
+            fs::create_dir_all(staged_path.parent().unwrap_or(&staging_root))
+                .and_then(|()| fs::write(&staged_path, blob.content()))
+                .map_err(|source| LintGitError::FailedToWriteStagedDocument {
+                    path: document_path.as_path().into(),
+                    source,
+                })?;
+
+            documents.push(LintGitStagedDocument { path: document_path, staged_path });
+        }
+
+        Ok(LintGitStagedDocuments { _staging_dir: staging_dir, documents })
+    }
+
+    fn revision_tree(&self, revision: &str) -> Result<Tree<'_>, LintGitError> {
+        self.repository
+            .revparse_single(revision)
+            .and_then(|object| object.peel_to_tree())
+            .map_err(|source| LintGitError::FailedToResolveRevision { revision: revision.into(), source })
+    }
+
+    /// Added and modified files of the diff by their absolute paths, along with the IDs of their new contents.
+    fn changed_files(&self, diff: &Diff) -> Result<HashMap<Utf8PathBuf, Oid>, LintGitError> {
+        let mut changed_files = HashMap::new();
+
+        for diff_delta in diff.deltas() {
+            if !matches!(
+                diff_delta.status(),
+                Delta::Added | Delta::Modified | Delta::Renamed | Delta::Copied | Delta::Typechange | Delta::Untracked
+            ) {
+                continue;
+            }
+
+            let Some(file_path) = diff_delta.new_file().path() else {
+                continue;
+            };
+
+            let file_path = Utf8PathBuf::try_from(file_path.to_owned()).map_err(|err| err.into_io_error())?;
+            changed_files.insert(self.workdir.join(file_path), diff_delta.new_file().id());
+        }
+
+        Ok(changed_files)
+    }
+}
+
+impl LintGitStagedDocuments {
+    pub fn documents(&self) -> &[LintGitStagedDocument] {
+        &self.documents
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+/// Document paths found among the changed files, in the order of `document_paths`.
+fn select_documents<'a, T>(
+    document_paths: &'a [Utf8PathBuf],
+    changed_files: &'a HashMap<Utf8PathBuf, T>,
+) -> impl Iterator<Item = (&'a Utf8PathBuf, &'a T)> {
+    document_paths.iter().filter_map(|document_path| {
+        let canonical_path = document_path.canonicalize_utf8().ok()?;
+        changed_files.get(&canonical_path).map(|changed_file| (document_path, changed_file))
+    })
+}
+
+/// Changed documents under `document_roots` which are missing from the working tree, sorted by path.
+///
+/// Their paths are the ones they would have when walking `document_roots`, documents inside of directories
+/// are skipped when walking would skip them (i.e. autosaves and documents ignored by `.kra-lintignore` files).
+fn select_removed_documents<'a, T>(
+    lint_config_collection: &LintConfigCollection,
+    document_roots: &[Utf8PathBuf],
+    changed_files: &'a HashMap<Utf8PathBuf, T>,
+) -> Result<Vec<(Utf8PathBuf, &'a T)>, LintGitError> {
+    let absolute_roots = document_roots
+        .iter()
+        .filter_map(|document_root| Some((document_root, absolute_path(document_root)?)))
+        .collect::<Vec<_>>();
+
+    let mut removed_documents = vec![];
+
+    for (file_path, changed_file) in changed_files {
+        if !is_document_path(file_path) || file_path.symlink_metadata().is_ok() {
+            continue;
+        }
+
+        let Some((document_root, relative_path)) = absolute_roots.iter().find_map(|(document_root, absolute_root)| {
+            Some((document_root, file_path.strip_prefix(absolute_root).ok()?))
+        }) else {
+            continue;
+        };
+
+        // Explicitly listed documents are never ignored
+        if relative_path.as_str().is_empty() {
+            removed_documents.push(((*document_root).clone(), changed_file));
+            continue;
+        }
+
+        let document_path = document_root.join(relative_path);
+
+        let is_walked_document = lint_config_collection.is_walked_document(&document_path).map_err(|source| {
+            LintGitError::FailedToMatchIgnoreFiles { path: document_path.as_path().into(), source: source.into() }
+        })?;
+
+        if is_walked_document {
+            removed_documents.push((document_path, changed_file));
+        }
+    }
+
+    removed_documents.sort_by(|(document_path_a, _), (document_path_b, _)| document_path_a.cmp(document_path_b));
+    Ok(removed_documents)
+}
+
+/// Canonical path of `path`, or of its closest existing parent directory when `path` itself does not exist.
+pub(crate) fn absolute_path(path: &Utf8Path) -> Option<Utf8PathBuf> {
+    if let Ok(canonical_path) = path.canonicalize_utf8() {
+        return Some(canonical_path);
+    }
+
+    let parent_path = path.parent().filter(|parent_path| !parent_path.as_str().is_empty());
+    let absolute_parent_path = match parent_path {
+        Some(parent_path) => absolute_path(parent_path)?,
+        None => Utf8Path::new(".").canonicalize_utf8().ok()?,
+    };
+
+    Some(absolute_parent_path.join(path.file_name()?))
+}
@@ -0,0 +1,61 @@
+use std::io;
+
+use camino_ext::FormattedPathBuf;
+use derive_more::{Display, Error, From};
+
+use crate::lint_config::LintConfigError;
+
+#[rustfmt::skip]
+#[non_exhaustive]
+#[derive(Debug, Display, Error, From)]
+pub enum LintGitError {
+    #[display(fmt = "Failed to open git repository containing \"{path:}\"")]
+    FailedToOpenRepository {
+        path: FormattedPathBuf,
+        source: git2::Error,
+    },
+
+    #[display(fmt = "Git repository \"{path:}\" has no working tree")]
+    MissingWorkingTree {
+        #[error(not(source))]
+        path: FormattedPathBuf,
+    },
+
+    #[display(fmt = "Failed to resolve git revision \"{revision:}\"")]
+    FailedToResolveRevision {
+        revision: String,
+        source: git2::Error,
+    },
+
+    #[display(fmt = "Failed to compare against git revision \"{revision:}\"")]
+    FailedToDiffRevision {
+        revision: String,
+        source: git2::Error,
+    },
+
+    #[display(fmt = "Failed to create a staging directory for staged documents")]
+    FailedToCreateStagingDirectory {
+        source: io::Error,
+    },
+
+    #[display(fmt = "Failed to read staged contents of \"{path:}\"")]
+    FailedToReadStagedDocument {
+        path: FormattedPathBuf,
+        source: git2::Error,
+    },
+
+    #[display(fmt = "Failed to match ignore files against removed document \"{path:}\"")]
+    FailedToMatchIgnoreFiles {
+        path: FormattedPathBuf,
+        source: Box<LintConfigError>,
+    },
+
+    #[display(fmt = "Failed to write staged contents of \"{path:}\"")]
+    FailedToWriteStagedDocument {
+        path: FormattedPathBuf,
+        source: io::Error,
+    },
+
+    #[from]
+    IoError(io::Error),
+}
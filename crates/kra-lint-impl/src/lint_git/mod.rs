@@ -0,0 +1,6 @@
+mod lint_git;
+mod lint_git_error;
+
+pub(crate) use lint_git::absolute_path;
+pub use lint_git::{LintGitRepository, LintGitStagedDocument, LintGitStagedDocuments};
+pub use lint_git_error::LintGitError;
@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::Serialize;
+
+use kra_parser::kra_archive::KraArchive;
+use kra_parser::kra_document_info::KraDocumentInfo;
+use kra_parser::kra_error::KraError;
+use kra_parser::kra_filter_params::KraFilterParamsContainer;
+use kra_parser::kra_keyframes::KraKeyframesDocument;
+use kra_parser::kra_main_doc::{KraLayerType, KraMainDoc, KraMaskType};
+
+use crate::lint_inspect::{LintInspectError, LintInspectFormat};
+
+/// Parsed document, along with the keyframes and filter params of its layers and masks keyed by their UUIDs.
+#[derive(Serialize)]
+pub(crate) struct LintInspectedDocument<'a> {
+    pub(crate) path: &'a Utf8Path,
+    pub(crate) document_info: &'a KraDocumentInfo,
+    pub(crate) main_doc: &'a KraMainDoc,
+    pub(crate) keyframes: BTreeMap<&'a str, KraKeyframesDocument>,
+    pub(crate) filter_params: BTreeMap<&'a str, KraFilterParamsContainer>,
+}
+
+impl<'a> LintInspectedDocument<'a> {
+    fn from_archive(path: &'a Utf8Path, kra_archive: &'a KraArchive) -> Result<Self, KraError> {
+        let mut keyframes = BTreeMap::new();
+        let mut filter_params = BTreeMap::new();
+
+        for layer in kra_archive.all_layers() {
+            if let Some(layer_keyframes) = layer.keyframes(kra_archive)? {
+                keyframes.insert(layer.uuid.as_str(), layer_keyframes);
+            }
+
+            if layer.layer_type == KraLayerType::FilterLayer {
+                if let Some(layer_filter_params) = layer.filter_params(kra_archive)? {
+                    filter_params.insert(layer.uuid.as_str(), layer_filter_params);
+                }
+            }
+        }
+
+        for (_, mask) in kra_archive.all_masks() {
+            if let Some(mask_keyframes) = mask.keyframes(kra_archive)? {
+                keyframes.insert(mask.uuid.as_str(), mask_keyframes);
+            }
+
+            if mask.mask_type == KraMaskType::FilterMask {
+                if let Some(mask_filter_params) = mask.filter_params(kra_archive)? {
+                    filter_params.insert(mask.uuid.as_str(), mask_filter_params);
+                }
+            }
+        }
+
+        Ok(LintInspectedDocument {
+            path,
+            document_info: &kra_archive.document_info,
+            main_doc: &kra_archive.main_doc,
+            keyframes,
+            filter_params,
+        })
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+/// Documents as the passes see them, printed to write configs and to debug their results.
+pub struct LintInspectedDocuments {
+    kra_documents: Vec<(Utf8PathBuf, KraArchive)>,
+}
+
+impl LintInspectedDocuments {
+    pub fn from_paths(paths: &[Utf8PathBuf]) -> Result<Self, LintInspectError> {
+        let kra_documents = paths
+            .iter()
+            .map(|kra_path| {
+                KraArchive::from_path(kra_path).map(|kra_archive| (kra_path.clone(), kra_archive)).map_err(|source| {
+                    LintInspectError::FailedToOpenDocument { path: kra_path.into(), source: source.into() }
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(LintInspectedDocuments { kra_documents })
+    }
+
+    #[rustfmt::skip]
+    pub fn write_output<W>(&self, writer: &mut W, inspect_format: LintInspectFormat) -> Result<(), LintInspectError> where W: Write {
+        let inspected_documents = self
+            .kra_documents
+            .iter()
+            .map(|(kra_path, kra_archive)| {
+                LintInspectedDocument::from_archive(kra_path, kra_archive).map_err(|source| {
+                    LintInspectError::FailedToReadSurfaceDetails { path: kra_path.into(), source: source.into() }
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match inspect_format {
+            LintInspectFormat::Tree => {
+                crate::lint_inspect::lint_inspect_tree::to_writer(writer, &inspected_documents)
+                    .map_err(LintInspectError::IoError)
+            }
+
+            #[cfg(feature = "output-json")]
+            LintInspectFormat::Json => {
+                serde_json::to_writer_pretty(&mut *writer, &inspected_documents)
+                    .map_err(LintInspectError::FailedToSerializeJsonOutput)?;
+                writeln!(writer).map_err(LintInspectError::IoError)
+            }
+
+            #[cfg(feature = "output-yaml")]
+            LintInspectFormat::Yaml => {
+                serde_yaml::to_writer(writer, &inspected_documents)
+                    .map_err(LintInspectError::FailedToSerializeYamlOutput)
+            }
+        }
+    }
+}
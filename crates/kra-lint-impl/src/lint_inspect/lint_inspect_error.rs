@@ -0,0 +1,34 @@
+use std::io;
+
+use camino_ext::FormattedPathBuf;
+use derive_more::{Display, Error, From};
+
+use kra_parser::kra_error::KraError;
+
+#[rustfmt::skip]
+#[non_exhaustive]
+#[derive(Debug, Display, Error, From)]
+pub enum LintInspectError {
+    #[display(fmt = "Failed to open document \"{path:}\"")]
+    FailedToOpenDocument {
+        path: FormattedPathBuf,
+        source: Box<KraError>,
+    },
+
+    #[display(fmt = "Failed to read keyframes or filter params of document \"{path:}\"")]
+    FailedToReadSurfaceDetails {
+        path: FormattedPathBuf,
+        source: Box<KraError>,
+    },
+
+    #[cfg(feature = "output-json")]
+    #[display(fmt = "Failed to serialize JSON output")]
+    FailedToSerializeJsonOutput(serde_json::Error),
+
+    #[cfg(feature = "output-yaml")]
+    #[display(fmt = "Failed to serialize YAML output")]
+    FailedToSerializeYamlOutput(serde_yaml::Error),
+
+    #[from]
+    IoError(io::Error),
+}
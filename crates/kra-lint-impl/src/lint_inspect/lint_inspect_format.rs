@@ -0,0 +1,17 @@
+use strum::EnumString;
+
+#[non_exhaustive]
+#[derive(EnumString, Copy, Clone, Debug)]
+#[strum(ascii_case_insensitive)]
+pub enum LintInspectFormat {
+    #[strum(serialize = "tree")]
+    Tree,
+
+    #[cfg(feature = "output-json")]
+    #[strum(serialize = "json")]
+    Json,
+
+    #[cfg(feature = "output-yaml")]
+    #[strum(serialize = "yaml")]
+    Yaml,
+}
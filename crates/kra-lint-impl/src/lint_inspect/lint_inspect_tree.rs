@@ -0,0 +1,116 @@
+use std::io::{self, Write};
+
+use kra_parser::kra_main_doc::KraMainDocLayerContainer;
+
+use crate::lint_inspect::lint_inspect::LintInspectedDocument;
+
+/// Writes an indented tree of the layers and masks of every document, with the fields configs usually match on.
+pub(crate) fn to_writer<W>(writer: &mut W, inspected_documents: &[LintInspectedDocument]) -> io::Result<()>
+where
+    W: Write,
+{
+    for (index, inspected_document) in inspected_documents.iter().enumerate() {
+        if index > 0 {
+            writeln!(writer)?;
+        }
+
+        write_document_tree(writer, inspected_document)?;
+    }
+
+    Ok(())
+}
+
+fn write_document_tree<W: Write>(writer: &mut W, inspected_document: &LintInspectedDocument) -> io::Result<()> {
+    let image = &inspected_document.main_doc.image;
+
+    writeln!(writer, "{}", inspected_document.path)?;
+    writeln!(writer, "  name: \"{}\"", image.name.escape_debug())?;
+    writeln!(writer, "  size: {}×{}px, {}×{}dpi", image.width, image.height, image.x_res, image.y_res)?;
+    writeln!(writer, "  colorspace: {} ({})", image.colorspace_name, image.profile)?;
+
+    writeln!(
+        writer,
+        "  software: {} {} (syntax version {})",
+        inspected_document.main_doc.editor,
+        inspected_document.main_doc.software_version,
+        inspected_document.main_doc.syntax_version
+    )?;
+
+    if let Some(animation) = image.animation.as_ref() {
+        writeln!(
+            writer,
+            "  animation: {}fps, frames {}..{}",
+            animation.framerate.value, animation.range.from, animation.range.to
+        )?;
+    }
+
+    writeln!(writer, "  layers:")?;
+    write_layers_tree(writer, inspected_document, &image.layer_container, 2)
+}
+
+fn write_layers_tree<W: Write>(
+    writer: &mut W,
+    inspected_document: &LintInspectedDocument,
+    layer_container: &KraMainDocLayerContainer,
+    depth: usize,
+) -> io::Result<()> {
+    let indent = "  ".repeat(depth);
+
+    for layer in layer_container {
+        writeln!(
+            writer,
+            "{}- {} \"{}\" {} (blending: {}, opacity: {}%{}{})",
+            indent,
+            layer.layer_type,
+            layer.name.escape_debug(),
+            layer.uuid,
+            layer.composite_op,
+            (layer.opacity * 100 + 127) / 255,
+            if layer.visible { "" } else { ", hidden" },
+            if layer.locked { ", locked" } else { "" },
+        )?;
+
+        write_surface_details(writer, inspected_document, &layer.uuid, depth + 1)?;
+
+        for mask in layer.mask_container.iter().flatten() {
+            writeln!(
+                writer,
+                "{}  - {} \"{}\" {}{}",
+                indent,
+                mask.mask_type,
+                mask.name.escape_debug(),
+                mask.uuid,
+                if mask.visible { "" } else { " (hidden)" },
+            )?;
+
+            write_surface_details(writer, inspected_document, &mask.uuid, depth + 2)?;
+        }
+
+        if let Some(layer_container) = layer.layer_container.as_ref() {
+            write_layers_tree(writer, inspected_document, layer_container, depth + 1)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Keyframe channels and filter params of a layer or mask.
+fn write_surface_details<W: Write>(
+    writer: &mut W,
+    inspected_document: &LintInspectedDocument,
+    uuid: &str,
+    depth: usize,
+) -> io::Result<()> {
+    let indent = "  ".repeat(depth);
+
+    for channel in inspected_document.keyframes.get(uuid).into_iter().flatten() {
+        let keyframe_times = channel.keyframes.iter().map(|keyframe| keyframe.time.to_string()).collect::<Vec<_>>();
+        writeln!(writer, "{}keyframes \"{}\": {}", indent, channel.name, keyframe_times.join(", "))?;
+    }
+
+    for param in inspected_document.filter_params.get(uuid).into_iter().flatten() {
+        writeln!(writer, "{}filter param \"{}\": \"{}\"", indent, param.name, param.value.escape_debug())?;
+    }
+
+    Ok(())
+}
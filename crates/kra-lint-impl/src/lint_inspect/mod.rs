@@ -0,0 +1,9 @@
+mod lint_inspect;
+mod lint_inspect_error;
+mod lint_inspect_format;
+
+pub use lint_inspect::LintInspectedDocuments;
+pub use lint_inspect_error::LintInspectError;
+pub use lint_inspect_format::LintInspectFormat;
+
+pub(crate) mod lint_inspect_tree;
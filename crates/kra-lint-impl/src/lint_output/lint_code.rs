@@ -0,0 +1,275 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::sync::{Mutex, PoisonError};
+
+use serde::de::Error as _;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+macro_rules! lint_codes {
+    ($($variant:ident => $code:literal, $name:literal;)*) => {
+        /// Stable identifier of a single lint check, independent of the message title.
+        ///
+        /// Codes are `KRA` followed by a two digit pass number and a two digit check number,
+        /// they are never renumbered or reused once released.
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum LintCode {
+            $($variant,)*
+
+            /// Rule reported by a script of the `script` pass, see `LintCode::script`.
+            Script { code: &'static str, name: &'static str },
+
+            /// Check of a pass registered through `LintPassRegistry`.
+            Plugin { code: &'static str, name: &'static str },
+        }
+
+        impl LintCode {
+            /// Every check of the builtin passes.
+            pub const ALL: &'static [LintCode] = &[$(LintCode::$variant,)*];
+
+            /// Code of the check, e.g. `KRA1508`.
+            pub fn code(&self) -> &'static str {
+                match *self {
+                    $(LintCode::$variant => $code,)*
+                    LintCode::Script { code, .. } => code,
+                    LintCode::Plugin { code, .. } => code,
+                }
+            }
+
+            /// Name of the check, e.g. `malformed_document.missing_preview`.
+            pub fn name(&self) -> &'static str {
+                match *self {
+                    $(LintCode::$variant => $name,)*
+                    LintCode::Script { name, .. } => name,
+                    LintCode::Plugin { name, .. } => name,
+                }
+            }
+
+            /// Whether `rule` refers to this check, by its code, its name or the name of its pass.
+            pub fn matches_rule(&self, rule: &str) -> bool {
+                rule.eq_ignore_ascii_case(self.code())
+                    || rule == self.name()
+                    || self.name().split_once('.').is_some_and(|(pass_name, _)| rule == pass_name)
+            }
+        }
+    };
+}
+
+#[rustfmt::skip]
+lint_codes! {
+    // General
+    UnreadableDocument                  => "KRA0001", "general.unreadable_document";
+    PassError                           => "KRA0002", "general.pass_error";
+
+    // Pass "animation"
+    AnimationAnimatedLayer              => "KRA0101", "animation.animated_layer";
+    AnimationAnimatedMask               => "KRA0102", "animation.animated_mask";
+    AnimationFramerate                  => "KRA0103", "animation.framerate";
+    AnimationUnpinnedLayer              => "KRA0104", "animation.unpinned_layer";
+    AnimationUnpinnedMask               => "KRA0105", "animation.unpinned_mask";
+    AnimationOnionSkin                  => "KRA0106", "animation.onion_skin";
+    AnimationFrameDelta                 => "KRA0107", "animation.frame_delta";
+
+    // Pass "audio_track"
+    AudioTrackMissingAudioTrack         => "KRA0201", "audio_track.missing_audio_track";
+    AudioTrackUnexpectedAudioTrack      => "KRA0202", "audio_track.unexpected_audio_track";
+    AudioTrackFileFormat                => "KRA0203", "audio_track.file_format";
+    AudioTrackMissingFile               => "KRA0204", "audio_track.missing_file";
+    AudioTrackMuted                     => "KRA0205", "audio_track.muted";
+    AudioTrackVolume                    => "KRA0206", "audio_track.volume";
+
+    // Pass "colorize_mask"
+    ColorizeMaskKeystrokesEditMode      => "KRA0301", "colorize_mask.keystrokes_edit_mode";
+    ColorizeMaskDisabledColoring        => "KRA0302", "colorize_mask.disabled_coloring";
+
+    // Pass "colorspace"
+    ColorspaceDocumentColorspace        => "KRA0401", "colorspace.document_colorspace";
+    ColorspaceLayerColorspace           => "KRA0402", "colorspace.layer_colorspace";
+    ColorspaceMaskColorspace            => "KRA0403", "colorspace.mask_colorspace";
+    ColorspaceDocumentProfile           => "KRA0404", "colorspace.document_profile";
+    ColorspaceLayerProfileChecksum      => "KRA0405", "colorspace.layer_profile_checksum";
+    ColorspaceColorizeMaskProfile       => "KRA0406", "colorspace.colorize_mask_profile_checksum";
+    ColorspaceDocumentProfileChecksum   => "KRA0407", "colorspace.document_profile_checksum";
+
+    // Pass "copyright"
+    CopyrightMissingCopyrightLine       => "KRA0501", "copyright.missing_copyright_line";
+    CopyrightCopyrightLine              => "KRA0502", "copyright.copyright_line";
+    CopyrightMissingCopyrightDisclaimer => "KRA0503", "copyright.missing_copyright_disclaimer";
+    CopyrightCopyrightDisclaimer        => "KRA0504", "copyright.copyright_disclaimer";
+    CopyrightMissingInitialAuthor       => "KRA0505", "copyright.missing_initial_author";
+    CopyrightInitialAuthor              => "KRA0506", "copyright.initial_author";
+    CopyrightMissingAuthorFullName      => "KRA0507", "copyright.missing_author_full_name";
+    CopyrightMissingAuthorFirstName     => "KRA0508", "copyright.missing_author_first_name";
+    CopyrightMissingAuthorLastName      => "KRA0509", "copyright.missing_author_last_name";
+    CopyrightInconsistentFirstName      => "KRA0510", "copyright.inconsistent_author_first_name";
+    CopyrightInconsistentLastName       => "KRA0511", "copyright.inconsistent_author_last_name";
+    CopyrightMissingStudioName          => "KRA0512", "copyright.missing_studio_name";
+    CopyrightStudioName                 => "KRA0513", "copyright.studio_name";
+
+    // Pass "document_name"
+    DocumentNameMissingDocumentName     => "KRA0601", "document_name.missing_document_name";
+    DocumentNameDocumentName            => "KRA0602", "document_name.document_name";
+
+    // Pass "document_size"
+    DocumentSizeDocumentSize            => "KRA0701", "document_size.document_size";
+    DocumentSizeInconsistentResolution  => "KRA0702", "document_size.inconsistent_resolution";
+
+    // Pass "document_structure"
+    DocumentStructureMaskRepetition     => "KRA0801", "document_structure.mask_repetition";
+    DocumentStructureExtraMask          => "KRA0802", "document_structure.extra_mask";
+    DocumentStructureLayerRepetition    => "KRA0803", "document_structure.layer_repetition";
+    DocumentStructureExtraLayer         => "KRA0804", "document_structure.extra_layer";
+
+    // Pass "file_layers"
+    FileLayersFileFormat                => "KRA0901", "file_layers.file_format";
+    FileLayersMissingFile               => "KRA0902", "file_layers.missing_file";
+    FileLayersScalingMethod             => "KRA0903", "file_layers.scaling_method";
+    FileLayersMissingScalingMethod      => "KRA0904", "file_layers.missing_scaling_method";
+
+    // Pass "file_name"
+    FileNameFileName                    => "KRA1001", "file_name.file_name";
+
+    // Pass "file_permissions"
+    FilePermissionsUnixPermissions      => "KRA1101", "file_permissions.unix_permissions";
+    FilePermissionsUnsupportedPlatform  => "KRA1102", "file_permissions.unsupported_platform";
+
+    // Pass "filters"
+    FiltersFilterLayerType              => "KRA1201", "filters.filter_layer_type";
+    FiltersFilterMaskType               => "KRA1202", "filters.filter_mask_type";
+    FiltersNonSquareLayerMosaic         => "KRA1203", "filters.non_square_layer_mosaic";
+    FiltersLayerMosaicSize              => "KRA1204", "filters.layer_mosaic_size";
+    FiltersTransparentLayerMosaic       => "KRA1205", "filters.transparent_layer_mosaic";
+    FiltersNonSquareMaskMosaic          => "KRA1206", "filters.non_square_mask_mosaic";
+    FiltersMaskMosaicSize               => "KRA1207", "filters.mask_mosaic_size";
+
+    // Pass "hidden_surface"
+    HiddenSurfaceHiddenLayer            => "KRA1301", "hidden_surface.hidden_layer";
+    HiddenSurfaceHiddenMask             => "KRA1302", "hidden_surface.hidden_mask";
+
+    // Pass "layer_styles"
+    LayerStylesLayerStyle               => "KRA1401", "layer_styles.layer_style";
+
+    // Pass "malformed_document"
+    MalformedDocumentUnescapedAbstract  => "KRA1501", "malformed_document.unescaped_abstract";
+    MalformedDocumentMissingDocInfo     => "KRA1502", "malformed_document.missing_document_info";
+    MalformedDocumentPathTraversal      => "KRA1503", "malformed_document.path_traversal";
+    MalformedDocumentMissingCloneLayer  => "KRA1504", "malformed_document.missing_clone_target_layer";
+    MalformedDocumentMissingCloneField  => "KRA1505", "malformed_document.missing_clone_target_field";
+    MalformedDocumentCompositionPath    => "KRA1506", "malformed_document.composition_path_traversal";
+    MalformedDocumentCloneLayerLoop     => "KRA1507", "malformed_document.clone_layer_loop";
+    MalformedDocumentMissingPreview     => "KRA1508", "malformed_document.missing_preview";
+    MalformedDocumentExtraneousPreview  => "KRA1509", "malformed_document.extraneous_preview";
+    MalformedDocumentMissingLayerProf   => "KRA1510", "malformed_document.missing_layer_profile";
+    MalformedDocumentMissingDocProf     => "KRA1511", "malformed_document.missing_document_profile";
+    MalformedDocumentMergedImageSize    => "KRA1512", "malformed_document.merged_image_size_mismatch";
+    MalformedDocumentCorruptMergedImage => "KRA1513", "malformed_document.corrupt_merged_image";
+
+    // Pass "non_default_blending"
+    NonDefaultBlendingLayerOpacity      => "KRA1601", "non_default_blending.layer_opacity";
+    NonDefaultBlendingLayerBlendingMode => "KRA1602", "non_default_blending.layer_blending_mode";
+    NonDefaultBlendingMaskBlendingMode  => "KRA1603", "non_default_blending.mask_blending_mode";
+    NonDefaultBlendingChannelFlags      => "KRA1604", "non_default_blending.channel_flags";
+    NonDefaultBlendingChannelLockFlags  => "KRA1605", "non_default_blending.channel_lock_flags";
+
+    // Pass "prohibit_compositions"
+    ProhibitCompositions                => "KRA1701", "prohibit_compositions.composition";
+
+    // Pass "prohibit_custom_palettes"
+    ProhibitCustomPalettes              => "KRA1801", "prohibit_custom_palettes.custom_palette";
+
+    // Pass "prohibit_kseexpr"
+    ProhibitKSeExpr                     => "KRA1901", "prohibit_kseexpr.kseexpr";
+
+    // Pass "software_version"
+    SoftwareVersionSoftwareName         => "KRA2001", "software_version.software_name";
+    SoftwareVersionSoftwareVersion      => "KRA2002", "software_version.software_version";
+    SoftwareVersionSyntaxVersion        => "KRA2003", "software_version.syntax_version";
+
+    // Pass "surface_names"
+    SurfaceNamesLayerName               => "KRA2101", "surface_names.layer_name";
+    SurfaceNamesMaskName                => "KRA2102", "surface_names.mask_name";
+
+    // Pass "surface_type"
+    SurfaceTypeLayerType                => "KRA2201", "surface_type.layer_type";
+    SurfaceTypeMaskType                 => "KRA2202", "surface_type.mask_type";
+
+    // Pass "vector_layers"
+    VectorLayersFontFamily              => "KRA2301", "vector_layers.font_family";
+    VectorLayersStrokeLinecap           => "KRA2302", "vector_layers.stroke_linecap";
+    VectorLayersStrokeLinejoin          => "KRA2303", "vector_layers.stroke_linejoin";
+    VectorLayersStrokeWidth             => "KRA2304", "vector_layers.stroke_width";
+    VectorLayersMalformedStrokeWidth    => "KRA2305", "vector_layers.malformed_stroke_width";
+    VectorLayersPlaceholderText         => "KRA2306", "vector_layers.placeholder_text";
+    VectorLayersBrokenTextGradient      => "KRA2307", "vector_layers.broken_text_gradient";
+}
+
+const SCRIPT_PASS_NAME: &str = "script";
+const SCRIPT_CODE_PREFIX: &str = "KRA24:";
+
+impl LintCode {
+    /// Check of the rule `rule` reported by a script, e.g. `KRA24:flat_group` (`script.flat_group`).
+    ///
+    /// Rule names are chosen by scripts, their codes are created once per rule and then reused.
+    pub fn script(rule: &str) -> LintCode {
+        static SCRIPT_CODES: Mutex<BTreeMap<String, LintCode>> = Mutex::new(BTreeMap::new());
+
+        let mut script_codes = SCRIPT_CODES.lock().unwrap_or_else(PoisonError::into_inner);
+
+        *script_codes.entry(rule.to_owned()).or_insert_with(|| LintCode::Script {
+            code: Box::leak(format!("{}{}", SCRIPT_CODE_PREFIX, rule).into_boxed_str()),
+            name: Box::leak(format!("{}.{}", SCRIPT_PASS_NAME, rule).into_boxed_str()),
+        })
+    }
+
+    /// Name of the builtin pass `pass_name`, `None` if there is no such builtin pass.
+    pub(crate) fn builtin_pass_name(pass_name: &str) -> Option<&'static str> {
+        LintCode::ALL
+            .iter()
+            .filter_map(|lint_code| lint_code.name().split_once('.').map(|(builtin_pass_name, _)| builtin_pass_name))
+            .chain([SCRIPT_PASS_NAME])
+            .find(|builtin_pass_name| *builtin_pass_name == pass_name)
+    }
+}
+
+impl Display for LintCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+impl Serialize for LintCode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("LintCode", 2)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("name", self.name())?;
+        state.end()
+    }
+}
+
+/// Only builtin and script checks can be deserialized, plugin checks are not known ahead of time.
+impl<'de> Deserialize<'de> for LintCode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct LintCodeFields {
+            code: String,
+        }
+
+        let LintCodeFields { code } = LintCodeFields::deserialize(deserializer)?;
+
+        if let Some(rule) = code.strip_prefix(SCRIPT_CODE_PREFIX) {
+            return Ok(LintCode::script(rule));
+        }
+
+        LintCode::ALL
+            .iter()
+            .find(|lint_code| lint_code.code() == code)
+            .copied()
+            .ok_or_else(|| D::Error::custom(format!("unknown lint code \"{}\"", code)))
+    }
+}
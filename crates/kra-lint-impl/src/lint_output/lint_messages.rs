@@ -1,44 +1,162 @@
+use std::collections::BTreeSet;
+
 use derive_more::IntoIterator;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::lint_output::LintMetadata;
+use crate::lint_fix::LintFix;
+use crate::lint_output::{LintCode, LintMetadata, LintSeverity};
 
-#[derive(Default, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Deserialize, Serialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct LintMessagesEntry {
     pub message_title: String,
+    pub message_code: LintCode,
+    pub message_severity: LintSeverity,
     pub message_metadata: Vec<LintMetadata>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_fix: Option<LintFix>,
 }
 
-#[rustfmt::skip]
 #[must_use = "lint results shouldn't be ignored"]
 #[derive(Default, Serialize, IntoIterator)]
 #[serde(transparent)]
-pub struct LintMessages (
+pub struct LintMessages {
     #[into_iterator(ref)]
-    Vec<LintMessagesEntry>
-);
+    messages: Vec<LintMessagesEntry>,
+    #[serde(skip)]
+    suppressed_messages: Vec<LintMessagesEntry>,
+    #[serde(skip)]
+    pass_names: BTreeSet<&'static str>,
+    #[serde(skip)]
+    preview_data: Option<Vec<u8>>,
+}
 
 impl LintMessages {
-    pub(crate) fn push<S>(&mut self, message_title: S, message_metadata: &[LintMetadata])
+    pub fn push<S>(&mut self, message_code: LintCode, message_title: S, message_metadata: &[LintMetadata])
     where
         S: AsRef<str> + Into<String>,
     {
-        self.0.push(LintMessagesEntry {
-            message_title: message_title.into(),
-            message_metadata: message_metadata.to_vec(),
-        });
+        self.with_severity(LintSeverity::default()).push(message_code, message_title, message_metadata);
+    }
+
+    /// Messages pushed through the returned handle are reported with `message_severity`.
+    pub fn with_severity(&mut self, message_severity: LintSeverity) -> LintMessagesWithSeverity<'_> {
+        LintMessagesWithSeverity { lint_messages: self, message_severity }
     }
 
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.messages.is_empty()
     }
 
     pub fn message_count(&self) -> usize {
-        self.0.len()
+        self.messages.len()
+    }
+
+    pub fn max_severity(&self) -> Option<LintSeverity> {
+        self.messages.iter().map(|entry| entry.message_severity).max()
+    }
+
+    /// Names of the passes which linted the document.
+    pub fn pass_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.pass_names.iter().copied()
+    }
+
+    /// PNG data of the preview image of the linted document, see `LintConfigCollection::set_keep_previews`.
+    pub fn preview_data(&self) -> Option<&[u8]> {
+        self.preview_data.as_deref()
+    }
+
+    pub(crate) fn set_preview_data(&mut self, preview_data: Option<Vec<u8>>) {
+        self.preview_data = preview_data;
+    }
+
+    pub(crate) fn record_pass(&mut self, pass_name: &'static str) {
+        self.pass_names.insert(pass_name);
+    }
+
+    pub fn fixes(&self) -> impl Iterator<Item = &LintFix> {
+        self.messages.iter().filter_map(|entry| entry.message_fix.as_ref())
+    }
+
+    pub(crate) fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&LintMessagesEntry) -> bool,
+    {
+        self.messages.retain(f);
+    }
+
+    /// Moves messages matching `f` out of the reported messages, see `take_suppressed`.
+    pub(crate) fn suppress<F>(&mut self, f: F)
+    where
+        F: FnMut(&LintMessagesEntry) -> bool,
+    {
+        let (suppressed_messages, messages): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut self.messages).into_iter().partition(f);
+
+        self.messages = messages;
+        self.suppressed_messages.extend(suppressed_messages);
+    }
+
+    pub(crate) fn take_suppressed(&mut self) -> LintMessages {
+        LintMessages {
+            messages: std::mem::take(&mut self.suppressed_messages),
+            suppressed_messages: vec![],
+            pass_names: self.pass_names.clone(),
+            preview_data: None,
+        }
+    }
+
+    /// Reported messages, suppressed messages and pass names, see `from_parts`.
+    pub(crate) fn parts(&self) -> (&[LintMessagesEntry], &[LintMessagesEntry], &BTreeSet<&'static str>) {
+        (&self.messages, &self.suppressed_messages, &self.pass_names)
+    }
+
+    pub(crate) fn from_parts(
+        messages: Vec<LintMessagesEntry>,
+        suppressed_messages: Vec<LintMessagesEntry>,
+        pass_names: BTreeSet<&'static str>,
+    ) -> Self {
+        LintMessages { messages, suppressed_messages, pass_names, preview_data: None }
     }
 
     pub(crate) fn sort_and_dedup(&mut self) {
-        self.0.sort();
-        self.0.dedup();
+        self.messages.sort();
+        self.messages.dedup();
+        self.suppressed_messages.sort();
+        self.suppressed_messages.dedup();
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+pub struct LintMessagesWithSeverity<'a> {
+    lint_messages: &'a mut LintMessages,
+    message_severity: LintSeverity,
+}
+
+impl LintMessagesWithSeverity<'_> {
+    pub fn push<S>(&mut self, message_code: LintCode, message_title: S, message_metadata: &[LintMetadata])
+    where
+        S: AsRef<str> + Into<String>,
+    {
+        self.push_with_fix(message_code, message_title, message_metadata, None);
+    }
+
+    pub fn push_with_fix<S, F>(
+        &mut self,
+        message_code: LintCode,
+        message_title: S,
+        message_metadata: &[LintMetadata],
+        message_fix: F,
+    ) where
+        S: AsRef<str> + Into<String>,
+        F: Into<Option<LintFix>>,
+    {
+        self.lint_messages.messages.push(LintMessagesEntry {
+            message_title: message_title.into(),
+            message_code,
+            message_severity: self.message_severity,
+            message_metadata: message_metadata.to_vec(),
+            message_fix: message_fix.into(),
+        });
     }
 }
@@ -1,65 +1,195 @@
+use std::fmt::Display;
 use std::io::Write;
 
 use camino::{Utf8Path, Utf8PathBuf};
 use derive_more::IntoIterator;
 use serde::Serialize;
 
-use crate::lint_output::{LintMessages, LintOutputError, LintOutputFormat};
+use crate::lint_output::{LintCode, LintMessages, LintMessagesEntry, LintOutputError, LintOutputFormat, LintSeverity};
 
-#[derive(Default, Serialize)]
+/// Outcome of linting a single document.
+#[derive(Debug, Serialize, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LintDocumentStatus {
+    Clean,
+    Issues,
+    FailedToOpen,
+}
+
+impl LintDocumentStatus {
+    fn from_messages(messages: &LintMessages) -> Self {
+        if messages.into_iter().any(|message| message.message_code == LintCode::UnreadableDocument) {
+            LintDocumentStatus::FailedToOpen
+        } else if messages.is_empty() {
+            LintDocumentStatus::Clean
+        } else {
+            LintDocumentStatus::Issues
+        }
+    }
+}
+
+impl Display for LintDocumentStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            LintDocumentStatus::Clean => write!(f, "clean"),
+            LintDocumentStatus::Issues => write!(f, "issues"),
+            LintDocumentStatus::FailedToOpen => write!(f, "failed to open"),
+        }
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+/// Document and message counts of a lint run.
+#[derive(Debug, Serialize, Default, Clone, PartialEq, Eq)]
+pub struct LintSummary {
+    pub documents: usize,
+    pub clean_documents: usize,
+    pub documents_with_issues: usize,
+    pub failed_documents: usize,
+    pub messages: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub infos: usize,
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+#[derive(Serialize)]
 pub struct LintMessagesCollectionEntry {
     pub path: Utf8PathBuf,
+    pub status: LintDocumentStatus,
     pub messages: LintMessages,
 }
 
-#[rustfmt::skip]
+/// Results of every linted document in the order they were linted, including clean documents.
 #[must_use = "lint results shouldn't be ignored"]
 #[derive(Default, Serialize, IntoIterator)]
 #[serde(transparent)]
-pub struct LintMessagesCollection (
+pub struct LintMessagesCollection {
     #[into_iterator(ref)]
-    Vec<LintMessagesCollectionEntry>
-);
+    entries: Vec<LintMessagesCollectionEntry>,
+    #[serde(skip)]
+    suppressed_entries: Vec<LintMessagesCollectionEntry>,
+}
+
+/// Serialized output, an array of the documents or with `verbose` an object with a summary of the run.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum LintMessagesOutput<'a> {
+    Documents(&'a LintMessagesCollection),
+    Report { documents: &'a LintMessagesCollection, summary: LintSummary },
+}
 
 impl LintMessagesCollection {
-    pub(crate) fn push(&mut self, path: &Utf8Path, messages: LintMessages) {
-        self.0.push(LintMessagesCollectionEntry { path: path.to_owned(), messages });
+    pub(crate) fn push(&mut self, path: &Utf8Path, mut messages: LintMessages) {
+        let suppressed_messages = messages.take_suppressed();
+
+        if !suppressed_messages.is_empty() {
+            self.suppressed_entries.push(LintMessagesCollectionEntry {
+                path: path.to_owned(),
+                status: LintDocumentStatus::from_messages(&suppressed_messages),
+                messages: suppressed_messages,
+            });
+        }
+
+        let status = LintDocumentStatus::from_messages(&messages);
+        self.entries.push(LintMessagesCollectionEntry { path: path.to_owned(), status, messages });
     }
 
+    /// Messages suppressed by directives inside the documents, these are never part of the output.
+    pub fn suppressed(&self) -> impl Iterator<Item = &LintMessagesCollectionEntry> {
+        self.suppressed_entries.iter()
+    }
+
+    /// Documents which were linted without any messages.
+    pub fn clean(&self) -> impl Iterator<Item = &LintMessagesCollectionEntry> {
+        self.entries.iter().filter(|entry| entry.status == LintDocumentStatus::Clean)
+    }
+
+    pub(crate) fn retain_messages<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&Utf8Path, &LintMessagesEntry) -> bool,
+    {
+        for entry in &mut self.entries {
+            entry.messages.retain(|message| f(&entry.path, message));
+            entry.status = LintDocumentStatus::from_messages(&entry.messages);
+        }
+    }
+
+    /// Whether no messages were reported for any document.
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.message_count() == 0
     }
 
     pub fn message_count(&self) -> usize {
-        self.0.iter().map(|entry| entry.messages.message_count()).sum()
+        self.entries.iter().map(|entry| entry.messages.message_count()).sum()
+    }
+
+    pub fn max_severity(&self) -> Option<LintSeverity> {
+        self.entries.iter().filter_map(|entry| entry.messages.max_severity()).max()
+    }
+
+    pub fn summary(&self) -> LintSummary {
+        let mut summary = LintSummary { documents: self.entries.len(), ..LintSummary::default() };
+
+        for entry in &self.entries {
+            match entry.status {
+                LintDocumentStatus::Clean => summary.clean_documents += 1,
+                LintDocumentStatus::Issues => summary.documents_with_issues += 1,
+                LintDocumentStatus::FailedToOpen => summary.failed_documents += 1,
+            }
+
+            for message in &entry.messages {
+                summary.messages += 1;
+
+                match message.message_severity {
+                    LintSeverity::Info => summary.infos += 1,
+                    LintSeverity::Warning => summary.warnings += 1,
+                    LintSeverity::Error => summary.errors += 1,
+                }
+            }
+        }
+
+        summary
     }
 }
 
 impl LintMessagesCollection {
+    /// Writes the results in `output_format`.
+    ///
+    /// Serialized formats (e.g. JSON) are an array of documents, `verbose` turns it into an object
+    /// of the `documents` and a `summary` of the run.
     #[allow(unused_variables)]
     #[rustfmt::skip]
-    pub fn write_output<W>(&self, writer: &mut W, output_format: LintOutputFormat) -> Result<(), LintOutputError> where W: Write {
+    pub fn write_output<W>(&self, writer: &mut W, output_format: LintOutputFormat, verbose: bool) -> Result<(), LintOutputError> where W: Write {
+        let output = if verbose {
+            LintMessagesOutput::Report { documents: self, summary: self.summary() }
+        } else {
+            LintMessagesOutput::Documents(self)
+        };
+
         match output_format {
             #[cfg(feature = "output-plaintext")]
             LintOutputFormat::PlainText => {
-                crate::lint_output::lint_output_plaintext::to_writer(writer, self)
+                crate::lint_output::lint_output_plaintext::to_writer(writer, self, verbose)
             }
 
             #[cfg(feature = "output-json")]
             LintOutputFormat::Json => {
-                serde_json::to_writer(writer, self)
+                serde_json::to_writer(writer, &output)
                     .map_err(LintOutputError::FailedToSerializeJsonOutput)
             }
 
             #[cfg(feature = "output-ron")]
             LintOutputFormat::Ron => {
-                ron::ser::to_writer(writer, self)
+                ron::ser::to_writer(writer, &output)
                     .map_err(LintOutputError::FailedToSerializeRonOutput)
             }
 
             #[cfg(feature = "output-yaml")]
             LintOutputFormat::Yaml => {
-                serde_yaml::to_writer(writer, self)
+                serde_yaml::to_writer(writer, &output)
                     .map_err(LintOutputError::FailedToSerializeYamlOutput)
             }
 
@@ -67,15 +197,30 @@ impl LintMessagesCollection {
             LintOutputFormat::Pickle => {
                 let pickle_options = serde_pickle::SerOptions::default();
 
-                serde_pickle::to_writer(writer, self, pickle_options)
+                serde_pickle::to_writer(writer, &output, pickle_options)
                     .map_err(LintOutputError::FailedToSerializePickleOutput)
             }
 
             #[cfg(feature = "output-gura")]
             LintOutputFormat::Gura => {
-                gura_ext::to_writer(writer, self)
+                gura_ext::to_writer(writer, &output)
                     .map_err(LintOutputError::FailedToSerializeGuraOutput)
             }
+
+            #[cfg(feature = "output-html")]
+            LintOutputFormat::Html => {
+                crate::lint_output::lint_output_html::to_writer(writer, self)
+            }
+
+            #[cfg(feature = "output-junit")]
+            LintOutputFormat::Junit => {
+                crate::lint_output::lint_output_junit::to_writer(writer, self)
+            }
+
+            #[cfg(feature = "output-sarif")]
+            LintOutputFormat::Sarif => {
+                crate::lint_output::lint_output_sarif::to_writer(writer, self)
+            }
         }
     }
 }
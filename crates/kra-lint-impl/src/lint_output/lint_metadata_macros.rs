@@ -1,54 +1,70 @@
+/// Layer metadata of a message, from a `KraMainDocLayer`.
+#[macro_export]
 macro_rules! meta_layer {
     ($layer:expr) => {
-        $crate::lint_output::LintMetadata::Layer {
+        $crate::LintMetadata::Layer {
             layer_name: $layer.name.to_string(),
             layer_uuid: $layer.uuid.to_string(),
         }
     };
 }
 
+/// Mask metadata of a message, from a `KraMainDocMask`.
+#[macro_export]
 macro_rules! meta_mask {
     ($mask:expr) => {
-        $crate::lint_output::LintMetadata::Mask { mask_name: $mask.name.to_string(), mask_uuid: $mask.uuid.to_string() }
+        $crate::LintMetadata::Mask { mask_name: $mask.name.to_string(), mask_uuid: $mask.uuid.to_string() }
     };
 }
 
+/// Expected value of a message, from anything implementing `Display`.
+#[macro_export]
 macro_rules! meta_expected {
     ($expected:expr) => {
-        $crate::lint_output::LintMetadata::Expected($expected.to_string())
+        $crate::LintMetadata::Expected($expected.to_string())
     };
 }
 
+/// Found value of a message, from anything implementing `Display`.
+#[macro_export]
 macro_rules! meta_found {
     ($found:expr) => {
-        $crate::lint_output::LintMetadata::Found($found.to_string())
+        $crate::LintMetadata::Found($found.to_string())
     };
 }
 
+/// Missing document field of a message.
+#[macro_export]
 macro_rules! meta_missing_field {
     ($missing_field:expr) => {
-        $crate::lint_output::LintMetadata::MissingField($missing_field.to_string())
+        $crate::LintMetadata::MissingField($missing_field.to_string())
     };
 }
 
+/// Krita bug number related to a message.
+#[macro_export]
 macro_rules! meta_bug {
     ($bug:expr) => {
-        $crate::lint_output::LintMetadata::Bug($bug)
+        $crate::LintMetadata::Bug($bug)
     };
 }
 
+/// Free-form comment of a message.
+#[macro_export]
 macro_rules! meta_comment {
     ($comment:expr) => {
-        $crate::lint_output::LintMetadata::Comment($comment.to_string())
+        $crate::LintMetadata::Comment($comment.to_string())
     };
 }
 
+/// Error which prevented a check, from anything implementing `Display`.
+#[macro_export]
 macro_rules! meta_error {
     ($error:expr) => {
-        $crate::lint_output::LintMetadata::Error($error.to_string())
+        $crate::LintMetadata::Error($error.to_string())
     };
 }
 
-pub(crate) use {
+pub(crate) use crate::{
     meta_bug, meta_comment, meta_error, meta_expected, meta_found, meta_layer, meta_mask, meta_missing_field,
 };
@@ -25,6 +25,10 @@ pub enum LintOutputError {
     #[display(fmt = "Failed to serialize Gura output")]
     FailedToSerializeGuraOutput(gura_ext::Error),
 
+    #[cfg(feature = "output-sarif")]
+    #[display(fmt = "Failed to serialize SARIF output")]
+    FailedToSerializeSarifOutput(serde_json::Error),
+
     #[from]
     IoError(io::Error),
 }
@@ -27,4 +27,28 @@ pub enum LintOutputFormat {
     #[cfg(feature = "output-gura")]
     #[strum(serialize = "gura")]
     Gura,
+
+    #[cfg(feature = "output-html")]
+    #[strum(serialize = "html")]
+    Html,
+
+    #[cfg(feature = "output-junit")]
+    #[strum(serialize = "junit")]
+    Junit,
+
+    #[cfg(feature = "output-sarif")]
+    #[strum(serialize = "sarif")]
+    Sarif,
+}
+
+impl LintOutputFormat {
+    /// Whether the output includes the preview images of the linted documents.
+    pub fn includes_previews(self) -> bool {
+        #[cfg(feature = "output-html")]
+        if matches!(self, LintOutputFormat::Html) {
+            return true;
+        }
+
+        false
+    }
 }
@@ -0,0 +1,138 @@
+#![cfg(feature = "output-html")]
+
+use std::io::Write;
+
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use itertools::Itertools;
+
+use crate::lint_output::lint_output_markup::markup_escape;
+use crate::lint_output::{
+    LintMessagesCollection, LintMessagesCollectionEntry, LintMessagesEntry, LintMetadata, LintOutputError, LintSeverity,
+};
+
+const HTML_STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; background: #f4f4f6; color: #222; }
+h1 { font-size: 1.5em; }
+.summary span { display: inline-block; margin-right: 1.5em; }
+.card { display: flex; gap: 1.5em; background: #fff; border-radius: 6px; margin: 1em 0; padding: 1em;
+        box-shadow: 0 1px 3px rgba(0, 0, 0, 0.15); border-left: 6px solid #3a3; }
+.card.info { border-left-color: #37c; }
+.card.warning { border-left-color: #e90; }
+.card.error { border-left-color: #d33; }
+.preview { flex: 0 0 160px; text-align: center; color: #888; }
+.preview img { max-width: 160px; max-height: 160px; border: 1px solid #ddd; }
+.issues { flex: 1; min-width: 0; }
+.issues h2 { font-size: 1.1em; margin: 0 0 0.5em 0; word-break: break-all; }
+.issue { margin: 0.5em 0; }
+.issue ul { margin: 0.25em 0; padding-left: 1.5em; }
+.severity { font-weight: bold; }
+.severity.info { color: #37c; }
+.severity.warning { color: #e90; }
+.severity.error { color: #d33; }
+.code { color: #666; font-family: monospace; }
+.surface { background: #fff3b0; border-radius: 3px; padding: 0 0.2em; }
+";
+
+/// Writes a single self-contained HTML page with one card per document, including its preview image
+/// when the previews were kept while linting.
+pub(crate) fn to_writer<W>(writer: &mut W, message_collection: &LintMessagesCollection) -> Result<(), LintOutputError>
+where
+    W: Write,
+{
+    let entries = message_collection
+        .into_iter()
+        .sorted_by(|entry_a, entry_b| entry_a.path.cmp(&entry_b.path))
+        .collect::<Vec<_>>();
+
+    let severity_count = |severity: LintSeverity| {
+        message_collection
+            .into_iter()
+            .flat_map(|entry| &entry.messages)
+            .filter(|message| message.message_severity == severity)
+            .count()
+    };
+
+    writer.write_all(b"<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n")?;
+    writer.write_all(b"<title>kra-lint report</title>\n")?;
+    writer.write_all(format!("<style>{}</style>\n", HTML_STYLE).as_bytes())?;
+    writer.write_all(b"</head>\n<body>\n<h1>kra-lint report</h1>\n")?;
+
+    writer.write_all(
+        format!(
+            "<p class=\"summary\"><span>Documents: {}</span><span>Clean: {}</span>\
+            <span>Errors: {}</span><span>Warnings: {}</span><span>Infos: {}</span></p>\n",
+            entries.len(),
+            message_collection.clean().count(),
+            severity_count(LintSeverity::Error),
+            severity_count(LintSeverity::Warning),
+            severity_count(LintSeverity::Info),
+        )
+        .as_bytes(),
+    )?;
+
+    for LintMessagesCollectionEntry { path, messages, .. } in entries {
+        let card_class = messages.max_severity().map_or("clean".to_owned(), |severity| severity.to_string());
+
+        writer.write_all(format!("<div class=\"card {}\">\n", card_class).as_bytes())?;
+
+        match messages.preview_data() {
+            Some(preview_data) => writer.write_all(
+                format!(
+                    "<div class=\"preview\"><img alt=\"Preview\" src=\"data:image/png;base64,{}\"></div>\n",
+                    BASE64_STANDARD.encode(preview_data)
+                )
+                .as_bytes(),
+            )?,
+            None => writer.write_all(b"<div class=\"preview\">No preview</div>\n")?,
+        }
+
+        writer.write_all(format!("<div class=\"issues\">\n<h2>{}</h2>\n", markup_escape(path.as_str())).as_bytes())?;
+
+        if messages.is_empty() {
+            writer.write_all(b"<p>No issues found</p>\n")?;
+        }
+
+        for ((message_title, message_code, message_severity), group) in &messages.into_iter().group_by(
+            |LintMessagesEntry { message_title, message_code, message_severity, .. }| {
+                (message_title, message_code, message_severity)
+            },
+        ) {
+            writer.write_all(
+                format!(
+                    "<div class=\"issue\"><span class=\"severity {0}\">{0}</span> \
+                    <span class=\"code\">[{1}]</span> {2}\n<ul>\n",
+                    message_severity,
+                    message_code,
+                    markup_escape(message_title)
+                )
+                .as_bytes(),
+            )?;
+
+            for LintMessagesEntry { message_metadata, .. } in group {
+                let metadata_html = message_metadata.iter().map(metadata_html).join(", ");
+                writer.write_all(format!("<li>{}</li>\n", metadata_html).as_bytes())?;
+            }
+
+            writer.write_all(b"</ul></div>\n")?;
+        }
+
+        writer.write_all(b"</div>\n</div>\n")?;
+    }
+
+    writer.write_all(b"</body>\n</html>\n")?;
+
+    Ok(())
+}
+
+fn metadata_html(metadata: &LintMetadata) -> String {
+    match metadata {
+        LintMetadata::Layer { layer_name, .. } => {
+            format!("Layer: <span class=\"surface\">{}</span>", markup_escape(layer_name))
+        }
+        LintMetadata::Mask { mask_name, .. } => {
+            format!("Mask: <span class=\"surface\">{}</span>", markup_escape(mask_name))
+        }
+        metadata => markup_escape(&metadata.to_string()),
+    }
+}
@@ -0,0 +1,114 @@
+#![cfg(feature = "output-junit")]
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use itertools::Itertools;
+
+use crate::lint_output::lint_output_markup::markup_escape;
+use crate::lint_output::{
+    LintMessagesCollection, LintMessagesCollectionEntry, LintMessagesEntry, LintOutputError, LintSeverity,
+};
+
+/// Writes one test suite per document and one test case per pass.
+///
+/// Error messages become failures, info and warning messages are written to the standard output of
+/// their test case, so that they are visible without failing it. Passes which did not report anything
+/// are written as passing test cases, so that clean documents show up as well. Messages not belonging
+/// to a pass are reported by a `general` test case.
+pub(crate) fn to_writer<W>(writer: &mut W, message_collection: &LintMessagesCollection) -> Result<(), LintOutputError>
+where
+    W: Write,
+{
+    let test_suites = message_collection
+        .into_iter()
+        .sorted_by(|entry_a, entry_b| entry_a.path.cmp(&entry_b.path))
+        .map(|LintMessagesCollectionEntry { path, messages, .. }| {
+            let mut test_cases = messages.pass_names().map(|pass_name| (pass_name, vec![])).collect::<BTreeMap<_, _>>();
+
+            for message in messages {
+                let pass_name =
+                    message.message_code.name().split_once('.').map_or("general", |(pass_name, _)| pass_name);
+                test_cases.entry(pass_name).or_default().push(message);
+            }
+
+            let failure_count = test_cases.values().filter(|test_case_messages| is_failure(test_case_messages)).count();
+
+            (path, test_cases, failure_count)
+        })
+        .collect::<Vec<_>>();
+
+    let test_case_count = test_suites.iter().map(|(_, test_cases, _)| test_cases.len()).sum::<usize>();
+    let failure_count = test_suites.iter().map(|(_, _, failure_count)| failure_count).sum::<usize>();
+
+    writer.write_all(b"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n")?;
+    writer.write_all(
+        format!("<testsuites name=\"kra-lint\" tests=\"{}\" failures=\"{}\">\n", test_case_count, failure_count)
+            .as_bytes(),
+    )?;
+
+    for (path, test_cases, failure_count) in test_suites {
+        let path = markup_escape(path.as_str());
+
+        writer.write_all(
+            format!("  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">\n", path, test_cases.len(), failure_count)
+                .as_bytes(),
+        )?;
+
+        for (pass_name, test_case_messages) in test_cases {
+            if test_case_messages.is_empty() {
+                writer
+                    .write_all(format!("    <testcase name=\"{}\" classname=\"{}\"/>\n", pass_name, path).as_bytes())?;
+                continue;
+            }
+
+            writer.write_all(format!("    <testcase name=\"{}\" classname=\"{}\">\n", pass_name, path).as_bytes())?;
+
+            let (failure_messages, output_messages): (Vec<_>, Vec<_>) =
+                test_case_messages.into_iter().partition(|message| message.message_severity == LintSeverity::Error);
+
+            for message in failure_messages {
+                writer.write_all(
+                    format!(
+                        "      <failure type=\"{}\" message=\"{}\">{}</failure>\n",
+                        message.message_code,
+                        markup_escape(&message.message_title),
+                        markup_escape(&message_body(message))
+                    )
+                    .as_bytes(),
+                )?;
+            }
+
+            if !output_messages.is_empty() {
+                let output_body = output_messages.into_iter().map(message_body).join("\n");
+                writer.write_all(
+                    format!("      <system-out>{}</system-out>\n", markup_escape(&output_body)).as_bytes(),
+                )?;
+            }
+
+            writer.write_all(b"    </testcase>\n")?;
+        }
+
+        writer.write_all(b"  </testsuite>\n")?;
+    }
+
+    writer.write_all(b"</testsuites>\n")?;
+
+    Ok(())
+}
+
+fn is_failure(test_case_messages: &[&LintMessagesEntry]) -> bool {
+    test_case_messages.iter().any(|message| message.message_severity == LintSeverity::Error)
+}
+
+fn message_body(message: &LintMessagesEntry) -> String {
+    let LintMessagesEntry { message_title, message_code, message_severity, message_metadata, .. } = message;
+
+    format!(
+        "{}[{}]: {}\n{}",
+        message_severity,
+        message_code,
+        message_title,
+        message_metadata.iter().map(|metadata| format!("{}\n", metadata)).join("")
+    )
+}
@@ -0,0 +1,15 @@
+#![cfg(any(feature = "output-html", feature = "output-junit"))]
+
+/// Escapes text for the contents and attribute values of both HTML and XML elements.
+pub(crate) fn markup_escape(text: &str) -> String {
+    text.chars()
+        .map(|char| match char {
+            '&' => "&amp;".to_owned(),
+            '<' => "&lt;".to_owned(),
+            '>' => "&gt;".to_owned(),
+            '"' => "&quot;".to_owned(),
+            '\'' => "&#39;".to_owned(),
+            char => char.to_string(),
+        })
+        .collect()
+}
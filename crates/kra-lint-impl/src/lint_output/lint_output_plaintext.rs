@@ -7,18 +7,37 @@ use unicode_width::UnicodeWidthStr;
 
 use crate::lint_output::{LintMessagesCollection, LintMessagesCollectionEntry, LintMessagesEntry, LintOutputError};
 
-pub(crate) fn to_writer<W>(writer: &mut W, message_collection: &LintMessagesCollection) -> Result<(), LintOutputError>
+/// Writes the messages grouped by title, `verbose` additionally lists the documents without any messages.
+pub(crate) fn to_writer<W>(
+    writer: &mut W,
+    message_collection: &LintMessagesCollection,
+    verbose: bool,
+) -> Result<(), LintOutputError>
 where
     W: Write,
 {
-    for LintMessagesCollectionEntry { path, messages } in message_collection {
-        for (message_title, group) in
-            &messages.into_iter().group_by(|LintMessagesEntry { message_title, .. }| message_title)
-        {
+    if verbose {
+        for LintMessagesCollectionEntry { path, status, .. } in message_collection.clean() {
+            writer.write_all(format!("{}: {}\n", path, status).as_bytes())?;
+        }
+
+        if message_collection.clean().next().is_some() {
+            writer.write_all(b"\n")?;
+        }
+    }
+
+    for LintMessagesCollectionEntry { path, messages, .. } in message_collection {
+        for ((message_title, message_code, message_severity), group) in &messages.into_iter().group_by(
+            |LintMessagesEntry { message_title, message_code, message_severity, .. }| {
+                (message_title, message_code, message_severity)
+            },
+        ) {
             let indent_size = path.to_string().width();
             let indent_str = format!("{}  | ", " ".repeat(indent_size));
 
-            writer.write_all(format!("{}: {}\n", path, message_title).as_bytes())?;
+            writer.write_all(
+                format!("{}: {}[{}]: {}\n", path, message_severity, message_code, message_title).as_bytes(),
+            )?;
             for LintMessagesEntry { message_metadata, .. } in group {
                 writer.write_all(format!("{}{}\n", indent_str, message_metadata.iter().join(", ")).as_bytes())?;
             }
@@ -0,0 +1,130 @@
+#![cfg(feature = "output-sarif")]
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use camino::Utf8Path;
+use itertools::Itertools;
+use serde_json::{json, Value};
+
+use crate::lint_output::{
+    LintCode, LintMessagesCollection, LintMessagesCollectionEntry, LintMessagesEntry, LintMetadata, LintOutputError,
+    LintSeverity,
+};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// Writes a SARIF 2.1.0 log with a single run, rules are limited to the codes found in the results.
+pub(crate) fn to_writer<W>(writer: &mut W, message_collection: &LintMessagesCollection) -> Result<(), LintOutputError>
+where
+    W: Write,
+{
+    let rule_indices = message_collection
+        .into_iter()
+        .flat_map(|entry| &entry.messages)
+        .map(|message| message.message_code)
+        .sorted()
+        .dedup()
+        .enumerate()
+        .map(|(rule_index, lint_code)| (lint_code, rule_index))
+        .collect::<BTreeMap<_, _>>();
+
+    let rules = rule_indices.keys().map(sarif_rule).collect::<Vec<_>>();
+
+    let results = message_collection
+        .into_iter()
+        .flat_map(|LintMessagesCollectionEntry { path, messages, .. }| {
+            messages.into_iter().map(|message| sarif_result(path, message, rule_indices[&message.message_code]))
+        })
+        .collect::<Vec<_>>();
+
+    let sarif_log = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "kra-lint",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    });
+
+    serde_json::to_writer(writer, &sarif_log).map_err(LintOutputError::FailedToSerializeSarifOutput)
+}
+
+fn sarif_rule(lint_code: &LintCode) -> Value {
+    json!({
+        "id": lint_code.code(),
+        "name": lint_code.name(),
+    })
+}
+
+fn sarif_result(path: &Utf8Path, message: &LintMessagesEntry, rule_index: usize) -> Value {
+    let LintMessagesEntry { message_title, message_code, message_severity, message_metadata, .. } = message;
+
+    let message_text = if message_metadata.is_empty() {
+        message_title.clone()
+    } else {
+        format!("{} ({})", message_title, message_metadata.iter().join(", "))
+    };
+
+    let logical_locations = message_metadata
+        .iter()
+        .filter_map(|metadata| match metadata {
+            LintMetadata::Layer { layer_name, layer_uuid } => {
+                Some(json!({ "name": layer_name, "fullyQualifiedName": layer_uuid, "kind": "layer" }))
+            }
+            LintMetadata::Mask { mask_name, mask_uuid } => {
+                Some(json!({ "name": mask_name, "fullyQualifiedName": mask_uuid, "kind": "mask" }))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let mut location = json!({
+        "physicalLocation": {
+            "artifactLocation": {
+                "uri": artifact_uri(path),
+            },
+        },
+    });
+
+    if !logical_locations.is_empty() {
+        location["logicalLocations"] = Value::Array(logical_locations);
+    }
+
+    json!({
+        "ruleId": message_code.code(),
+        "ruleIndex": rule_index,
+        "level": sarif_level(*message_severity),
+        "message": {
+            "text": message_text,
+        },
+        "locations": [location],
+    })
+}
+
+fn sarif_level(message_severity: LintSeverity) -> &'static str {
+    match message_severity {
+        LintSeverity::Info => "note",
+        LintSeverity::Warning => "warning",
+        LintSeverity::Error => "error",
+    }
+}
+
+/// Relative URI reference of the document, percent-encoding everything but unreserved characters and `/`.
+fn artifact_uri(path: &Utf8Path) -> String {
+    let path = path.as_str().replace('\\', "/");
+
+    path.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => (byte as char).to_string(),
+            byte => format!("%{:02X}", byte),
+        })
+        .collect()
+}
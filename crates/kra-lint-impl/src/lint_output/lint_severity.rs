@@ -0,0 +1,30 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use strum::EnumString;
+
+/// Severity of a lint message, ordered from least to most severe.
+#[derive(Debug, Deserialize, Serialize, EnumString, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+#[strum(ascii_case_insensitive)]
+pub enum LintSeverity {
+    #[strum(serialize = "info")]
+    Info,
+
+    #[strum(serialize = "warning")]
+    Warning,
+
+    #[default]
+    #[strum(serialize = "error")]
+    Error,
+}
+
+impl Display for LintSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            LintSeverity::Info => write!(f, "info"),
+            LintSeverity::Warning => write!(f, "warning"),
+            LintSeverity::Error => write!(f, "error"),
+        }
+    }
+}
@@ -1,14 +1,24 @@
+mod lint_code;
 mod lint_messages;
 mod lint_messages_collection;
 mod lint_metadata;
 mod lint_output_error;
 mod lint_output_format;
+mod lint_severity;
 
-pub use lint_messages::{LintMessages, LintMessagesEntry};
-pub use lint_messages_collection::{LintMessagesCollection, LintMessagesCollectionEntry};
+pub use lint_code::LintCode;
+pub use lint_messages::{LintMessages, LintMessagesEntry, LintMessagesWithSeverity};
+pub use lint_messages_collection::{
+    LintDocumentStatus, LintMessagesCollection, LintMessagesCollectionEntry, LintSummary,
+};
 pub use lint_metadata::LintMetadata;
 pub use lint_output_error::LintOutputError;
 pub use lint_output_format::LintOutputFormat;
+pub use lint_severity::LintSeverity;
 
 pub(crate) mod lint_metadata_macros;
+pub(crate) mod lint_output_html;
+pub(crate) mod lint_output_junit;
+pub(crate) mod lint_output_markup;
 pub(crate) mod lint_output_plaintext;
+pub(crate) mod lint_output_sarif;
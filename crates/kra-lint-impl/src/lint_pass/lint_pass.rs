@@ -14,10 +14,17 @@ pub enum LintPassError {
 
     #[from]
     KraError(kra_parser::kra_error::KraError),
+
+    /// Error of a plugin pass, see `LintPassRegistry`.
+    #[display(fmt = "{}", _0)]
+    Custom(#[error(not(source))] String),
 }
 
-pub(crate) type LintPassResult = Result<(), LintPassError>;
+pub type LintPassResult = Result<(), LintPassError>;
 
-pub(crate) trait LintPass {
+/// Single lint pass, checking a document and reporting its findings into `lint_messages`.
+///
+/// Passes are shared between the lint threads, errors are reported as a lint message of the document.
+pub trait LintPass: Send + Sync {
     fn lint(&self, kra_archive: &KraArchive, lint_messages: &mut LintMessages) -> LintPassResult;
 }
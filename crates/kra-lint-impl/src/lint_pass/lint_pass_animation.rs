@@ -4,21 +4,37 @@ use serde::{Deserialize, Serialize};
 use kra_parser::kra_archive::KraArchive;
 use kra_parser::kra_main_doc::KraLayerType;
 
-use crate::lint_config_fields::{NumberMatchExpression, ValueByLayerType, ValueByMaskType};
+use crate::lint_config::impl_lint_config_merge;
+use crate::lint_config_fields::{NumberMatchExpression, ValueByLayerType, ValueByMaskType, ValueWithSeverity};
+use crate::lint_fix::LintFix;
 use crate::lint_output::lint_metadata_macros::{meta_expected, meta_found, meta_layer, meta_mask};
-use crate::lint_output::LintMessages;
+use crate::lint_output::{LintCode, LintMessages};
 use crate::lint_pass::{LintPass, LintPassResult};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct LintPassAnimation {
-    animated_layers: Option<ValueByLayerType<bool>>,
-    animated_masks: Option<ValueByMaskType<bool>>,
-    framerate: Option<NumberMatchExpression<usize>>,
-    frame_delta: Option<NumberMatchExpression<usize>>,
-    force_layer_pin: Option<ValueByLayerType<bool>>,
-    force_mask_pin: Option<ValueByMaskType<bool>>,
-    warn_onion_skin: Option<bool>,
+    animated_layers: Option<ValueWithSeverity<ValueByLayerType<bool>>>,
+    animated_masks: Option<ValueWithSeverity<ValueByMaskType<bool>>>,
+    framerate: Option<ValueWithSeverity<NumberMatchExpression<usize>>>,
+    frame_delta: Option<ValueWithSeverity<NumberMatchExpression<usize>>>,
+    force_layer_pin: Option<ValueWithSeverity<ValueByLayerType<bool>>>,
+    force_mask_pin: Option<ValueWithSeverity<ValueByMaskType<bool>>>,
+    warn_onion_skin: Option<ValueWithSeverity<bool>>,
+}
+
+impl_lint_config_merge! {
+    LintPassAnimation,
+    optional: [
+        animated_layers,
+        animated_masks,
+        framerate,
+        frame_delta,
+        force_layer_pin,
+        force_mask_pin,
+        warn_onion_skin,
+    ],
+    required: [],
 }
 
 impl LintPass for LintPassAnimation {
@@ -26,6 +42,8 @@ impl LintPass for LintPassAnimation {
         // Sub-pass #1
         {
             if let Some(animated_layers) = self.animated_layers.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(animated_layers.severity());
+
                 for layer in kra_archive.all_layers() {
                     let (layer_opt, layer_display) = animated_layers.get(layer);
 
@@ -34,6 +52,7 @@ impl LintPass for LintPassAnimation {
                         if layer.keyframes.is_some() {
                             #[rustfmt::skip]
                             lint_messages.push(
+                                LintCode::AnimationAnimatedLayer,
                                 format!("Prohibited use of animated {}", layer_display),
                                 &[
                                     meta_layer!(layer),
@@ -48,6 +67,8 @@ impl LintPass for LintPassAnimation {
         // Sub-pass #2
         {
             if let Some(animated_masks) = self.animated_masks.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(animated_masks.severity());
+
                 for (layer, mask) in kra_archive.all_masks() {
                     let (mask_opt, mask_display) = animated_masks.get(mask);
 
@@ -56,6 +77,7 @@ impl LintPass for LintPassAnimation {
                         if mask.keyframes.is_some() {
                             #[rustfmt::skip]
                             lint_messages.push(
+                                LintCode::AnimationAnimatedMask,
                                 format!("Prohibited use of animated {}", mask_display),
                                 &[
                                     meta_layer!(layer),
@@ -71,12 +93,15 @@ impl LintPass for LintPassAnimation {
         // Sub-pass #3
         {
             if let Some(framerate) = self.framerate.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(framerate.severity());
+
                 if let Some(kra_animation) = kra_archive.main_doc.image.animation.as_ref() {
                     let kra_framerate = kra_animation.framerate.value;
 
                     if !framerate.matches(&kra_framerate) {
                         #[rustfmt::skip]
                         lint_messages.push(
+                            LintCode::AnimationFramerate,
                             "Incorrect animation framerate",
                             &[
                                 meta_expected!(format!("{}fps", framerate)),
@@ -91,6 +116,8 @@ impl LintPass for LintPassAnimation {
         // Sub-pass #4
         {
             if let Some(force_layer_pin) = self.force_layer_pin.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(force_layer_pin.severity());
+
                 for layer in kra_archive.all_layers() {
                     let (layer_opt, layer_display) = force_layer_pin.get(layer);
 
@@ -101,6 +128,7 @@ impl LintPass for LintPassAnimation {
                             //   Cannot save layer pinning changes by themselves without other unrelated changes.
                             #[rustfmt::skip]
                             lint_messages.push(
+                                LintCode::AnimationUnpinnedLayer,
                                 format!("Unpinned animated {}", layer_display),
                                 &[
                                     meta_layer!(layer),
@@ -115,6 +143,8 @@ impl LintPass for LintPassAnimation {
         // Sub-pass #5
         {
             if let Some(force_mask_pin) = self.force_mask_pin.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(force_mask_pin.severity());
+
                 for (layer, mask) in kra_archive.all_masks() {
                     let (mask_opt, mask_display) = force_mask_pin.get(mask);
 
@@ -125,6 +155,7 @@ impl LintPass for LintPassAnimation {
                             //   Cannot save mask pinning changes by themselves without other unrelated changes.
                             #[rustfmt::skip]
                             lint_messages.push(
+                                LintCode::AnimationUnpinnedMask,
                                 format!("Unpinned animated {}", mask_display),
                                 &[
                                     meta_layer!(layer),
@@ -139,15 +170,26 @@ impl LintPass for LintPassAnimation {
 
         // Sub-pass #6
         {
-            for layer in kra_archive.all_layers() {
-                if layer.onion_skin == Some(true) {
-                    #[rustfmt::skip]
-                    lint_messages.push(
-                        "Active onion skins leading to false document previews",
-                        &[
-                            meta_layer!(layer),
-                        ],
-                    );
+            if let Some(warn_onion_skin) = self.warn_onion_skin.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(warn_onion_skin.severity());
+
+                if *warn_onion_skin.value() {
+                    for layer in kra_archive.all_layers() {
+                        if layer.onion_skin == Some(true) {
+                            #[rustfmt::skip]
+                            lint_messages.push_with_fix(
+                                LintCode::AnimationOnionSkin,
+                                "Active onion skins leading to false document previews",
+                                &[
+                                    meta_layer!(layer),
+                                ],
+                                LintFix::DisableOnionSkin {
+                                    layer_name: layer.name.clone(),
+                                    layer_uuid: layer.uuid.clone(),
+                                },
+                            );
+                        }
+                    }
                 }
             }
         }
@@ -155,6 +197,8 @@ impl LintPass for LintPassAnimation {
         // Sub-pass #7
         {
             if let Some(frame_delta) = self.frame_delta.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(frame_delta.severity());
+
                 for layer in kra_archive.all_layers_by_type(KraLayerType::PaintLayer) {
                     if let Some(kra_keyframes) = layer.keyframes(kra_archive)? {
                         for kra_channel in kra_keyframes {
@@ -169,6 +213,7 @@ impl LintPass for LintPassAnimation {
                                     if !frame_delta.matches(&kra_keyframe_delta) {
                                         #[rustfmt::skip]
                                         lint_messages.push(
+                                            LintCode::AnimationFrameDelta,
                                             "Incorrect paint layer frame delta",
                                             &[
                                                 meta_layer!(layer),
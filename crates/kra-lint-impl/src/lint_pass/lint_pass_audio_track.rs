@@ -3,30 +3,46 @@ use serde::{Deserialize, Serialize};
 
 use kra_parser::kra_archive::KraArchive;
 
-use crate::lint_config_fields::StringMatchExpression;
+use crate::lint_config::impl_lint_config_merge;
+use crate::lint_config_fields::{StringMatchExpression, ValueWithSeverity};
 use crate::lint_output::lint_metadata_macros::{meta_comment, meta_expected, meta_found};
-use crate::lint_output::LintMessages;
+use crate::lint_output::{LintCode, LintMessages};
 use crate::lint_pass::{LintPass, LintPassResult};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct LintPassAudioTrack {
-    audio_track_present: Option<bool>,
-    file_formats: Option<StringMatchExpression>,
-    check_missing_files: Option<bool>,
-    default_volume: Option<bool>,
+    audio_track_present: Option<ValueWithSeverity<bool>>,
+    file_formats: Option<ValueWithSeverity<StringMatchExpression>>,
+    check_missing_files: Option<ValueWithSeverity<bool>>,
+    default_volume: Option<ValueWithSeverity<bool>>,
+}
+
+impl_lint_config_merge! {
+    LintPassAudioTrack,
+    optional: [
+        audio_track_present,
+        file_formats,
+        check_missing_files,
+        default_volume,
+    ],
+    required: [],
 }
 
 impl LintPass for LintPassAudioTrack {
     fn lint(&self, kra_archive: &KraArchive, lint_messages: &mut LintMessages) -> LintPassResult {
         // Sub-pass #1
         {
-            if let Some(audio_track_present) = self.audio_track_present {
+            if let Some(audio_track_present) = self.audio_track_present.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(audio_track_present.severity());
+                let audio_track_present = *audio_track_present.value();
+
                 let kra_audio_track_present = kra_archive.main_doc.image.audio.is_some();
 
                 if audio_track_present && !kra_audio_track_present {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::AudioTrackMissingAudioTrack,
                         "Incorrect audio track",
                         &[
                             meta_comment!("Required audio track not present"),
@@ -35,6 +51,7 @@ impl LintPass for LintPassAudioTrack {
                 } else if !audio_track_present && kra_audio_track_present {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::AudioTrackUnexpectedAudioTrack,
                         "Incorrect audio track",
                         &[
                             meta_comment!("Unexpected audio track present"),
@@ -47,6 +64,8 @@ impl LintPass for LintPassAudioTrack {
         // Sub-pass #2
         {
             if let Some(file_formats) = self.file_formats.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(file_formats.severity());
+
                 // Bug: Changing audio tracks do not set the modified flag on the document.
                 //   Cannot save audio track changes by themselves without other document changes.
                 if let Some(kra_audio_track) = kra_archive.main_doc.image.audio.as_ref() {
@@ -56,6 +75,7 @@ impl LintPass for LintPassAudioTrack {
                     if !file_formats.matches(kra_audio_ext) {
                         #[rustfmt::skip]
                         lint_messages.push(
+                            LintCode::AudioTrackFileFormat,
                             "Incorrect audio track format",
                             &[
                                 meta_expected!(file_formats),
@@ -69,7 +89,9 @@ impl LintPass for LintPassAudioTrack {
 
         // Sub-pass #3
         {
-            if self.check_missing_files == Some(true) {
+            if let Some(severity) = self.check_missing_files.as_ref().and_then(ValueWithSeverity::severity_if_enabled) {
+                let mut lint_messages = lint_messages.with_severity(severity);
+
                 if let Some(kra_audio_track) = kra_archive.main_doc.image.audio.as_ref() {
                     // Audio path field stores relative paths, path traversal is intentional here.
                     let resolved_source_path = kra_archive
@@ -81,6 +103,7 @@ impl LintPass for LintPassAudioTrack {
                     if !resolved_source_path.is_file() {
                         #[rustfmt::skip]
                         lint_messages.push(
+                            LintCode::AudioTrackMissingFile,
                             "Missing audio track file",
                             &[
                                 meta_comment!(format!("Source: \"{}\"", resolved_source_path)),
@@ -93,7 +116,9 @@ impl LintPass for LintPassAudioTrack {
 
         // Sub-pass #4
         {
-            if self.default_volume == Some(true) {
+            if let Some(severity) = self.default_volume.as_ref().and_then(ValueWithSeverity::severity_if_enabled) {
+                let mut lint_messages = lint_messages.with_severity(severity);
+
                 if let Some(kra_audio_track) = kra_archive.main_doc.image.audio.as_ref() {
                     let kra_audio_muted = kra_audio_track.audio_muted.value;
                     let kra_audio_volume = kra_audio_track.audio_volume.value;
@@ -101,6 +126,7 @@ impl LintPass for LintPassAudioTrack {
                     if kra_audio_muted == 1 {
                         #[rustfmt::skip]
                         lint_messages.push(
+                            LintCode::AudioTrackMuted,
                             "Non default audio track volume",
                             &[
                                 meta_comment!("Muted audio track"),
@@ -111,6 +137,7 @@ impl LintPass for LintPassAudioTrack {
                     if kra_audio_volume != 0.5 {
                         #[rustfmt::skip]
                         lint_messages.push(
+                            LintCode::AudioTrackVolume,
                             "Non default audio track volume",
                             &[
                                 meta_expected!("50%"),
@@ -3,31 +3,52 @@ use serde::{Deserialize, Serialize};
 use kra_parser::kra_archive::KraArchive;
 use kra_parser::kra_main_doc::KraMaskType;
 
+use crate::lint_config::impl_lint_config_merge;
+use crate::lint_config_fields::ValueWithSeverity;
+use crate::lint_fix::LintFix;
 use crate::lint_output::lint_metadata_macros::{meta_layer, meta_mask};
-use crate::lint_output::LintMessages;
+use crate::lint_output::{LintCode, LintMessages};
 use crate::lint_pass::{LintPass, LintPassResult};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct LintPassColorizeMask {
-    warn_keystrokes_edit_mode: Option<bool>,
-    enforce_coloring: Option<bool>,
+    warn_keystrokes_edit_mode: Option<ValueWithSeverity<bool>>,
+    enforce_coloring: Option<ValueWithSeverity<bool>>,
+}
+
+impl_lint_config_merge! {
+    LintPassColorizeMask,
+    optional: [
+        warn_keystrokes_edit_mode,
+        enforce_coloring,
+    ],
+    required: [],
 }
 
 impl LintPass for LintPassColorizeMask {
     fn lint(&self, kra_archive: &KraArchive, lint_messages: &mut LintMessages) -> LintPassResult {
         // Sub-pass #1
         {
-            if self.warn_keystrokes_edit_mode == Some(true) {
+            if let Some(severity) =
+                self.warn_keystrokes_edit_mode.as_ref().and_then(ValueWithSeverity::severity_if_enabled)
+            {
+                let mut lint_messages = lint_messages.with_severity(severity);
+
                 for (layer, mask) in kra_archive.all_masks_by_type(KraMaskType::ColorizeMask) {
                     if mask.edit_keystrokes == Some(true) {
                         #[rustfmt::skip]
-                        lint_messages.push(
+                        lint_messages.push_with_fix(
+                            LintCode::ColorizeMaskKeystrokesEditMode,
                             "Active key stroke edit modes on colorize masks leading to false document previews",
                             &[
                                 meta_layer!(layer),
                                 meta_mask!(mask),
                             ],
+                            LintFix::DisableKeystrokesEditMode {
+                                mask_name: mask.name.clone(),
+                                mask_uuid: mask.uuid.clone(),
+                            },
                         );
                     }
                 }
@@ -36,11 +57,14 @@ impl LintPass for LintPassColorizeMask {
 
         // Sub-pass #2
         {
-            if self.enforce_coloring == Some(true) {
+            if let Some(severity) = self.enforce_coloring.as_ref().and_then(ValueWithSeverity::severity_if_enabled) {
+                let mut lint_messages = lint_messages.with_severity(severity);
+
                 for (layer, mask) in kra_archive.all_masks_by_type(KraMaskType::ColorizeMask) {
                     if mask.show_coloring == Some(false) {
                         #[rustfmt::skip]
                         lint_messages.push(
+                            LintCode::ColorizeMaskDisabledColoring,
                             "Disabled coloring on colorize mask",
                             &[
                                 meta_layer!(layer),
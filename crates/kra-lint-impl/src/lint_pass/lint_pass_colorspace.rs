@@ -5,17 +5,28 @@ use kra_parser::kra_main_doc::{KraLayerType, KraMaskType};
 
 use sha2::{Digest, Sha256};
 
-use crate::lint_config_fields::StringMatchExpression;
+use crate::lint_config::impl_lint_config_merge;
+use crate::lint_config_fields::{StringMatchExpression, ValueWithSeverity};
 use crate::lint_output::lint_metadata_macros::{meta_comment, meta_expected, meta_found, meta_layer, meta_mask};
-use crate::lint_output::LintMessages;
+use crate::lint_output::{LintCode, LintMessages};
 use crate::lint_pass::{LintPass, LintPassResult};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct LintPassColorspace {
-    colorspace: Option<StringMatchExpression>,
-    profile: Option<StringMatchExpression>,
-    profile_checksum: Option<StringMatchExpression>,
+    colorspace: Option<ValueWithSeverity<StringMatchExpression>>,
+    profile: Option<ValueWithSeverity<StringMatchExpression>>,
+    profile_checksum: Option<ValueWithSeverity<StringMatchExpression>>,
+}
+
+impl_lint_config_merge! {
+    LintPassColorspace,
+    optional: [
+        colorspace,
+        profile,
+        profile_checksum,
+    ],
+    required: [],
 }
 
 impl LintPass for LintPassColorspace {
@@ -23,11 +34,14 @@ impl LintPass for LintPassColorspace {
         // Sub-pass #1
         {
             if let Some(colorspace) = self.colorspace.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(colorspace.severity());
+
                 let kra_colorspace = &kra_archive.main_doc.image.colorspace_name;
 
                 if !colorspace.matches(kra_colorspace) {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::ColorspaceDocumentColorspace,
                         "Incorrect document color space",
                         &[
                             meta_expected!(colorspace),
@@ -41,11 +55,14 @@ impl LintPass for LintPassColorspace {
         // Sub-pass #2
         {
             if let Some(colorspace) = self.colorspace.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(colorspace.severity());
+
                 for layer in kra_archive.all_layers() {
                     if let Some(layer_colorspace) = layer.colorspace_name.as_ref() {
                         if !colorspace.matches(layer_colorspace) {
                             #[rustfmt::skip]
                             lint_messages.push(
+                                LintCode::ColorspaceLayerColorspace,
                                 "Incorrect layer color space",
                                 &[
                                     meta_layer!(layer),
@@ -62,11 +79,14 @@ impl LintPass for LintPassColorspace {
         // Sub-pass #3
         {
             if let Some(colorspace) = self.colorspace.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(colorspace.severity());
+
                 for (layer, mask) in kra_archive.all_masks() {
                     if let Some(mask_colorspace) = mask.colorspace_name.as_ref() {
                         if !colorspace.matches(mask_colorspace) {
                             #[rustfmt::skip]
                             lint_messages.push(
+                                LintCode::ColorspaceMaskColorspace,
                                 "Incorrect mask color space",
                                 &[
                                     meta_layer!(layer),
@@ -84,11 +104,14 @@ impl LintPass for LintPassColorspace {
         // Sub-pass #4
         {
             if let Some(profile) = self.profile.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(profile.severity());
+
                 let kra_profile = &kra_archive.main_doc.image.profile;
 
                 if !profile.matches(kra_profile) {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::ColorspaceDocumentProfile,
                         "Incorrect document color profile",
                         &[
                             meta_expected!(profile),
@@ -102,6 +125,8 @@ impl LintPass for LintPassColorspace {
         // Sub-pass #5
         {
             if let Some(profile_checksum) = self.profile_checksum.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(profile_checksum.severity());
+
                 for layer in kra_archive.all_layers_by_type(KraLayerType::PaintLayer) {
                     let layer_color_profile = layer.color_profile(kra_archive)?;
                     let layer_color_profile_checksum =
@@ -112,6 +137,7 @@ impl LintPass for LintPassColorspace {
                         //   the document color profile on reload. I cannot lint this data loss.
                         #[rustfmt::skip]
                         lint_messages.push(
+                            LintCode::ColorspaceLayerProfileChecksum,
                             "Incorrect layer color profile",
                             &[
                                 meta_layer!(layer),
@@ -126,6 +152,8 @@ impl LintPass for LintPassColorspace {
         // Sub-pass #6
         {
             if let Some(profile_checksum) = self.profile_checksum.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(profile_checksum.severity());
+
                 for (layer, mask) in kra_archive.all_masks_by_type(KraMaskType::ColorizeMask) {
                     let mask_color_profile = mask.colorize_color_profile(kra_archive)?;
                     let mask_color_profile_checksum =
@@ -134,6 +162,7 @@ impl LintPass for LintPassColorspace {
                     if !profile_checksum.matches(&mask_color_profile_checksum) {
                         #[rustfmt::skip]
                         lint_messages.push(
+                            LintCode::ColorspaceColorizeMaskProfile,
                             "Incorrect colorize mask color profile",
                             &[
                                 meta_layer!(layer),
@@ -149,6 +178,8 @@ impl LintPass for LintPassColorspace {
         // Sub-pass #7
         {
             if let Some(profile_checksum) = self.profile_checksum.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(profile_checksum.severity());
+
                 let document_color_profile = kra_archive.main_doc.image.color_profile(kra_archive)?;
                 let document_color_profile_checksum =
                     base16ct::lower::encode_string(&Sha256::digest(document_color_profile));
@@ -156,6 +187,7 @@ impl LintPass for LintPassColorspace {
                 if !profile_checksum.matches(&document_color_profile_checksum) {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::ColorspaceDocumentProfileChecksum,
                         "Incorrect document color profile",
                         &[
                             meta_comment!("Profile checksum mismatch"),
@@ -2,19 +2,33 @@ use serde::{Deserialize, Serialize};
 
 use kra_parser::kra_archive::KraArchive;
 
-use crate::lint_config_fields::StringMatchExpression;
+use crate::lint_config::impl_lint_config_merge;
+use crate::lint_config_fields::{StringMatchExpression, ValueWithSeverity};
+use crate::lint_fix::LintFix;
 use crate::lint_output::lint_metadata_macros::{meta_comment, meta_expected, meta_found, meta_missing_field};
-use crate::lint_output::LintMessages;
+use crate::lint_output::{LintCode, LintMessages};
 use crate::lint_pass::{LintPass, LintPassResult};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct LintPassCopyright {
-    copyright_line: Option<StringMatchExpression>,
-    copyright_disclaimer: Option<StringMatchExpression>,
-    studio_name: Option<StringMatchExpression>,
-    initial_author: Option<StringMatchExpression>,
-    ensure_author_exists: Option<bool>,
+    copyright_line: Option<ValueWithSeverity<StringMatchExpression>>,
+    copyright_disclaimer: Option<ValueWithSeverity<StringMatchExpression>>,
+    studio_name: Option<ValueWithSeverity<StringMatchExpression>>,
+    initial_author: Option<ValueWithSeverity<StringMatchExpression>>,
+    ensure_author_exists: Option<ValueWithSeverity<bool>>,
+}
+
+impl_lint_config_merge! {
+    LintPassCopyright,
+    optional: [
+        copyright_line,
+        copyright_disclaimer,
+        studio_name,
+        initial_author,
+        ensure_author_exists,
+    ],
+    required: [],
 }
 
 impl LintPass for LintPassCopyright {
@@ -22,24 +36,34 @@ impl LintPass for LintPassCopyright {
         // Sub-pass #1
         {
             if let Some(copyright_line) = self.copyright_line.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(copyright_line.severity());
+
                 let kra_copyright_line = &kra_archive.document_info.about.license;
 
+                // Only exact copyright lines can be filled in
+                let copyright_line_fix =
+                    copyright_line.exact_value().map(|copyright_line| LintFix::SetCopyrightLine(copyright_line.into()));
+
                 if kra_copyright_line.is_empty() {
                     #[rustfmt::skip]
-                    lint_messages.push(
+                    lint_messages.push_with_fix(
+                        LintCode::CopyrightMissingCopyrightLine,
                         "Missing copyright line",
                         &[
                             meta_expected!(copyright_line),
                         ],
+                        copyright_line_fix,
                     );
                 } else if !copyright_line.matches(kra_copyright_line) {
                     #[rustfmt::skip]
-                    lint_messages.push(
+                    lint_messages.push_with_fix(
+                        LintCode::CopyrightCopyrightLine,
                         "Incorrect copyright line",
                         &[
                             meta_expected!(copyright_line),
                             meta_found!(kra_copyright_line),
                         ],
+                        copyright_line_fix,
                     );
                 }
             }
@@ -48,11 +72,14 @@ impl LintPass for LintPassCopyright {
         // Sub-pass #2
         {
             if let Some(copyright_disclaimer) = self.copyright_disclaimer.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(copyright_disclaimer.severity());
+
                 let kra_copyright_disclaimer = &kra_archive.document_info.about.r#abstract;
 
                 if kra_copyright_disclaimer.is_empty() {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::CopyrightMissingCopyrightDisclaimer,
                         "Missing copyright disclaimer",
                         &[
                             meta_expected!(copyright_disclaimer),
@@ -61,6 +88,7 @@ impl LintPass for LintPassCopyright {
                 } else if !copyright_disclaimer.matches(kra_copyright_disclaimer) {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::CopyrightCopyrightDisclaimer,
                         "Incorrect copyright disclaimer",
                         &[
                             meta_expected!(copyright_disclaimer),
@@ -74,11 +102,14 @@ impl LintPass for LintPassCopyright {
         // Sub-pass #3
         {
             if let Some(initial_author) = self.initial_author.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(initial_author.severity());
+
                 let kra_initial_author = &kra_archive.document_info.about.initial_creator;
 
                 if kra_initial_author.is_empty() {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::CopyrightMissingInitialAuthor,
                         "Missing author information",
                         &[
                             meta_missing_field!("Initial creator"),
@@ -87,6 +118,7 @@ impl LintPass for LintPassCopyright {
                 } else if !initial_author.matches(kra_initial_author) {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::CopyrightInitialAuthor,
                         "Incorrect initial author",
                         &[
                             meta_expected!(initial_author),
@@ -99,7 +131,10 @@ impl LintPass for LintPassCopyright {
 
         // Sub-pass #4
         {
-            if self.ensure_author_exists == Some(true) {
+            if let Some(severity) = self.ensure_author_exists.as_ref().and_then(ValueWithSeverity::severity_if_enabled)
+            {
+                let mut lint_messages = lint_messages.with_severity(severity);
+
                 let kra_author_full_name = &kra_archive.document_info.author.full_name;
                 let kra_author_first_name = &kra_archive.document_info.author.creator_first_name;
                 let kra_author_last_name = &kra_archive.document_info.author.creator_last_name;
@@ -107,6 +142,7 @@ impl LintPass for LintPassCopyright {
                 if kra_author_full_name.is_empty() {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::CopyrightMissingAuthorFullName,
                         "Missing author information",
                         &[
                             meta_missing_field!("Author full name"),
@@ -117,6 +153,7 @@ impl LintPass for LintPassCopyright {
                 if kra_author_first_name.is_empty() {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::CopyrightMissingAuthorFirstName,
                         "Missing author information",
                         &[
                             meta_missing_field!("Author first name"),
@@ -127,6 +164,7 @@ impl LintPass for LintPassCopyright {
                 if kra_author_last_name.is_empty() {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::CopyrightMissingAuthorLastName,
                         "Missing author information",
                         &[
                             meta_missing_field!("Author last name"),
@@ -139,6 +177,7 @@ impl LintPass for LintPassCopyright {
                     if !kra_author_full_name.contains(kra_author_first_name) {
                         #[rustfmt::skip]
                         lint_messages.push(
+                            LintCode::CopyrightInconsistentFirstName,
                             "Inconsistent author information",
                             &[
                                 meta_comment!(format!("First name: \"{}\"", kra_author_first_name.to_string())),
@@ -153,6 +192,7 @@ impl LintPass for LintPassCopyright {
                     if !kra_author_full_name.contains(kra_author_last_name) {
                         #[rustfmt::skip]
                         lint_messages.push(
+                            LintCode::CopyrightInconsistentLastName,
                             "Inconsistent author information",
                             &[
                                 meta_comment!(format!("Last name: \"{}\"", kra_author_last_name.to_string())),
@@ -167,11 +207,14 @@ impl LintPass for LintPassCopyright {
         // Sub-pass #5
         {
             if let Some(studio_name) = self.studio_name.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(studio_name.severity());
+
                 let kra_studio_name = &kra_archive.document_info.author.company;
 
                 if kra_studio_name.is_empty() {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::CopyrightMissingStudioName,
                         "Missing studio name",
                         &[
                             meta_expected!(studio_name),
@@ -180,6 +223,7 @@ impl LintPass for LintPassCopyright {
                 } else if !studio_name.matches(kra_studio_name) {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::CopyrightStudioName,
                         "Incorrect studio name",
                         &[
                             meta_expected!(studio_name),
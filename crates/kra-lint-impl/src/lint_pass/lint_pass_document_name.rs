@@ -2,39 +2,59 @@ use serde::{Deserialize, Serialize};
 
 use kra_parser::kra_archive::KraArchive;
 
-use crate::lint_config_fields::StringMatchExpression;
+use crate::lint_config::impl_lint_config_merge;
+use crate::lint_config_fields::{StringMatchExpression, ValueWithSeverity};
+use crate::lint_fix::LintFix;
 use crate::lint_output::lint_metadata_macros::{meta_expected, meta_found};
-use crate::lint_output::LintMessages;
+use crate::lint_output::{LintCode, LintMessages};
 use crate::lint_pass::{LintPass, LintPassResult};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct LintPassDocumentName {
-    document_name: StringMatchExpression,
+    document_name: ValueWithSeverity<StringMatchExpression>,
+}
+
+impl_lint_config_merge! {
+    LintPassDocumentName,
+    optional: [],
+    required: [
+        document_name,
+    ],
 }
 
 impl LintPass for LintPassDocumentName {
     fn lint(&self, kra_archive: &KraArchive, lint_messages: &mut LintMessages) -> LintPassResult {
         // Sub-pass #1
         {
+            let mut lint_messages = lint_messages.with_severity(self.document_name.severity());
+
             let kra_document_name = &kra_archive.document_info.about.title;
 
+            // Only exact document names can be filled in
+            let document_name_fix =
+                self.document_name.exact_value().map(|document_name| LintFix::SetDocumentName(document_name.into()));
+
             if kra_document_name.is_empty() {
                 #[rustfmt::skip]
-                lint_messages.push(
+                lint_messages.push_with_fix(
+                    LintCode::DocumentNameMissingDocumentName,
                     "Missing document name",
                     &[
                         meta_expected!(self.document_name),
                     ],
+                    document_name_fix,
                 );
             } else if !self.document_name.matches(kra_document_name) {
                 #[rustfmt::skip]
-                lint_messages.push(
+                lint_messages.push_with_fix(
+                    LintCode::DocumentNameDocumentName,
                     "Incorrect document name",
                     &[
                         meta_expected!(self.document_name),
                         meta_found!(kra_document_name),
                     ],
+                    document_name_fix,
                 );
             }
         }
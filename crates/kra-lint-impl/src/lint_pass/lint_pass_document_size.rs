@@ -1,16 +1,16 @@
 use serde::{Deserialize, Serialize};
 
 use kra_parser::kra_archive::KraArchive;
-use std_ext::OptionExt;
 
-use crate::lint_config_fields::NumberMatchExpression;
+use crate::lint_config::impl_lint_config_merge;
+use crate::lint_config_fields::{NumberMatchExpression, ValueWithSeverity};
 use crate::lint_output::lint_metadata_macros::{meta_comment, meta_expected, meta_found};
-use crate::lint_output::LintMessages;
+use crate::lint_output::{LintCode, LintMessages};
 use crate::lint_pass::{LintPass, LintPassResult};
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 struct LintPassDocumentSizeEntry {
     width: Option<NumberMatchExpression<usize>>,
@@ -23,10 +23,10 @@ struct LintPassDocumentSizeEntry {
 impl LintPassDocumentSizeEntry {
     fn matches(&self, kra_width: usize, kra_height: usize, kra_resolution: f64) -> bool {
         let matches_inner = |kra_width: usize, kra_height: usize, kra_resolution: f64| -> bool {
-            self.width.as_ref().is_none_or(|m| m.matches(&kra_width))
-                && self.height.as_ref().is_none_or(|m| m.matches(&kra_height))
-                && self.aspect_ratio.as_ref().is_none_or(|m| m.matches(&(kra_width as f64 / kra_height as f64)))
-                && self.resolution.as_ref().is_none_or(|m| m.matches(&kra_resolution))
+            self.width.as_ref().map_or(true, |m| m.matches(&kra_width))
+                && self.height.as_ref().map_or(true, |m| m.matches(&kra_height))
+                && self.aspect_ratio.as_ref().map_or(true, |m| m.matches(&(kra_width as f64 / kra_height as f64)))
+                && self.resolution.as_ref().map_or(true, |m| m.matches(&kra_resolution))
         };
 
         if self.rotation == Some(true) {
@@ -62,16 +62,26 @@ impl LintPassDocumentSizeEntry {
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct LintPassDocumentSize {
-    document_sizes: Vec<LintPassDocumentSizeEntry>,
+    document_sizes: ValueWithSeverity<Vec<LintPassDocumentSizeEntry>>,
+}
+
+impl_lint_config_merge! {
+    LintPassDocumentSize,
+    optional: [],
+    required: [
+        document_sizes,
+    ],
 }
 
 impl LintPass for LintPassDocumentSize {
     fn lint(&self, kra_archive: &KraArchive, lint_messages: &mut LintMessages) -> LintPassResult {
         // Sub-pass #1
         {
+            let mut lint_messages = lint_messages.with_severity(self.document_sizes.severity());
+
             let kra_width = kra_archive.main_doc.image.width;
             let kra_height = kra_archive.main_doc.image.height;
             let kra_resolution = kra_archive.main_doc.image.x_res;
@@ -89,6 +99,7 @@ impl LintPass for LintPassDocumentSize {
 
                 #[rustfmt::skip]
                 lint_messages.push(
+                    LintCode::DocumentSizeDocumentSize,
                     "Incorrect document size",
                     &[
                         meta_expected!(document_size_list),
@@ -100,12 +111,15 @@ impl LintPass for LintPassDocumentSize {
 
         // Sub-pass #2
         {
+            let mut lint_messages = lint_messages.with_severity(self.document_sizes.severity());
+
             let kra_resolution_x = kra_archive.main_doc.image.x_res;
             let kra_resolution_y = kra_archive.main_doc.image.y_res;
 
             if kra_resolution_x != kra_resolution_y {
                 #[rustfmt::skip]
                 lint_messages.push(
+                    LintCode::DocumentSizeInconsistentResolution,
                     "Inconsistent horizontal and vertical document resolution",
                     &[
                         meta_comment!(format!("Horizontal: {}dpi, Vertical: {}dpi", kra_resolution_x, kra_resolution_y)),
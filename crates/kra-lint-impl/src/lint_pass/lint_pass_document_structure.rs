@@ -7,17 +7,19 @@ use kra_parser::kra_color_label::KraColorLabel;
 use kra_parser::kra_main_doc::{
     KraLayerType, KraMainDocLayer, KraMainDocLayerContainer, KraMainDocMask, KraMainDocMaskContainer, KraMaskType,
 };
-use std_ext::OptionExt;
 
-use crate::lint_config_fields::{GenericMatchExpression, NumberMatchExpression, StringMatchExpression};
+use crate::lint_config::impl_lint_config_merge;
+use crate::lint_config_fields::{
+    GenericMatchExpression, NumberMatchExpression, StringMatchExpression, ValueWithSeverity,
+};
 use crate::lint_output::lint_metadata_macros::{meta_comment, meta_expected, meta_found, meta_layer, meta_mask};
-use crate::lint_output::LintMessages;
+use crate::lint_output::{LintCode, LintMessages, LintMessagesWithSeverity};
 use crate::lint_pass::{LintPass, LintPassResult};
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 
 #[rustfmt::skip]
-#[derive(Debug, Deserialize, Serialize, Default, IntoIterator)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default, IntoIterator)]
 struct DocumentStructureMaskContainer (
     #[into_iterator(ref)]
     Vec<DocumentStructureMask>,
@@ -25,7 +27,7 @@ struct DocumentStructureMaskContainer (
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 struct DocumentStructureMask {
     mask_name: Option<StringMatchExpression>,
     mask_type: Option<GenericMatchExpression<KraMaskType>>,
@@ -36,9 +38,9 @@ struct DocumentStructureMask {
 impl DocumentStructureMask {
     #[rustfmt::skip]
     fn matches(&self, kra_mask: &KraMainDocMask) -> bool {
-        self.mask_name.as_ref().is_none_or(|m| m.matches(&kra_mask.name))
-            && self.mask_type.as_ref().is_none_or(|m| m.matches(&kra_mask.mask_type))
-            && self.mask_color.as_ref().is_none_or(|m| {
+        self.mask_name.as_ref().map_or(true, |m| m.matches(&kra_mask.name))
+            && self.mask_type.as_ref().map_or(true, |m| m.matches(&kra_mask.mask_type))
+            && self.mask_color.as_ref().map_or(true, |m| {
                 kra_mask.color_label.as_ref().is_some_and(|color_label| m.matches(color_label))
             })
     }
@@ -56,7 +58,7 @@ impl DocumentStructureMask {
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 
 #[rustfmt::skip]
-#[derive(Debug, Deserialize, Serialize, Default, IntoIterator)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default, IntoIterator)]
 struct DocumentStructureLayerContainer (
     #[into_iterator(ref)]
     Vec<DocumentStructureLayer>,
@@ -64,7 +66,7 @@ struct DocumentStructureLayerContainer (
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 struct DocumentStructureLayer {
     layer_name: Option<StringMatchExpression>,
     layer_type: Option<GenericMatchExpression<KraLayerType>>,
@@ -76,9 +78,9 @@ struct DocumentStructureLayer {
 
 impl DocumentStructureLayer {
     fn matches(&self, kra_layer: &KraMainDocLayer) -> bool {
-        self.layer_name.as_ref().is_none_or(|m| m.matches(&kra_layer.name))
-            && self.layer_type.as_ref().is_none_or(|m| m.matches(&kra_layer.layer_type))
-            && self.layer_color.as_ref().is_none_or(|m| m.matches(&kra_layer.color_label))
+        self.layer_name.as_ref().map_or(true, |m| m.matches(&kra_layer.name))
+            && self.layer_type.as_ref().map_or(true, |m| m.matches(&kra_layer.layer_type))
+            && self.layer_color.as_ref().map_or(true, |m| m.matches(&kra_layer.color_label))
     }
 
     fn message_fmt(&self) -> String {
@@ -93,10 +95,18 @@ impl DocumentStructureLayer {
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct LintPassDocumentStructure {
-    layers: DocumentStructureLayerContainer,
+    layers: ValueWithSeverity<DocumentStructureLayerContainer>,
+}
+
+impl_lint_config_merge! {
+    LintPassDocumentStructure,
+    optional: [],
+    required: [
+        layers,
+    ],
 }
 
 impl LintPass for LintPassDocumentStructure {
@@ -106,7 +116,7 @@ impl LintPass for LintPassDocumentStructure {
             fn compare_masks(
                 kra_mask_container: &KraMainDocMaskContainer,
                 lint_mask_container: &DocumentStructureMaskContainer,
-                lint_messages: &mut LintMessages,
+                lint_messages: &mut LintMessagesWithSeverity,
             ) -> LintPassResult {
                 let mut kra_mask_iterator = kra_mask_container.into_iter();
 
@@ -121,6 +131,7 @@ impl LintPass for LintPassDocumentStructure {
                     if !mask_count.matches(&kra_matching_masks.len()) {
                         #[rustfmt::skip]
                         lint_messages.push(
+                            LintCode::DocumentStructureMaskRepetition,
                             "Incorrect document structure",
                             &[
                                 meta_comment!("Mask repetition mismatch"),
@@ -136,6 +147,7 @@ impl LintPass for LintPassDocumentStructure {
                 for kra_extra_mask in kra_mask_iterator {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::DocumentStructureExtraMask,
                         "Incorrect document structure",
                         &[
                             meta_comment!("Extra mask"),
@@ -150,7 +162,7 @@ impl LintPass for LintPassDocumentStructure {
             fn compare_layers(
                 kra_layer_container: &KraMainDocLayerContainer,
                 lint_layer_container: &DocumentStructureLayerContainer,
-                lint_messages: &mut LintMessages,
+                lint_messages: &mut LintMessagesWithSeverity,
             ) -> LintPassResult {
                 let dummy_kra_layer_container = KraMainDocLayerContainer::default();
                 let dummy_kra_mask_container = KraMainDocMaskContainer::default();
@@ -193,6 +205,7 @@ impl LintPass for LintPassDocumentStructure {
                     } else {
                         #[rustfmt::skip]
                         lint_messages.push(
+                            LintCode::DocumentStructureLayerRepetition,
                             "Incorrect document structure",
                             &[
                                 meta_comment!("Layer repetition mismatch"),
@@ -208,6 +221,7 @@ impl LintPass for LintPassDocumentStructure {
                 for kra_extra_layer in kra_layer_iterator {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::DocumentStructureExtraLayer,
                         "Incorrect document structure",
                         &[
                             meta_comment!("Extra layer"),
@@ -219,7 +233,9 @@ impl LintPass for LintPassDocumentStructure {
                 Ok(())
             }
 
-            compare_layers(&kra_archive.main_doc.image.layer_container, &self.layers, lint_messages)?;
+            let mut lint_messages = lint_messages.with_severity(self.layers.severity());
+
+            compare_layers(&kra_archive.main_doc.image.layer_container, &self.layers, &mut lint_messages)?;
         }
 
         Ok(())
@@ -4,17 +4,28 @@ use serde::{Deserialize, Serialize};
 use kra_parser::kra_archive::KraArchive;
 use kra_parser::kra_main_doc::{KraLayerType, KraScalingMethod};
 
-use crate::lint_config_fields::{GenericMatchExpression, StringMatchExpression};
+use crate::lint_config::impl_lint_config_merge;
+use crate::lint_config_fields::{GenericMatchExpression, StringMatchExpression, ValueWithSeverity};
 use crate::lint_output::lint_metadata_macros::{meta_comment, meta_expected, meta_found, meta_layer};
-use crate::lint_output::LintMessages;
+use crate::lint_output::{LintCode, LintMessages};
 use crate::lint_pass::{LintPass, LintPassResult};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct LintPassFileLayers {
-    file_formats: Option<StringMatchExpression>,
-    check_missing_files: Option<bool>,
-    scaling_method: Option<GenericMatchExpression<KraScalingMethod>>,
+    file_formats: Option<ValueWithSeverity<StringMatchExpression>>,
+    check_missing_files: Option<ValueWithSeverity<bool>>,
+    scaling_method: Option<ValueWithSeverity<GenericMatchExpression<KraScalingMethod>>>,
+}
+
+impl_lint_config_merge! {
+    LintPassFileLayers,
+    optional: [
+        file_formats,
+        check_missing_files,
+        scaling_method,
+    ],
+    required: [],
 }
 
 impl LintPass for LintPassFileLayers {
@@ -22,6 +33,8 @@ impl LintPass for LintPassFileLayers {
         // Sub-pass #1
         {
             if let Some(file_formats) = self.file_formats.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(file_formats.severity());
+
                 for layer in kra_archive.all_layers_by_type(KraLayerType::FileLayer) {
                     if let Some(source) = layer.source.as_ref() {
                         let source_ext = Utf8Path::new(source).extension().unwrap_or("");
@@ -29,6 +42,7 @@ impl LintPass for LintPassFileLayers {
                         if !file_formats.matches(source_ext) {
                             #[rustfmt::skip]
                             lint_messages.push(
+                                LintCode::FileLayersFileFormat,
                                 "Incorrect file layer source image format",
                                 &[
                                     meta_layer!(layer),
@@ -44,7 +58,9 @@ impl LintPass for LintPassFileLayers {
 
         // Sub-pass #2
         {
-            if self.check_missing_files == Some(true) {
+            if let Some(severity) = self.check_missing_files.as_ref().and_then(ValueWithSeverity::severity_if_enabled) {
+                let mut lint_messages = lint_messages.with_severity(severity);
+
                 for layer in kra_archive.all_layers_by_type(KraLayerType::FileLayer) {
                     if let Some(source) = layer.source.as_ref() {
                         // File layers store relative paths, path traversal is intentional here.
@@ -57,6 +73,7 @@ impl LintPass for LintPassFileLayers {
                         if !resolved_source_path.is_file() {
                             #[rustfmt::skip]
                             lint_messages.push(
+                                LintCode::FileLayersMissingFile,
                                 "Missing file layer source image",
                                 &[
                                     meta_layer!(layer),
@@ -72,11 +89,14 @@ impl LintPass for LintPassFileLayers {
         // Sub-pass #3
         {
             if let Some(scaling_method) = self.scaling_method.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(scaling_method.severity());
+
                 for layer in kra_archive.all_layers_by_type(KraLayerType::FileLayer) {
                     if let Some(kra_scaling_method) = layer.scaling_method.as_ref() {
                         if !scaling_method.matches(kra_scaling_method) {
                             #[rustfmt::skip]
                             lint_messages.push(
+                                LintCode::FileLayersScalingMethod,
                                 "Incorrect file layer scaling method",
                                 &[
                                     meta_layer!(layer),
@@ -88,6 +108,7 @@ impl LintPass for LintPassFileLayers {
                     } else {
                         #[rustfmt::skip]
                         lint_messages.push(
+                            LintCode::FileLayersMissingScalingMethod,
                             "Missing file layer scaling method",
                             &[
                                 meta_layer!(layer),
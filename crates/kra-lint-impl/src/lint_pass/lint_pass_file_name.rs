@@ -2,26 +2,38 @@ use serde::{Deserialize, Serialize};
 
 use kra_parser::kra_archive::KraArchive;
 
-use crate::lint_config_fields::StringMatchExpression;
+use crate::lint_config::impl_lint_config_merge;
+use crate::lint_config_fields::{StringMatchExpression, ValueWithSeverity};
 use crate::lint_output::lint_metadata_macros::{meta_expected, meta_found};
-use crate::lint_output::LintMessages;
+use crate::lint_output::{LintCode, LintMessages};
 use crate::lint_pass::{LintPass, LintPassResult};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct LintPassFileName {
-    file_name: StringMatchExpression,
+    file_name: ValueWithSeverity<StringMatchExpression>,
+}
+
+impl_lint_config_merge! {
+    LintPassFileName,
+    optional: [],
+    required: [
+        file_name,
+    ],
 }
 
 impl LintPass for LintPassFileName {
     fn lint(&self, kra_archive: &KraArchive, lint_messages: &mut LintMessages) -> LintPassResult {
         // Sub-pass #1
         {
+            let mut lint_messages = lint_messages.with_severity(self.file_name.severity());
+
             let kra_file_name = kra_archive.zip_path.file_name().expect("Failed to get file name");
 
             if !self.file_name.matches(kra_file_name) {
                 #[rustfmt::skip]
                 lint_messages.push(
+                    LintCode::FileNameFileName,
                     "Incorrect file name",
                     &[
                         meta_expected!(self.file_name),
@@ -5,15 +5,24 @@ use serde::{Deserialize, Serialize};
 
 use kra_parser::kra_archive::KraArchive;
 
-use crate::lint_config_fields::StringMatchExpression;
+use crate::lint_config::impl_lint_config_merge;
+use crate::lint_config_fields::{StringMatchExpression, ValueWithSeverity};
 use crate::lint_output::lint_metadata_macros::{meta_expected, meta_found};
-use crate::lint_output::LintMessages;
+use crate::lint_output::{LintCode, LintMessages};
 use crate::lint_pass::{LintPass, LintPassResult};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct LintPassFilePermissions {
-    unix_permissions: Option<StringMatchExpression>,
+    unix_permissions: Option<ValueWithSeverity<StringMatchExpression>>,
+}
+
+impl_lint_config_merge! {
+    LintPassFilePermissions,
+    optional: [
+        unix_permissions,
+    ],
+    required: [],
 }
 
 impl LintPass for LintPassFilePermissions {
@@ -21,6 +30,8 @@ impl LintPass for LintPassFilePermissions {
         // Sub-pass #1
         {
             if let Some(unix_permissions) = self.unix_permissions.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(unix_permissions.severity());
+
                 #[cfg(unix)]
                 {
                     let kra_file_permissions = unix_mode::to_string(kra_archive.zip_path.metadata()?.mode());
@@ -28,6 +39,7 @@ impl LintPass for LintPassFilePermissions {
                     if !unix_permissions.matches(&kra_file_permissions) {
                         #[rustfmt::skip]
                         lint_messages.push(
+                            LintCode::FilePermissionsUnixPermissions,
                             "Incorrect UNIX file permissions",
                             &[
                                 meta_expected!(unix_permissions),
@@ -41,6 +53,7 @@ impl LintPass for LintPassFilePermissions {
                 {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::FilePermissionsUnsupportedPlatform,
                         "Platform doesn't support UNIX file permissions",
                         &[
                             meta_expected!("unix"),
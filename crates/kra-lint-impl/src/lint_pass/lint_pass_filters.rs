@@ -4,16 +4,26 @@ use kra_parser::kra_archive::KraArchive;
 use kra_parser::kra_filter_config::KraPixelizeFilterConfig;
 use kra_parser::kra_main_doc::{KraLayerType, KraMaskType};
 
-use crate::lint_config_fields::StringMatchExpression;
+use crate::lint_config::impl_lint_config_merge;
+use crate::lint_config_fields::{StringMatchExpression, ValueWithSeverity};
 use crate::lint_output::lint_metadata_macros::{meta_comment, meta_expected, meta_found, meta_layer, meta_mask};
-use crate::lint_output::LintMessages;
+use crate::lint_output::{LintCode, LintMessages};
 use crate::lint_pass::{LintPass, LintPassResult};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct LintPassFilters {
-    filter_types: Option<StringMatchExpression>,
-    pixiv_mosaics: Option<bool>,
+    filter_types: Option<ValueWithSeverity<StringMatchExpression>>,
+    pixiv_mosaics: Option<ValueWithSeverity<bool>>,
+}
+
+impl_lint_config_merge! {
+    LintPassFilters,
+    optional: [
+        filter_types,
+        pixiv_mosaics,
+    ],
+    required: [],
 }
 
 impl LintPass for LintPassFilters {
@@ -21,11 +31,14 @@ impl LintPass for LintPassFilters {
         // Sub-pass #1
         {
             if let Some(filter_types) = self.filter_types.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(filter_types.severity());
+
                 for layer in kra_archive.all_layers_by_type(KraLayerType::FilterLayer) {
                     if let Some(kra_filter_type) = layer.filter_name.as_ref() {
                         if !filter_types.matches(kra_filter_type) {
                             #[rustfmt::skip]
                             lint_messages.push(
+                                LintCode::FiltersFilterLayerType,
                                 "Incorrect filter layer type",
                                 &[
                                     meta_layer!(layer),
@@ -42,11 +55,14 @@ impl LintPass for LintPassFilters {
         // Sub-pass #2
         {
             if let Some(filter_types) = self.filter_types.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(filter_types.severity());
+
                 for (layer, mask) in kra_archive.all_masks_by_type(KraMaskType::FilterMask) {
                     if let Some(kra_filter_type) = mask.filter_name.as_ref() {
                         if !filter_types.matches(kra_filter_type) {
                             #[rustfmt::skip]
                             lint_messages.push(
+                                LintCode::FiltersFilterMaskType,
                                 "Incorrect filter mask type",
                                 &[
                                     meta_layer!(layer),
@@ -63,7 +79,9 @@ impl LintPass for LintPassFilters {
 
         // Sub-pass #3
         {
-            if self.pixiv_mosaics == Some(true) {
+            if let Some(severity) = self.pixiv_mosaics.as_ref().and_then(ValueWithSeverity::severity_if_enabled) {
+                let mut lint_messages = lint_messages.with_severity(severity);
+
                 let minimum_mosaic_size = {
                     let kra_document_width = kra_archive.main_doc.image.width;
                     let kra_document_height = kra_archive.main_doc.image.height;
@@ -77,6 +95,7 @@ impl LintPass for LintPassFilters {
                         if filter_config.pixel_width != filter_config.pixel_height {
                             #[rustfmt::skip]
                             lint_messages.push(
+                                LintCode::FiltersNonSquareLayerMosaic,
                                 "Incorrect Pixiv mosaics",
                                 &[
                                     meta_comment!("Non-square filter layer mosaics"),
@@ -88,6 +107,7 @@ impl LintPass for LintPassFilters {
                         } else if filter_config.pixel_width < minimum_mosaic_size {
                             #[rustfmt::skip]
                             lint_messages.push(
+                                LintCode::FiltersLayerMosaicSize,
                                 "Incorrect Pixiv mosaics",
                                 &[
                                     meta_comment!("Insufficient filter layer mosaic size"),
@@ -101,6 +121,7 @@ impl LintPass for LintPassFilters {
                         if layer.opacity < 255 {
                             #[rustfmt::skip]
                             lint_messages.push(
+                                LintCode::FiltersTransparentLayerMosaic,
                                 "Incorrect Pixiv mosaics",
                                 &[
                                     meta_comment!("Transparent mosaic filter layer"),
@@ -118,6 +139,7 @@ impl LintPass for LintPassFilters {
                         if filter_config.pixel_width != filter_config.pixel_height {
                             #[rustfmt::skip]
                             lint_messages.push(
+                                LintCode::FiltersNonSquareMaskMosaic,
                                 "Incorrect Pixiv mosaics",
                                 &[
                                     meta_comment!("Non-square filter mask mosaics"),
@@ -130,6 +152,7 @@ impl LintPass for LintPassFilters {
                         } else if filter_config.pixel_width < minimum_mosaic_size {
                             #[rustfmt::skip]
                             lint_messages.push(
+                                LintCode::FiltersMaskMosaicSize,
                                 "Incorrect Pixiv mosaics",
                                 &[
                                     meta_comment!("Insufficient filter mask mosaic size"),
@@ -2,22 +2,34 @@ use serde::{Deserialize, Serialize};
 
 use kra_parser::kra_archive::KraArchive;
 
-use crate::lint_config_fields::{ValueByLayerType, ValueByMaskType};
+use crate::lint_config::impl_lint_config_merge;
+use crate::lint_config_fields::{ValueByLayerType, ValueByMaskType, ValueWithSeverity};
 use crate::lint_output::lint_metadata_macros::{meta_layer, meta_mask};
-use crate::lint_output::LintMessages;
+use crate::lint_output::{LintCode, LintMessages};
 use crate::lint_pass::{LintPass, LintPassResult};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct LintPassHiddenSurface {
-    hidden_layers: ValueByLayerType<bool>,
-    hidden_masks: ValueByMaskType<bool>,
+    hidden_layers: ValueWithSeverity<ValueByLayerType<bool>>,
+    hidden_masks: ValueWithSeverity<ValueByMaskType<bool>>,
+}
+
+impl_lint_config_merge! {
+    LintPassHiddenSurface,
+    optional: [],
+    required: [
+        hidden_layers,
+        hidden_masks,
+    ],
 }
 
 impl LintPass for LintPassHiddenSurface {
     fn lint(&self, kra_archive: &KraArchive, lint_messages: &mut LintMessages) -> LintPassResult {
         // Sub-pass #1
         {
+            let mut lint_messages = lint_messages.with_severity(self.hidden_layers.severity());
+
             for layer in kra_archive.all_layers() {
                 let (layer_opt, layer_display) = self.hidden_layers.get(layer);
 
@@ -26,6 +38,7 @@ impl LintPass for LintPassHiddenSurface {
                     if !layer.visible || (layer.opacity == 0) {
                         #[rustfmt::skip]
                         lint_messages.push(
+                            LintCode::HiddenSurfaceHiddenLayer,
                             format!("Prohibited hidden {}", layer_display),
                             &[
                                 meta_layer!(layer),
@@ -38,6 +51,8 @@ impl LintPass for LintPassHiddenSurface {
 
         // Sub-pass #2
         {
+            let mut lint_messages = lint_messages.with_severity(self.hidden_masks.severity());
+
             for (layer, mask) in kra_archive.all_masks() {
                 let (mask_opt, mask_display) = self.hidden_masks.get(mask);
 
@@ -48,6 +63,7 @@ impl LintPass for LintPassHiddenSurface {
                     if !mask.visible {
                         #[rustfmt::skip]
                         lint_messages.push(
+                            LintCode::HiddenSurfaceHiddenMask,
                             format!("Prohibited hidden {}", mask_display),
                             &[
                                 meta_layer!(layer),
@@ -2,21 +2,32 @@ use serde::{Deserialize, Serialize};
 
 use kra_parser::kra_archive::KraArchive;
 
-use crate::lint_config_fields::ValueByLayerType;
+use crate::lint_config::impl_lint_config_merge;
+use crate::lint_config_fields::{ValueByLayerType, ValueWithSeverity};
 use crate::lint_output::lint_metadata_macros::meta_layer;
-use crate::lint_output::LintMessages;
+use crate::lint_output::{LintCode, LintMessages};
 use crate::lint_pass::{LintPass, LintPassResult};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct LintPassLayerStyles {
-    styleable_layers: ValueByLayerType<bool>,
+    styleable_layers: ValueWithSeverity<ValueByLayerType<bool>>,
+}
+
+impl_lint_config_merge! {
+    LintPassLayerStyles,
+    optional: [],
+    required: [
+        styleable_layers,
+    ],
 }
 
 impl LintPass for LintPassLayerStyles {
     fn lint(&self, kra_archive: &KraArchive, lint_messages: &mut LintMessages) -> LintPassResult {
         // Sub-pass #1
         {
+            let mut lint_messages = lint_messages.with_severity(self.styleable_layers.severity());
+
             for layer in kra_archive.all_layers() {
                 let (layer_opt, layer_display) = self.styleable_layers.get(layer);
 
@@ -28,6 +39,7 @@ impl LintPass for LintPassLayerStyles {
                         //  are present.
                         #[rustfmt::skip]
                         lint_messages.push(
+                            LintCode::LayerStylesLayerStyle,
                             format!("Prohibited {} styles", layer_display),
                             &[
                                 meta_layer!(layer),
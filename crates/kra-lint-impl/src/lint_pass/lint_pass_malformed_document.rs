@@ -6,24 +6,40 @@ use kra_parser::kra_main_doc::KraLayerType;
 
 use ziparchive_ext::ZipArchiveExt;
 
-use crate::lint_output::lint_metadata_macros::{meta_bug, meta_comment, meta_layer};
-use crate::lint_output::LintMessages;
+use crate::lint_config::impl_lint_config_merge;
+use crate::lint_output::lint_metadata_macros::{meta_bug, meta_comment, meta_expected, meta_found, meta_layer};
+use crate::lint_output::{LintCode, LintMessages, LintSeverity};
 use crate::lint_pass::{LintPass, LintPassResult};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
-pub(crate) struct LintPassMalformedDocument {}
+pub(crate) struct LintPassMalformedDocument {
+    severity: Option<LintSeverity>,
+    decode_merged_image: Option<bool>,
+}
+
+impl_lint_config_merge! {
+    LintPassMalformedDocument,
+    optional: [
+        severity,
+        decode_merged_image,
+    ],
+    required: [],
+}
 
 impl LintPass for LintPassMalformedDocument {
     fn lint(&self, kra_archive: &KraArchive, lint_messages: &mut LintMessages) -> LintPassResult {
+        let lint_messages = &mut lint_messages.with_severity(self.severity.unwrap_or_default());
+
         // Sub-pass #1
         {
-            let mut zip_archive = kra_archive.zip_archive.borrow_mut();
+            let mut zip_archive = kra_archive.zip_archive();
 
             if let Some(documentinfo_xml) = zip_archive.read_to_string("documentinfo.xml")? {
                 if documentinfo_xml.matches("]]>").count() > 1 {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::MalformedDocumentUnescapedAbstract,
                         "Malformed document",
                         &[
                             meta_comment!("Unescaped documentinfo.xml <abstract> tag"),
@@ -34,6 +50,7 @@ impl LintPass for LintPassMalformedDocument {
             } else {
                 #[rustfmt::skip]
                 lint_messages.push(
+                    LintCode::MalformedDocumentMissingDocInfo,
                     "Malformed document",
                     &[
                         meta_comment!("Missing documentinfo.xml"),
@@ -44,11 +61,12 @@ impl LintPass for LintPassMalformedDocument {
 
         // Sub-pass #2
         {
-            let zip_archive = kra_archive.zip_archive.borrow();
+            let zip_archive = kra_archive.zip_archive();
 
             if zip_archive.file_names().any(|file_name| file_name.contains("../")) {
                 #[rustfmt::skip]
                 lint_messages.push(
+                    LintCode::MalformedDocumentPathTraversal,
                     "Malformed document",
                     &[
                         meta_comment!("Path traversal vulnerability"),
@@ -65,6 +83,7 @@ impl LintPass for LintPassMalformedDocument {
                     if !kra_archive.all_layers().any(|target_layer| &target_layer.uuid == clone_from_uuid) {
                         #[rustfmt::skip]
                         lint_messages.push(
+                            LintCode::MalformedDocumentMissingCloneLayer,
                             "Malformed document",
                             &[
                                 meta_comment!("Missing clone layer target layer"),
@@ -76,6 +95,7 @@ impl LintPass for LintPassMalformedDocument {
                 } else {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::MalformedDocumentMissingCloneField,
                         "Malformed document",
                         &[
                             meta_comment!("Missing clone layer target field"),
@@ -92,6 +112,7 @@ impl LintPass for LintPassMalformedDocument {
                 if composition_container.into_iter().any(|composition| composition.name.contains('/')) {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::MalformedDocumentCompositionPath,
                         "Malformed document",
                         &[
                             meta_comment!("Compositions path traversal vulnerability"),
@@ -121,6 +142,7 @@ impl LintPass for LintPassMalformedDocument {
                     if referencing_uuid.contains(&uuid_root) {
                         #[rustfmt::skip]
                         lint_messages.push(
+                            LintCode::MalformedDocumentCloneLayerLoop,
                             "Malformed document",
                             &[
                                 meta_comment!("Clone layer loop"),
@@ -143,7 +165,7 @@ impl LintPass for LintPassMalformedDocument {
 
         // Sub-pass #6
         {
-            let mut zip_archive = kra_archive.zip_archive.borrow_mut();
+            let mut zip_archive = kra_archive.zip_archive();
 
             let contains_mergedimage_png = zip_archive.exists("mergedimage.png")?;
             let file_extension = kra_archive.zip_path.extension().map(str::to_lowercase);
@@ -152,6 +174,7 @@ impl LintPass for LintPassMalformedDocument {
                 (Some("kra"), false) => {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::MalformedDocumentMissingPreview,
                         "Malformed document",
                         &[
                             meta_comment!("KRA archive without required preview image"),
@@ -161,6 +184,7 @@ impl LintPass for LintPassMalformedDocument {
                 (Some("krz"), true) => {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::MalformedDocumentExtraneousPreview,
                         "Malformed document",
                         &[
                             meta_comment!("KRZ archive with extraneous preview image"),
@@ -177,6 +201,7 @@ impl LintPass for LintPassMalformedDocument {
                 if let Err(KraError::ColorProfileNotFound { .. }) = layer.color_profile(kra_archive) {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::MalformedDocumentMissingLayerProf,
                         "Malformed document",
                         &[
                             meta_comment!("Missing layer color profile"),
@@ -192,6 +217,7 @@ impl LintPass for LintPassMalformedDocument {
             if let Err(KraError::ColorProfileNotFound { .. }) = kra_archive.main_doc.image.color_profile(kra_archive) {
                 #[rustfmt::skip]
                 lint_messages.push(
+                    LintCode::MalformedDocumentMissingDocProf,
                     "Malformed document",
                     &[
                         meta_comment!("Missing document color profile"),
@@ -200,6 +226,52 @@ impl LintPass for LintPassMalformedDocument {
             }
         }
 
+        // Sub-pass #9
+        {
+            let kra_width = kra_archive.main_doc.image.width;
+            let kra_height = kra_archive.main_doc.image.height;
+
+            // Only the header is read for the size, decoding every pixel is opt-in
+            let merged_image_size = kra_archive.merged_image_size().and_then(|merged_image_size| {
+                if self.decode_merged_image.unwrap_or(false) {
+                    kra_archive.merged_image()?;
+                }
+
+                Ok(merged_image_size)
+            });
+
+            match merged_image_size {
+                Ok((merged_image_width, merged_image_height)) => {
+                    if (merged_image_width, merged_image_height) != (kra_width, kra_height) {
+                        #[rustfmt::skip]
+                        lint_messages.push(
+                            LintCode::MalformedDocumentMergedImageSize,
+                            "Malformed document",
+                            &[
+                                meta_comment!("Merged image size mismatch"),
+                                meta_expected!(format!("{}×{}px", kra_width, kra_height)),
+                                meta_found!(format!("{}×{}px", merged_image_width, merged_image_height)),
+                            ],
+                        );
+                    }
+                }
+                Err(KraError::ImageCannotDecode { .. }) => {
+                    #[rustfmt::skip]
+                    lint_messages.push(
+                        LintCode::MalformedDocumentCorruptMergedImage,
+                        "Malformed document",
+                        &[
+                            meta_comment!("Corrupt merged image"),
+                        ],
+                    );
+                }
+                Err(KraError::ImageNotFound { .. }) => {
+                    // Already handled by sub-pass #6
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+
         Ok(())
     }
 }
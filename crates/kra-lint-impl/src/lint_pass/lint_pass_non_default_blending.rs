@@ -3,28 +3,47 @@ use serde::{Deserialize, Serialize};
 use kra_parser::kra_archive::KraArchive;
 use kra_parser::kra_main_doc::{KraLayerType, KraMaskType};
 
+use crate::lint_config::impl_lint_config_merge;
+use crate::lint_fix::LintFix;
 use crate::lint_output::lint_metadata_macros::{meta_expected, meta_found, meta_layer, meta_mask};
-use crate::lint_output::LintMessages;
+use crate::lint_output::{LintCode, LintMessages, LintSeverity};
 use crate::lint_pass::{LintPass, LintPassResult};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
-pub(crate) struct LintPassNonDefaultBlending {}
+pub(crate) struct LintPassNonDefaultBlending {
+    severity: Option<LintSeverity>,
+}
+
+impl_lint_config_merge! {
+    LintPassNonDefaultBlending,
+    optional: [
+        severity,
+    ],
+    required: [],
+}
 
 impl LintPass for LintPassNonDefaultBlending {
     fn lint(&self, kra_archive: &KraArchive, lint_messages: &mut LintMessages) -> LintPassResult {
+        let lint_messages = &mut lint_messages.with_severity(self.severity.unwrap_or_default());
+
         // Sub-pass #1
         {
             for layer in kra_archive.all_layers() {
                 if layer.opacity != 255 {
                     #[rustfmt::skip]
-                    lint_messages.push(
+                    lint_messages.push_with_fix(
+                        LintCode::NonDefaultBlendingLayerOpacity,
                         "Non-default layer transparency",
                         &[
                             meta_layer!(layer),
                             meta_expected!(format!("{:.0}%", 100.0)),
                             meta_found!(format!("{:.0}%", layer.opacity as f64 / 255.0 * 100.0)),
                         ],
+                        LintFix::ResetLayerOpacity {
+                            layer_name: layer.name.clone(),
+                            layer_uuid: layer.uuid.clone(),
+                        },
                     );
                 }
             }
@@ -41,6 +60,7 @@ impl LintPass for LintPassNonDefaultBlending {
                 if layer.composite_op != expected_blending_mode {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::NonDefaultBlendingLayerBlendingMode,
                         "Non-default layer blending mode",
                         &[
                             meta_layer!(layer),
@@ -63,6 +83,7 @@ impl LintPass for LintPassNonDefaultBlending {
                 if mask.composite_op.as_deref() != expected_blending_mode {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::NonDefaultBlendingMaskBlendingMode,
                         "Non-default mask blending mode",
                         &[
                             meta_layer!(layer),
@@ -81,6 +102,7 @@ impl LintPass for LintPassNonDefaultBlending {
                 if (layer.channel_flags != "1111") && !layer.channel_flags.is_empty() {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::NonDefaultBlendingChannelFlags,
                         "Non-default set of active channels",
                         &[
                             meta_layer!(layer),
@@ -99,6 +121,7 @@ impl LintPass for LintPassNonDefaultBlending {
                     if (channel_lock_flags != "1111") && !channel_lock_flags.is_empty() {
                         #[rustfmt::skip]
                         lint_messages.push(
+                            LintCode::NonDefaultBlendingChannelLockFlags,
                             "Non-default channel lock flags",
                             &[
                                 meta_layer!(layer),
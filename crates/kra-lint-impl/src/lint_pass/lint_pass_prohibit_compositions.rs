@@ -2,22 +2,36 @@ use serde::{Deserialize, Serialize};
 
 use kra_parser::kra_archive::KraArchive;
 
+use crate::lint_config::impl_lint_config_merge;
 use crate::lint_output::lint_metadata_macros::meta_comment;
-use crate::lint_output::LintMessages;
+use crate::lint_output::{LintCode, LintMessages, LintSeverity};
 use crate::lint_pass::{LintPass, LintPassResult};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
-pub(crate) struct LintPassProhibitCompositions {}
+pub(crate) struct LintPassProhibitCompositions {
+    severity: Option<LintSeverity>,
+}
+
+impl_lint_config_merge! {
+    LintPassProhibitCompositions,
+    optional: [
+        severity,
+    ],
+    required: [],
+}
 
 impl LintPass for LintPassProhibitCompositions {
     fn lint(&self, kra_archive: &KraArchive, lint_messages: &mut LintMessages) -> LintPassResult {
+        let lint_messages = &mut lint_messages.with_severity(self.severity.unwrap_or_default());
+
         // Sub-pass #1
         {
             if let Some(composition_container) = kra_archive.main_doc.image.composition_container.as_ref() {
                 for composition in composition_container.into_iter() {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::ProhibitCompositions,
                         "Prohibited use of compositions",
                         &[
                             meta_comment!(format!("Composition name: \"{}\"", composition.name)),
@@ -2,16 +2,29 @@ use serde::{Deserialize, Serialize};
 
 use kra_parser::kra_archive::KraArchive;
 
+use crate::lint_config::impl_lint_config_merge;
 use crate::lint_output::lint_metadata_macros::meta_comment;
-use crate::lint_output::LintMessages;
+use crate::lint_output::{LintCode, LintMessages, LintSeverity};
 use crate::lint_pass::{LintPass, LintPassResult};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
-pub(crate) struct LintPassProhibitCustomPalettes {}
+pub(crate) struct LintPassProhibitCustomPalettes {
+    severity: Option<LintSeverity>,
+}
+
+impl_lint_config_merge! {
+    LintPassProhibitCustomPalettes,
+    optional: [
+        severity,
+    ],
+    required: [],
+}
 
 impl LintPass for LintPassProhibitCustomPalettes {
     fn lint(&self, kra_archive: &KraArchive, lint_messages: &mut LintMessages) -> LintPassResult {
+        let lint_messages = &mut lint_messages.with_severity(self.severity.unwrap_or_default());
+
         // Sub-pass #1
         {
             if let Some(kra_palette_container) = kra_archive.main_doc.image.palette_container.as_ref() {
@@ -19,6 +32,7 @@ impl LintPass for LintPassProhibitCustomPalettes {
                     // Bug: KRA files known to have mangled internal palettes (clusterfuck around bit depths/color spaces)
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::ProhibitCustomPalettes,
                         "Prohibited use of custom palettes",
                         &[
                             meta_comment!(format!("Palette: \"{}\"", kra_palette.name)),
@@ -3,22 +3,36 @@ use serde::{Deserialize, Serialize};
 use kra_parser::kra_archive::KraArchive;
 use kra_parser::kra_main_doc::KraLayerType;
 
+use crate::lint_config::impl_lint_config_merge;
 use crate::lint_output::lint_metadata_macros::meta_layer;
-use crate::lint_output::LintMessages;
+use crate::lint_output::{LintCode, LintMessages, LintSeverity};
 use crate::lint_pass::{LintPass, LintPassResult};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
-pub(crate) struct LintPassProhibitKSeExpr {}
+pub(crate) struct LintPassProhibitKSeExpr {
+    severity: Option<LintSeverity>,
+}
+
+impl_lint_config_merge! {
+    LintPassProhibitKSeExpr,
+    optional: [
+        severity,
+    ],
+    required: [],
+}
 
 impl LintPass for LintPassProhibitKSeExpr {
     fn lint(&self, kra_archive: &KraArchive, lint_messages: &mut LintMessages) -> LintPassResult {
+        let lint_messages = &mut lint_messages.with_severity(self.severity.unwrap_or_default());
+
         // Sub-pass #1
         {
             for layer in kra_archive.all_layers_by_type(KraLayerType::FillLayer) {
                 if layer.generator_name.as_deref() == Some("seexpr") {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::ProhibitKSeExpr,
                         "Prohibited use of KSeExpr",
                         &[
                             meta_layer!(layer),
@@ -0,0 +1,83 @@
+use std::collections::BTreeMap;
+use std::sync::{PoisonError, RwLock};
+
+use derive_more::{Display, Error};
+use serde::de::DeserializeOwned;
+
+use crate::lint_output::LintCode;
+use crate::lint_pass::LintPass;
+
+#[non_exhaustive]
+#[derive(Debug, Display, Error)]
+pub enum LintPassRegistryError {
+    #[display(fmt = "Lint pass \"{pass_name:}\" is already registered")]
+    DuplicatePassName {
+        #[error(not(source))]
+        pass_name: String,
+    },
+
+    #[display(fmt = "Lint pass name \"{pass_name:}\" is reserved")]
+    ReservedPassName {
+        #[error(not(source))]
+        pass_name: String,
+    },
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+type LintPassFactoryResult = Result<Box<dyn LintPass>, serde_json::Error>;
+type LintPassFactory = fn(serde_json::Value) -> LintPassFactoryResult;
+
+static LINT_PASS_REGISTRY: RwLock<BTreeMap<&'static str, LintPassFactory>> = RwLock::new(BTreeMap::new());
+
+const RESERVED_SECTION_NAMES: &[&str] = &["includes", "extends", "unset", "paths", "overrides"];
+
+fn create_lint_pass<T>(pass_config: serde_json::Value) -> LintPassFactoryResult
+where
+    T: LintPass + DeserializeOwned + 'static,
+{
+    Ok(Box::new(serde_json::from_value::<T>(pass_config)?))
+}
+
+/// Process-wide registry of lint passes defined outside of this crate.
+///
+/// A registered pass is configured by the config section named after it, e.g. a pass
+/// registered as `inking_layers` is enabled by an `[inking_layers]` section. Passes have to
+/// be registered before the config files are loaded, unknown sections are rejected.
+pub struct LintPassRegistry;
+
+impl LintPassRegistry {
+    pub fn register<T>(pass_name: &str) -> Result<(), LintPassRegistryError>
+    where
+        T: LintPass + DeserializeOwned + 'static,
+    {
+        if LintCode::builtin_pass_name(pass_name).is_some() || RESERVED_SECTION_NAMES.contains(&pass_name) {
+            return Err(LintPassRegistryError::ReservedPassName { pass_name: pass_name.to_owned() });
+        }
+
+        let mut lint_pass_registry = LINT_PASS_REGISTRY.write().unwrap_or_else(PoisonError::into_inner);
+
+        if lint_pass_registry.contains_key(pass_name) {
+            return Err(LintPassRegistryError::DuplicatePassName { pass_name: pass_name.to_owned() });
+        }
+
+        // Pass names are config field names, which serde only accepts as static strings
+        lint_pass_registry.insert(Box::leak(pass_name.into()), create_lint_pass::<T>);
+        Ok(())
+    }
+
+    pub fn is_registered(pass_name: &str) -> bool {
+        LINT_PASS_REGISTRY.read().unwrap_or_else(PoisonError::into_inner).contains_key(pass_name)
+    }
+
+    /// Creates the registered pass `pass_name` from its config section, `None` if no such pass is registered.
+    pub(crate) fn create(
+        pass_name: &str,
+        pass_config: serde_json::Value,
+    ) -> Option<(&'static str, LintPassFactoryResult)> {
+        let lint_pass_registry = LINT_PASS_REGISTRY.read().unwrap_or_else(PoisonError::into_inner);
+        let (pass_name, lint_pass_factory) = lint_pass_registry.get_key_value(pass_name)?;
+
+        Some((pass_name, lint_pass_factory(pass_config)))
+    }
+}
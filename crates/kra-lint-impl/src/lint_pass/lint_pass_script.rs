@@ -0,0 +1,405 @@
+use std::cell::RefCell;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use camino_ext::FormattedPathBuf;
+use rhai::module_resolvers::{DummyModuleResolver, FileModuleResolver};
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, Module, ModuleResolver, Position, Scope, Shared, AST, INT};
+use serde::{Deserialize, Serialize};
+
+use kra_parser::kra_archive::KraArchive;
+use kra_parser::kra_document_info::KraDocumentInfo;
+use kra_parser::kra_main_doc::{KraMainDoc, KraMainDocLayer, KraMainDocMask};
+
+use crate::lint_config::{impl_lint_config_merge, LintConfigError};
+use crate::lint_config_fields::ValueWithSeverity;
+use crate::lint_output::lint_metadata_macros::{meta_comment, meta_expected, meta_found};
+use crate::lint_output::{LintCode, LintMessages, LintMetadata};
+use crate::lint_pass::{LintPass, LintPassError, LintPassResult};
+
+macro_rules! script_map {
+    ($($key:literal => $value:expr),* $(,)?) => {{
+        let mut script_map = Map::new();
+        $(script_map.insert($key.into(), Dynamic::from($value));)*
+        script_map
+    }};
+}
+
+// Limits keeping a runaway script from hanging or exhausting the lint threads
+const SCRIPT_MAX_OPERATIONS: u64 = 10_000_000;
+const SCRIPT_MAX_CALL_LEVELS: usize = 64;
+const SCRIPT_MAX_STRING_SIZE: usize = 1024 * 1024;
+
+type ReportedMessage = (LintCode, String, Vec<LintMetadata>);
+
+thread_local! {
+    /// Messages reported by the script running on this thread, as every document shares one engine.
+    static REPORTED_MESSAGES: RefCell<Vec<ReportedMessage>> = const { RefCell::new(vec![]) };
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+/// Rhai script, compiled once its path has been resolved relative to the config file.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(from = "Utf8PathBuf", into = "Utf8PathBuf")]
+struct LintScript {
+    script_path: Utf8PathBuf,
+    script_ast: Option<AST>,
+    /// Files of the modules imported by the script, which are embedded into its AST.
+    module_paths: Vec<Utf8PathBuf>,
+}
+
+impl LintScript {
+    fn resolve_path(&mut self, config_root: &Utf8Path) -> Result<(), LintConfigError> {
+        self.script_path = config_root.join(&self.script_path);
+
+        let script = fs::read_to_string(&self.script_path).map_err(|source| LintConfigError::FailedToReadScript {
+            path: self.script_path.as_path().into(),
+            source,
+        })?;
+
+        // Imports are resolved relative to the script, once, when it is compiled
+        let script_root = self.script_path.parent().unwrap_or(config_root);
+        let module_resolver = LintScriptModuleResolver::new(script_root);
+        let module_paths = module_resolver.module_paths.clone();
+
+        let mut engine = script_engine();
+        engine.set_module_resolver(module_resolver);
+
+        let mut script_ast = engine.compile_into_self_contained(&Scope::new(), script).map_err(|source| {
+            LintConfigError::FailedToCompileScript { path: self.script_path.as_path().into(), source }
+        })?;
+        script_ast.set_source(self.script_path.as_str());
+
+        self.script_ast = Some(script_ast);
+        self.module_paths = module_paths.lock().unwrap_or_else(PoisonError::into_inner).clone();
+        Ok(())
+    }
+}
+
+impl From<Utf8PathBuf> for LintScript {
+    fn from(script_path: Utf8PathBuf) -> Self {
+        // Scripts are compiled by `resolve_path`, once the config file has been located
+        LintScript { script_path, script_ast: None, module_paths: vec![] }
+    }
+}
+
+impl From<LintScript> for Utf8PathBuf {
+    fn from(script: LintScript) -> Self {
+        script.script_path
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+/// Resolves imported modules from files, keeping track of the files which were imported.
+struct LintScriptModuleResolver {
+    file_module_resolver: FileModuleResolver,
+    module_paths: Arc<Mutex<Vec<Utf8PathBuf>>>,
+}
+
+impl LintScriptModuleResolver {
+    fn new(script_root: &Utf8Path) -> Self {
+        LintScriptModuleResolver {
+            file_module_resolver: FileModuleResolver::new_with_path(script_root),
+            module_paths: Arc::default(),
+        }
+    }
+
+    fn record_module_path(&self, source: Option<&str>, path: &str) {
+        let module_path = self.file_module_resolver.get_file_path(path, source.map(Path::new));
+        let mut module_paths = self.module_paths.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if let Ok(module_path) = Utf8PathBuf::from_path_buf(module_path) {
+            if !module_paths.contains(&module_path) {
+                module_paths.push(module_path);
+            }
+        }
+    }
+}
+
+impl ModuleResolver for LintScriptModuleResolver {
+    fn resolve(
+        &self,
+        engine: &Engine,
+        source: Option<&str>,
+        path: &str,
+        pos: Position,
+    ) -> Result<Shared<Module>, Box<EvalAltResult>> {
+        self.record_module_path(source, path);
+        self.file_module_resolver.resolve(engine, source, path, pos)
+    }
+
+    fn resolve_ast(
+        &self,
+        engine: &Engine,
+        source: Option<&str>,
+        path: &str,
+        pos: Position,
+    ) -> Option<Result<AST, Box<EvalAltResult>>> {
+        self.record_module_path(source, path);
+        self.file_module_resolver.resolve_ast(engine, source, path, pos)
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct LintPassScript {
+    scripts: Vec<ValueWithSeverity<LintScript>>,
+}
+
+impl_lint_config_merge! {
+    LintPassScript,
+    optional: [],
+    required: [
+        scripts,
+    ],
+}
+
+impl LintPassScript {
+    /// Resolves script paths relative to the config file and compiles the scripts.
+    pub(crate) fn resolve_paths(&mut self, config_root: &Utf8Path) -> Result<(), LintConfigError> {
+        for script in &mut self.scripts {
+            match script {
+                ValueWithSeverity::WithSeverity { value, .. } => value.resolve_path(config_root)?,
+                ValueWithSeverity::Value(value) => value.resolve_path(config_root)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Files of the scripts and of the modules they import.
+    pub(crate) fn script_paths(&self) -> impl Iterator<Item = &Utf8Path> {
+        self.scripts.iter().flat_map(|script| {
+            let script = script.value();
+            [script.script_path.as_path()].into_iter().chain(script.module_paths.iter().map(Utf8PathBuf::as_path))
+        })
+    }
+}
+
+impl LintPass for LintPassScript {
+    fn lint(&self, kra_archive: &KraArchive, lint_messages: &mut LintMessages) -> LintPassResult {
+        // Sub-pass #1
+        {
+            let mut scope = Scope::new();
+            scope.push_constant("document", document_view(&kra_archive.main_doc));
+            scope.push_constant("document_info", document_info_view(&kra_archive.document_info));
+            scope.push_constant("layers", layers_view(&kra_archive.main_doc.image.layer_container));
+            scope.push_constant("all_layers", layers_view(kra_archive.all_layers()));
+
+            for script in &self.scripts {
+                let mut lint_messages = lint_messages.with_severity(script.severity());
+
+                let Some(script_ast) = script.script_ast.as_ref() else {
+                    return Err(LintPassError::Custom(format!(
+                        "Script \"{}\" is not compiled",
+                        FormattedPathBuf::from(&script.script_path)
+                    )));
+                };
+
+                REPORTED_MESSAGES.with(|reported_messages| reported_messages.borrow_mut().clear());
+
+                // Messages reported before a script error are kept
+                let script_result = shared_script_engine().run_ast_with_scope(&mut scope.clone(), script_ast);
+
+                for (message_code, title, metadata) in REPORTED_MESSAGES.with(RefCell::take) {
+                    lint_messages.push(message_code, title, &metadata);
+                }
+
+                script_result.map_err(|err| {
+                    LintPassError::Custom(format!(
+                        "Script \"{}\" failed: {}",
+                        FormattedPathBuf::from(&script.script_path),
+                        err
+                    ))
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+/// Engine running the scripts of every document, imports are already embedded into the compiled scripts.
+fn shared_script_engine() -> &'static Engine {
+    static SCRIPT_ENGINE: OnceLock<Engine> = OnceLock::new();
+
+    SCRIPT_ENGINE.get_or_init(|| {
+        let mut engine = script_engine();
+        engine.set_module_resolver(DummyModuleResolver::new());
+        engine
+    })
+}
+
+/// Engine with the limits and the functions available to scripts.
+fn script_engine() -> Engine {
+    fn string_field(map: &Map, field: &str) -> Result<String, Box<EvalAltResult>> {
+        map.get(field)
+            .and_then(|value| value.clone().into_string().ok())
+            .ok_or_else(|| format!("Missing string field \"{}\"", field).into())
+    }
+
+    fn rule_code(rule: &str) -> Result<LintCode, Box<EvalAltResult>> {
+        let is_valid_rule = !rule.is_empty()
+            && rule
+                .chars()
+                .all(|rule_char| rule_char.is_ascii_lowercase() || rule_char.is_ascii_digit() || rule_char == '_');
+
+        if !is_valid_rule {
+            return Err(
+                format!("Invalid rule name \"{}\", expected lowercase letters, digits and underscores", rule).into()
+            );
+        }
+
+        Ok(LintCode::script(rule))
+    }
+
+    fn report(rule: &str, title: &str, metadata: Array) -> Result<(), Box<EvalAltResult>> {
+        let metadata = metadata
+            .into_iter()
+            .map(|metadata| {
+                let type_name = metadata.type_name();
+                metadata.try_cast::<LintMetadata>().ok_or_else(|| {
+                    format!("Expected metadata created by one of the meta_* functions, found {}", type_name)
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let message_code = rule_code(rule)?;
+        REPORTED_MESSAGES.with(|reported_messages| {
+            reported_messages.borrow_mut().push((message_code, title.to_owned(), metadata));
+        });
+
+        Ok(())
+    }
+
+    let mut engine = Engine::new();
+
+    engine.set_max_operations(SCRIPT_MAX_OPERATIONS);
+    engine.set_max_call_levels(SCRIPT_MAX_CALL_LEVELS);
+    engine.set_max_string_size(SCRIPT_MAX_STRING_SIZE);
+
+    engine.register_type_with_name::<LintMetadata>("LintMetadata");
+    engine.register_fn("to_string", |metadata: &mut LintMetadata| metadata.to_string());
+
+    engine.register_fn("report", |rule: &str, title: &str| report(rule, title, Array::new()));
+    engine.register_fn("report", report);
+
+    engine.register_fn("meta_layer", |layer: Map| -> Result<LintMetadata, Box<EvalAltResult>> {
+        Ok(LintMetadata::Layer { layer_name: string_field(&layer, "name")?, layer_uuid: string_field(&layer, "uuid")? })
+    });
+    engine.register_fn("meta_mask", |mask: Map| -> Result<LintMetadata, Box<EvalAltResult>> {
+        Ok(LintMetadata::Mask { mask_name: string_field(&mask, "name")?, mask_uuid: string_field(&mask, "uuid")? })
+    });
+    engine.register_fn("meta_expected", |expected: Dynamic| meta_expected!(expected));
+    engine.register_fn("meta_found", |found: Dynamic| meta_found!(found));
+    engine.register_fn("meta_comment", |comment: Dynamic| meta_comment!(comment));
+
+    engine
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+fn enum_view<T>(value: &T) -> Dynamic
+where
+    T: Serialize,
+{
+    rhai::serde::to_dynamic(value).unwrap_or(Dynamic::UNIT)
+}
+
+fn option_view<T>(value: Option<T>) -> Dynamic
+where
+    T: Into<Dynamic>,
+{
+    value.map_or(Dynamic::UNIT, Into::into)
+}
+
+fn document_view(main_doc: &KraMainDoc) -> Map {
+    script_map! {
+        "name"             => main_doc.image.name.clone(),
+        "description"      => main_doc.image.description.clone(),
+        "width"            => main_doc.image.width as INT,
+        "height"           => main_doc.image.height as INT,
+        "x_res"            => main_doc.image.x_res,
+        "y_res"            => main_doc.image.y_res,
+        "profile"          => main_doc.image.profile.clone(),
+        "colorspace_name"  => main_doc.image.colorspace_name.clone(),
+        "software_version" => main_doc.software_version.clone(),
+        "editor"           => main_doc.editor.clone(),
+        "syntax_version"   => main_doc.syntax_version.clone(),
+    }
+}
+
+fn document_info_view(document_info: &KraDocumentInfo) -> Map {
+    let about = &document_info.about;
+    let author = &document_info.author;
+
+    script_map! {
+        "about" => script_map! {
+            "title"           => about.title.clone(),
+            "description"     => about.description.clone(),
+            "subject"         => about.subject.clone(),
+            "abstract"        => about.r#abstract.clone(),
+            "keyword"         => about.keyword.clone(),
+            "initial_creator" => about.initial_creator.clone(),
+            "editing_cycles"  => about.editing_cycles as INT,
+            "date"            => about.date.clone(),
+            "creation_date"   => about.creation_date.clone(),
+            "language"        => about.language.clone(),
+            "license"         => about.license.clone(),
+        },
+        "author" => script_map! {
+            "full_name"          => author.full_name.clone(),
+            "creator_first_name" => author.creator_first_name.clone(),
+            "creator_last_name"  => author.creator_last_name.clone(),
+            "initial"            => author.initial.clone(),
+            "author_title"       => author.author_title.clone(),
+            "position"           => author.position.clone(),
+            "company"            => author.company.clone(),
+        },
+    }
+}
+
+fn layers_view<'a>(layers: impl IntoIterator<Item = &'a KraMainDocLayer>) -> Array {
+    layers.into_iter().map(|layer| Dynamic::from(layer_view(layer))).collect()
+}
+
+fn layer_view(layer: &KraMainDocLayer) -> Map {
+    let child_layers = layer.layer_container.iter().flatten();
+    let masks = layer.mask_container.iter().flatten();
+
+    script_map! {
+        "name"            => layer.name.clone(),
+        "uuid"            => layer.uuid.clone(),
+        "layer_type"      => enum_view(&layer.layer_type),
+        "color_label"     => enum_view(&layer.color_label),
+        "colorspace_name" => option_view(layer.colorspace_name.clone()),
+        "composite_op"    => layer.composite_op.clone(),
+        "opacity"         => layer.opacity as INT,
+        "visible"         => layer.visible,
+        "locked"          => layer.locked,
+        "x"               => layer.x as INT,
+        "y"               => layer.y as INT,
+        "layers"          => layers_view(child_layers),
+        "masks"           => masks.map(|mask| Dynamic::from(mask_view(mask))).collect::<Array>(),
+    }
+}
+
+fn mask_view(mask: &KraMainDocMask) -> Map {
+    script_map! {
+        "name"         => mask.name.clone(),
+        "uuid"         => mask.uuid.clone(),
+        "mask_type"    => enum_view(&mask.mask_type),
+        "color_label"  => option_view(mask.color_label.as_ref().map(enum_view)),
+        "composite_op" => option_view(mask.composite_op.clone()),
+        "visible"      => mask.visible,
+        "locked"       => mask.locked,
+    }
+}
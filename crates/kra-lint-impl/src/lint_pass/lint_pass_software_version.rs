@@ -2,17 +2,28 @@ use serde::{Deserialize, Serialize};
 
 use kra_parser::kra_archive::KraArchive;
 
-use crate::lint_config_fields::StringMatchExpression;
+use crate::lint_config::impl_lint_config_merge;
+use crate::lint_config_fields::{StringMatchExpression, ValueWithSeverity};
 use crate::lint_output::lint_metadata_macros::{meta_expected, meta_found};
-use crate::lint_output::LintMessages;
+use crate::lint_output::{LintCode, LintMessages};
 use crate::lint_pass::{LintPass, LintPassResult};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct LintPassSoftwareVersion {
-    software_name: Option<StringMatchExpression>,
-    software_version: Option<StringMatchExpression>,
-    syntax_version: Option<StringMatchExpression>,
+    software_name: Option<ValueWithSeverity<StringMatchExpression>>,
+    software_version: Option<ValueWithSeverity<StringMatchExpression>>,
+    syntax_version: Option<ValueWithSeverity<StringMatchExpression>>,
+}
+
+impl_lint_config_merge! {
+    LintPassSoftwareVersion,
+    optional: [
+        software_name,
+        software_version,
+        syntax_version,
+    ],
+    required: [],
 }
 
 impl LintPass for LintPassSoftwareVersion {
@@ -20,11 +31,14 @@ impl LintPass for LintPassSoftwareVersion {
         // Sub-pass #1
         {
             if let Some(software_name) = self.software_name.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(software_name.severity());
+
                 let kra_software_name = &kra_archive.main_doc.editor;
 
                 if !software_name.matches(kra_software_name) {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::SoftwareVersionSoftwareName,
                         "Incorrect software name",
                         &[
                             meta_expected!(software_name),
@@ -38,11 +52,14 @@ impl LintPass for LintPassSoftwareVersion {
         // Sub-pass #2
         {
             if let Some(software_version) = self.software_version.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(software_version.severity());
+
                 let kra_software_version = &kra_archive.main_doc.software_version;
 
                 if !software_version.matches(kra_software_version) {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::SoftwareVersionSoftwareVersion,
                         "Incorrect software version",
                         &[
                             meta_expected!(software_version),
@@ -56,11 +73,14 @@ impl LintPass for LintPassSoftwareVersion {
         // Sub-pass #3
         {
             if let Some(syntax_version) = self.syntax_version.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(syntax_version.severity());
+
                 let kra_syntax_version = &kra_archive.main_doc.syntax_version;
 
                 if !syntax_version.matches(kra_syntax_version) {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::SoftwareVersionSyntaxVersion,
                         "Incorrect document syntax version",
                         &[
                             meta_expected!(syntax_version),
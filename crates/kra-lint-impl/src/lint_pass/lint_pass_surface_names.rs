@@ -2,16 +2,26 @@ use serde::{Deserialize, Serialize};
 
 use kra_parser::kra_archive::KraArchive;
 
-use crate::lint_config_fields::{StringMatchExpression, ValueByLayerType, ValueByMaskType};
+use crate::lint_config::impl_lint_config_merge;
+use crate::lint_config_fields::{StringMatchExpression, ValueByLayerType, ValueByMaskType, ValueWithSeverity};
 use crate::lint_output::lint_metadata_macros::{meta_expected, meta_found, meta_layer, meta_mask};
-use crate::lint_output::LintMessages;
+use crate::lint_output::{LintCode, LintMessages};
 use crate::lint_pass::{LintPass, LintPassResult};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct LintPassSurfaceNames {
-    layer_names: Option<ValueByLayerType<StringMatchExpression>>,
-    mask_names: Option<ValueByMaskType<StringMatchExpression>>,
+    layer_names: Option<ValueWithSeverity<ValueByLayerType<StringMatchExpression>>>,
+    mask_names: Option<ValueWithSeverity<ValueByMaskType<StringMatchExpression>>>,
+}
+
+impl_lint_config_merge! {
+    LintPassSurfaceNames,
+    optional: [
+        layer_names,
+        mask_names,
+    ],
+    required: [],
 }
 
 impl LintPass for LintPassSurfaceNames {
@@ -19,6 +29,8 @@ impl LintPass for LintPassSurfaceNames {
         // Sub-pass #1
         {
             if let Some(layer_names) = self.layer_names.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(layer_names.severity());
+
                 for layer in kra_archive.all_layers() {
                     let (layer_opt, layer_display) = layer_names.get(layer);
 
@@ -26,6 +38,7 @@ impl LintPass for LintPassSurfaceNames {
                         if !string_match_expr.matches(&layer.name) {
                             #[rustfmt::skip]
                             lint_messages.push(
+                                LintCode::SurfaceNamesLayerName,
                                 format!("Incorrect {} name", layer_display),
                                 &[
                                     meta_layer!(layer),
@@ -42,6 +55,8 @@ impl LintPass for LintPassSurfaceNames {
         // Sub-pass #2
         {
             if let Some(mask_names) = self.mask_names.as_ref() {
+                let mut lint_messages = lint_messages.with_severity(mask_names.severity());
+
                 for (layer, mask) in kra_archive.all_masks() {
                     let (mask_opt, mask_display) = mask_names.get(mask);
 
@@ -49,6 +64,7 @@ impl LintPass for LintPassSurfaceNames {
                         if !string_match_expr.matches(&mask.name) {
                             #[rustfmt::skip]
                             lint_messages.push(
+                                LintCode::SurfaceNamesMaskName,
                                 format!("Incorrect {} name", mask_display),
                                 &[
                                     meta_layer!(layer),
@@ -2,28 +2,41 @@ use serde::{Deserialize, Serialize};
 
 use kra_parser::kra_archive::KraArchive;
 
-use crate::lint_config_fields::{ValueByLayerType, ValueByMaskType};
+use crate::lint_config::impl_lint_config_merge;
+use crate::lint_config_fields::{ValueByLayerType, ValueByMaskType, ValueWithSeverity};
 use crate::lint_output::lint_metadata_macros::{meta_layer, meta_mask};
-use crate::lint_output::LintMessages;
+use crate::lint_output::{LintCode, LintMessages};
 use crate::lint_pass::{LintPass, LintPassResult};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct LintPassSurfaceType {
-    layer_types: ValueByLayerType<bool>,
-    mask_types: ValueByMaskType<bool>,
+    layer_types: ValueWithSeverity<ValueByLayerType<bool>>,
+    mask_types: ValueWithSeverity<ValueByMaskType<bool>>,
+}
+
+impl_lint_config_merge! {
+    LintPassSurfaceType,
+    optional: [],
+    required: [
+        layer_types,
+        mask_types,
+    ],
 }
 
 impl LintPass for LintPassSurfaceType {
     fn lint(&self, kra_archive: &KraArchive, lint_messages: &mut LintMessages) -> LintPassResult {
         // Sub-pass #1
         {
+            let mut lint_messages = lint_messages.with_severity(self.layer_types.severity());
+
             for layer in kra_archive.all_layers() {
                 let (layer_opt, layer_display) = self.layer_types.get(layer);
 
                 if *layer_opt == Some(false) {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::SurfaceTypeLayerType,
                         format!("Prohibited use of {}", layer_display),
                         &[
                             meta_layer!(layer),
@@ -35,12 +48,15 @@ impl LintPass for LintPassSurfaceType {
 
         // Sub-pass #2
         {
+            let mut lint_messages = lint_messages.with_severity(self.mask_types.severity());
+
             for (layer, mask) in kra_archive.all_masks() {
                 let (mask_opt, mask_display) = self.mask_types.get(mask);
 
                 if *mask_opt == Some(false) {
                     #[rustfmt::skip]
                     lint_messages.push(
+                        LintCode::SurfaceTypeMaskType,
                         format!("Prohibited use of {}", mask_display),
                         &[
                             meta_layer!(layer),
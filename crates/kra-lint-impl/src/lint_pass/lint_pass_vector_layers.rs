@@ -7,26 +7,43 @@ use svg::parser::Event;
 use kra_parser::kra_archive::KraArchive;
 use kra_parser::kra_main_doc::KraLayerType;
 
-use crate::lint_config_fields::{NumberMatchExpression, StringMatchExpression};
+use crate::lint_config::impl_lint_config_merge;
+use crate::lint_config_fields::{NumberMatchExpression, StringMatchExpression, ValueWithSeverity};
 use crate::lint_output::lint_metadata_macros::{meta_bug, meta_expected, meta_found, meta_layer};
-use crate::lint_output::LintMessages;
+use crate::lint_output::{LintCode, LintMessages};
 use crate::lint_pass::{LintPass, LintPassResult};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub(crate) struct LintPassVectorLayers {
-    font_family: Option<StringMatchExpression>,
-    stroke_linecap: Option<StringMatchExpression>,
-    stroke_linejoin: Option<StringMatchExpression>,
-    stroke_width: Option<NumberMatchExpression<f64>>,
-    placeholder_text: Option<StringMatchExpression>,
-    warn_broken_text_gradients: Option<bool>,
+    font_family: Option<ValueWithSeverity<StringMatchExpression>>,
+    stroke_linecap: Option<ValueWithSeverity<StringMatchExpression>>,
+    stroke_linejoin: Option<ValueWithSeverity<StringMatchExpression>>,
+    stroke_width: Option<ValueWithSeverity<NumberMatchExpression<f64>>>,
+    placeholder_text: Option<ValueWithSeverity<StringMatchExpression>>,
+    warn_broken_text_gradients: Option<ValueWithSeverity<bool>>,
+}
+
+impl_lint_config_merge! {
+    LintPassVectorLayers,
+    optional: [
+        font_family,
+        stroke_linecap,
+        stroke_linejoin,
+        stroke_width,
+        placeholder_text,
+        warn_broken_text_gradients,
+    ],
+    required: [],
 }
 
 impl LintPass for LintPassVectorLayers {
     fn lint(&self, kra_archive: &KraArchive, lint_messages: &mut LintMessages) -> LintPassResult {
         // Sub-pass #1, #2, #3, #4, #5, #6
         {
+            let text_gradient_regex =
+                regex::Regex::new(r"^url\(#gradient\d+\)$").expect("Failed to compile regular expression");
+
             for layer in kra_archive.all_layers_by_type(KraLayerType::VectorLayer) {
                 let content_svg_data = layer.content_svg(kra_archive)?;
                 let content_svg_parser = svg::read(&content_svg_data)?;
@@ -34,11 +51,14 @@ impl LintPass for LintPassVectorLayers {
                 for svg_event in content_svg_parser {
                     // Sub-pass #1
                     if let Some(font_family) = self.font_family.as_ref() {
+                        let mut lint_messages = lint_messages.with_severity(font_family.severity());
+
                         if let Event::Tag("text" | "tspan", Type::Start, svg_attributes) = &svg_event {
                             if let Some(svg_font_family) = svg_attributes.get("font-family") {
                                 if !font_family.matches(svg_font_family) {
                                     #[rustfmt::skip]
                                     lint_messages.push(
+                                        LintCode::VectorLayersFontFamily,
                                         "Prohibited font family on vector layer",
                                         &[
                                             meta_layer!(layer),
@@ -53,6 +73,8 @@ impl LintPass for LintPassVectorLayers {
 
                     // Sub-pass #2
                     if let Some(stroke_linecap) = self.stroke_linecap.as_ref() {
+                        let mut lint_messages = lint_messages.with_severity(stroke_linecap.severity());
+
                         if let Event::Tag(
                             "rect" | "circle" | "ellipse" | "path" | "text",
                             Type::Start,
@@ -63,6 +85,7 @@ impl LintPass for LintPassVectorLayers {
                                 if !stroke_linecap.matches(svg_stroke_linecap) {
                                     #[rustfmt::skip]
                                     lint_messages.push(
+                                        LintCode::VectorLayersStrokeLinecap,
                                         "Prohibited stroke line cap on vector layer",
                                         &[
                                             meta_layer!(layer),
@@ -77,6 +100,8 @@ impl LintPass for LintPassVectorLayers {
 
                     // Sub-pass #3
                     if let Some(stroke_linejoin) = self.stroke_linejoin.as_ref() {
+                        let mut lint_messages = lint_messages.with_severity(stroke_linejoin.severity());
+
                         if let Event::Tag(
                             "rect" | "circle" | "ellipse" | "path" | "text",
                             Type::Start,
@@ -87,6 +112,7 @@ impl LintPass for LintPassVectorLayers {
                                 if !stroke_linejoin.matches(svg_stroke_linejoin) {
                                     #[rustfmt::skip]
                                     lint_messages.push(
+                                        LintCode::VectorLayersStrokeLinejoin,
                                         "Prohibited stroke line join on vector layer",
                                         &[
                                             meta_layer!(layer),
@@ -101,6 +127,8 @@ impl LintPass for LintPassVectorLayers {
 
                     // Sub-pass #4
                     if let Some(stroke_width) = self.stroke_width.as_ref() {
+                        let mut lint_messages = lint_messages.with_severity(stroke_width.severity());
+
                         if let Event::Tag(
                             "rect" | "circle" | "ellipse" | "path" | "text",
                             Type::Start,
@@ -113,6 +141,7 @@ impl LintPass for LintPassVectorLayers {
                                     if !stroke_width.matches(&svg_stroke_width) {
                                         #[rustfmt::skip]
                                         lint_messages.push(
+                                            LintCode::VectorLayersStrokeWidth,
                                             "Incorrect stroke width on vector layer",
                                             &[
                                                 meta_layer!(layer),
@@ -124,6 +153,7 @@ impl LintPass for LintPassVectorLayers {
                                 } else {
                                     #[rustfmt::skip]
                                     lint_messages.push(
+                                        LintCode::VectorLayersMalformedStrokeWidth,
                                         "Malformed stroke width on vector layer",
                                         &[
                                             meta_layer!(layer),
@@ -138,10 +168,13 @@ impl LintPass for LintPassVectorLayers {
 
                     // Sub-pass #5
                     if let Some(placeholder_text) = self.placeholder_text.as_ref() {
+                        let mut lint_messages = lint_messages.with_severity(placeholder_text.severity());
+
                         if let Event::Text(svg_text) = &svg_event {
                             if placeholder_text.matches(svg_text) {
                                 #[rustfmt::skip]
                                 lint_messages.push(
+                                    LintCode::VectorLayersPlaceholderText,
                                     "Prohibited placeholder text on vector layer",
                                     &[
                                         meta_layer!(layer),
@@ -153,15 +186,17 @@ impl LintPass for LintPassVectorLayers {
                     }
 
                     // Sub-pass #6
-                    if self.warn_broken_text_gradients == Some(true) {
+                    if let Some(severity) =
+                        self.warn_broken_text_gradients.as_ref().and_then(ValueWithSeverity::severity_if_enabled)
+                    {
+                        let mut lint_messages = lint_messages.with_severity(severity);
+
                         if let Event::Tag("text", Type::Start, svg_attributes) = &svg_event {
                             if let Some(svg_fill) = svg_attributes.get("fill") {
-                                let compiled_regex = regex::Regex::new(r"^url\(#gradient\d+\)$")
-                                    .expect("Failed to compile regular expression");
-
-                                if compiled_regex.is_match(svg_fill) {
+                                if text_gradient_regex.is_match(svg_fill) {
                                     #[rustfmt::skip]
                                     lint_messages.push(
+                                        LintCode::VectorLayersBrokenTextGradient,
                                         "Broken text gradient fill on vector layer",
                                         &[
                                             meta_layer!(layer),
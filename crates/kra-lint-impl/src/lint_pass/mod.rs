@@ -1,7 +1,8 @@
 mod lint_pass;
+mod lint_pass_registry;
 
-pub use lint_pass::LintPassError;
-pub(crate) use lint_pass::{LintPass, LintPassResult};
+pub use lint_pass::{LintPass, LintPassError, LintPassResult};
+pub use lint_pass_registry::{LintPassRegistry, LintPassRegistryError};
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 
@@ -24,6 +25,7 @@ pub(crate) mod lint_pass_non_default_blending;
 pub(crate) mod lint_pass_prohibit_compositions;
 pub(crate) mod lint_pass_prohibit_custom_palettes;
 pub(crate) mod lint_pass_prohibit_kseexpr;
+pub(crate) mod lint_pass_script;
 pub(crate) mod lint_pass_software_version;
 pub(crate) mod lint_pass_surface_names;
 pub(crate) mod lint_pass_surface_type;
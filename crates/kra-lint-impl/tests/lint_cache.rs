@@ -0,0 +1,67 @@
+use std::fs;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use tempfile::TempDir;
+
+use kra_lint_impl::{LintCache, LintConfigCollection};
+
+fn fixture_path(file_name: &str) -> Utf8PathBuf {
+    Utf8PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(file_name)
+}
+
+fn temp_dir_path(temp_dir: &TempDir) -> Utf8PathBuf {
+    Utf8PathBuf::from_path_buf(temp_dir.path().to_owned()).unwrap()
+}
+
+fn cache_entry_count(cache_root: &Utf8Path) -> usize {
+    cache_root.read_dir_utf8().map_or(0, Iterator::count)
+}
+
+fn lint_with_cache(config_path: &Utf8Path, cache_root: &Utf8Path) -> Vec<&'static str> {
+    let mut lint_config_collection = LintConfigCollection::default();
+    lint_config_collection.load_config(config_path).unwrap();
+    lint_config_collection.set_cache(LintCache::new(cache_root, &lint_config_collection));
+
+    let lint_messages = lint_config_collection.lint_path(&fixture_path("paint_layer.kra"));
+    lint_messages.into_iter().map(|entry| entry.message_code.code()).collect()
+}
+
+#[test]
+fn edited_config_invalidates_cached_results() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let config_path = temp_dir_path(&temp_dir).join(".kra-lint");
+    let cache_root = temp_dir_path(&temp_dir).join("cache");
+
+    fs::write(&config_path, "[document_size]\ndocument_sizes = [{ width = 128, height = 128 }]\n").unwrap();
+
+    assert_eq!(lint_with_cache(&config_path, &cache_root), ["KRA0701"]);
+    assert_eq!(cache_entry_count(&cache_root), 1);
+
+    // Unchanged configs reuse the cached result
+    assert_eq!(lint_with_cache(&config_path, &cache_root), ["KRA0701"]);
+    assert_eq!(cache_entry_count(&cache_root), 1);
+
+    fs::write(&config_path, "[document_size]\ndocument_sizes = [{ width = 256, height = 256 }]\n").unwrap();
+
+    assert_eq!(lint_with_cache(&config_path, &cache_root), Vec::<&str>::new());
+    assert_eq!(cache_entry_count(&cache_root), 2);
+}
+
+#[test]
+fn pruning_bounds_cache_size() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let cache_root = temp_dir_path(&temp_dir);
+
+    for entry_index in 0..4 {
+        fs::write(cache_root.join(format!("entry_{}.json", entry_index)), [0; 100]).unwrap();
+    }
+
+    fs::write(cache_root.join("unrelated.txt"), [0; 100]).unwrap();
+
+    LintCache::prune(&cache_root, 250).unwrap();
+    assert_eq!(cache_entry_count(&cache_root), 3);
+    assert!(cache_root.join("unrelated.txt").is_file());
+
+    LintCache::prune(&cache_root, 0).unwrap();
+    assert_eq!(cache_entry_count(&cache_root), 1);
+}
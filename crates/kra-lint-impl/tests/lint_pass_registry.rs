@@ -0,0 +1,102 @@
+use std::sync::Once;
+
+use camino::Utf8PathBuf;
+use serde::Deserialize;
+
+use kra_lint_impl::{
+    meta_expected, meta_layer, LintCode, LintConfigCollection, LintMessages, LintMessagesEntry, LintMetadata, LintPass,
+    LintPassRegistry, LintPassRegistryError, LintPassResult,
+};
+use kra_parser::kra_archive::KraArchive;
+use kra_parser::kra_main_doc::KraLayerType;
+
+/// Studio rules which do not belong upstream, as a plugin pass would implement them.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HouseRules {
+    required_group: String,
+    forbidden_layer_prefix: Option<String>,
+}
+
+const MISSING_GROUP: LintCode = LintCode::Plugin { code: "STUDIO0101", name: "house_rules.missing_group" };
+const FORBIDDEN_LAYER_NAME: LintCode =
+    LintCode::Plugin { code: "STUDIO0102", name: "house_rules.forbidden_layer_name" };
+
+impl LintPass for HouseRules {
+    fn lint(&self, kra_archive: &KraArchive, lint_messages: &mut LintMessages) -> LintPassResult {
+        let has_required_group = kra_archive
+            .all_layers()
+            .any(|layer| layer.layer_type == KraLayerType::GroupLayer && layer.name == self.required_group);
+
+        if !has_required_group {
+            lint_messages.push(MISSING_GROUP, "Missing required group", &[meta_expected!(self.required_group)]);
+        }
+
+        if let Some(forbidden_layer_prefix) = self.forbidden_layer_prefix.as_ref() {
+            for layer in kra_archive.all_layers().filter(|layer| layer.name.starts_with(forbidden_layer_prefix)) {
+                lint_messages.push(FORBIDDEN_LAYER_NAME, "Forbidden layer name", &[meta_layer!(layer)]);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn register_house_rules() {
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(|| LintPassRegistry::register::<HouseRules>("house_rules").unwrap());
+}
+
+fn fixture_path(file_name: &str) -> Utf8PathBuf {
+    Utf8PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(file_name)
+}
+
+fn lint_with_config(config_name: &str) -> Vec<LintMessagesEntry> {
+    register_house_rules();
+
+    let mut lint_config_collection = LintConfigCollection::default();
+    lint_config_collection.load_config(&fixture_path(config_name)).unwrap();
+
+    let lint_messages = lint_config_collection.lint_path(&fixture_path("paint_layer.kra"));
+    lint_messages.into_iter().collect()
+}
+
+#[test]
+fn registered_pass_reports_messages() {
+    let lint_messages = lint_with_config("house_rules.toml");
+
+    // Messages are sorted by title
+    let message_codes = lint_messages.iter().map(|entry| entry.message_code.name()).collect::<Vec<_>>();
+    assert_eq!(message_codes, ["house_rules.forbidden_layer_name", "house_rules.missing_group"]);
+
+    let layer_names = lint_messages[0].message_metadata.iter().filter_map(|metadata| match metadata {
+        LintMetadata::Layer { layer_name, .. } => Some(layer_name.as_str()),
+        _ => None,
+    });
+    assert_eq!(layer_names.collect::<Vec<_>>(), ["Paint Layer 1"]);
+
+    assert_eq!(lint_messages[1].message_code.code(), "STUDIO0101");
+    assert_eq!(lint_messages[1].message_metadata, [LintMetadata::Expected("FLAT".to_owned())]);
+}
+
+#[test]
+fn registered_pass_options_are_merged() {
+    let lint_messages = lint_with_config("house_rules_unset.toml");
+
+    let message_codes = lint_messages.iter().map(|entry| entry.message_code.code()).collect::<Vec<_>>();
+    assert_eq!(message_codes, ["STUDIO0101"]);
+}
+
+#[test]
+fn reserved_and_duplicate_pass_names_are_rejected() {
+    register_house_rules();
+
+    let result = LintPassRegistry::register::<HouseRules>("house_rules");
+    assert!(matches!(result, Err(LintPassRegistryError::DuplicatePassName { .. })), "{:?}", result);
+
+    let result = LintPassRegistry::register::<HouseRules>("surface_names");
+    assert!(matches!(result, Err(LintPassRegistryError::ReservedPassName { .. })), "{:?}", result);
+
+    let result = LintPassRegistry::register::<HouseRules>("overrides");
+    assert!(matches!(result, Err(LintPassRegistryError::ReservedPassName { .. })), "{:?}", result);
+}
@@ -4,7 +4,7 @@ use std::error::Error;
 use std::process::{Command, ExitCode};
 use std::{env, fs, io, str};
 
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use derive_more::{Display, Error};
 use diff::Result as DiffResult;
 
@@ -53,7 +53,14 @@ fn main() -> ExitCode {
     }
 }
 
+/// Command line running one of the workspace binaries, for tests driven by a `kra-lint.sh` script.
+fn bin_command_line(workspace_directory: &Utf8Path, bin_name: &str) -> String {
+    format!("cargo run --quiet --manifest-path {}/Cargo.toml --bin {} --", workspace_directory, bin_name)
+}
+
 fn main_inner() -> Result<ExitCode, Box<dyn Error>> {
+    let workspace_directory = Utf8PathBuf::try_from(env::current_dir()?)?;
+
     let test_directories = glob::glob("tests/*/")?
         .map(|glob_res| glob_res.map(|path_buf| path_buf.canonicalize().map(Utf8PathBuf::try_from)))
         .collect::<Result<Result<Result<Vec<Utf8PathBuf>, _>, _>, _>>()???;
@@ -67,22 +74,31 @@ fn main_inner() -> Result<ExitCode, Box<dyn Error>> {
         let test_name = test_directory.file_name()
             .ok_or(TestError::ExtractTestName { test_directory: test_directory.clone() })?;
 
-        let input_args = fs::read_to_string("kra-lint.args")
-            .map_err(|source| TestError::InputArgs { test_name: test_name.to_owned(), source })?
-            .split_ascii_whitespace()
-            .map(String::from)
-            .collect::<Vec<_>>();
-
-        let input_documents = glob::glob("*.kr[az]")?
-            .map(|glob_res| glob_res.map(Utf8PathBuf::try_from))
-            .collect::<Result<Result<Vec<Utf8PathBuf>, _>, _>>()??;
-
-        let kra_lint_output = Command::new("cargo")
-            .args(["run", "--bin", "kra-lint", "--quiet"])
-            .args(["--"])
-            .args(input_args)
-            .args(input_documents)
-            .output()?;
+        let kra_lint_output = if Utf8Path::new("kra-lint.sh").is_file() {
+            // Scripted tests set up their own inputs and may run several binaries
+            Command::new("sh")
+                .arg("kra-lint.sh")
+                .env("KRA_LINT", bin_command_line(&workspace_directory, "kra-lint"))
+                .env("KRA_DIFF", bin_command_line(&workspace_directory, "kra-diff"))
+                .output()?
+        } else {
+            let input_args = fs::read_to_string("kra-lint.args")
+                .map_err(|source| TestError::InputArgs { test_name: test_name.to_owned(), source })?
+                .split_ascii_whitespace()
+                .map(String::from)
+                .collect::<Vec<_>>();
+
+            let input_documents = glob::glob("*.kr[az]")?
+                .map(|glob_res| glob_res.map(Utf8PathBuf::try_from))
+                .collect::<Result<Result<Vec<Utf8PathBuf>, _>, _>>()??;
+
+            Command::new("cargo")
+                .args(["run", "--bin", "kra-lint", "--quiet"])
+                .args(["--"])
+                .args(input_args)
+                .args(input_documents)
+                .output()?
+        };
 
         let expected_stdout = fs::read("kra-lint.stdout")
             .map_err(|source| TestError::ExpectedStdout { test_name: test_name.to_owned(), source })?;
@@ -1,23 +1,30 @@
-use std::cell::RefCell;
-use std::fs::File;
-use std::io;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::sync::{Mutex, MutexGuard, PoisonError};
 
 use camino::{Utf8Path, Utf8PathBuf};
-use strong_xml::XmlRead;
-use zip::ZipArchive;
+use strong_xml::{XmlRead, XmlWrite};
+use zip::result::ZipError;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
 
 use crate::kra_document_info::KraDocumentInfo;
 use crate::kra_error::KraError;
 use crate::kra_main_doc::KraMainDoc;
+use crate::kra_xml_patch::patch_xml;
 
 #[derive(Debug)]
 pub struct KraArchive {
     pub document_info: KraDocumentInfo,
     pub main_doc: KraMainDoc,
-    pub zip_archive: RefCell<ZipArchive<File>>,
+    zip_archive: Mutex<ZipArchive<File>>,
     pub zip_path: Utf8PathBuf,
 }
 
+const MIMETYPE_PATH: &str = "mimetype";
+const MAIN_DOC_PATH: &str = "maindoc.xml";
+const DOCUMENT_INFO_PATH: &str = "documentinfo.xml";
+
 impl KraArchive {
     pub fn from_path(path: &Utf8Path) -> Result<Self, KraError> {
         let zip_file = File::open(path).map_err(|source| KraError::ArchiveCannotOpen { path: path.into(), source })?;
@@ -48,10 +55,130 @@ impl KraArchive {
         }
 
         Ok(KraArchive {
-            document_info: kra_xml!(KraDocumentInfo, "documentinfo.xml"),
-            main_doc: kra_xml!(KraMainDoc, "maindoc.xml"),
-            zip_archive: RefCell::new(zip_archive),
+            document_info: kra_xml!(KraDocumentInfo, DOCUMENT_INFO_PATH),
+            main_doc: kra_xml!(KraMainDoc, MAIN_DOC_PATH),
+            zip_archive: Mutex::new(zip_archive),
             zip_path: path.to_owned(),
         })
     }
+
+    /// Locks the underlying ZIP archive, so that documents can be shared between threads.
+    pub fn zip_archive(&self) -> MutexGuard<'_, ZipArchive<File>> {
+        // Reading entries leaves no state behind which a panicking thread could have corrupted
+        self.zip_archive.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Writes the document to `path`, which may be the path it was loaded from.
+    ///
+    /// The modified attributes and texts of `maindoc.xml` and `documentinfo.xml` are
+    /// patched into their original XML, every other entry is copied over byte-for-byte.
+    /// The `mimetype` entry is always written first and stored uncompressed.
+    pub fn save_to_path(&self, path: &Utf8Path) -> Result<(), KraError> {
+        let mut zip_archive = self.zip_archive();
+
+        macro_rules! kra_xml {
+            ($xml_type:ident, $xml_value:expr, $xml_path:expr) => {{
+                let serialize = |xml_value: &$xml_type| {
+                    xml_value.to_string().map_err(|source| KraError::XmlCannotSerialize {
+                        path: path.into(),
+                        xml_path: $xml_path.to_owned(),
+                        source,
+                    })
+                };
+
+                let original_data = io::read_to_string(zip_archive.by_name($xml_path)?)?;
+                let original_value = $xml_type::from_str(&original_data)?;
+
+                let original_model_data = serialize(&original_value)?;
+                let current_model_data = serialize($xml_value)?;
+
+                if original_model_data == current_model_data {
+                    None
+                } else {
+                    let patched_data =
+                        patch_xml(&original_data, &original_model_data, &current_model_data).map_err(|source| {
+                            KraError::XmlCannotPatch { path: path.into(), xml_path: $xml_path.to_owned(), source }
+                        })?;
+
+                    Some(patched_data)
+                }
+            }};
+        }
+
+        let main_doc_data = kra_xml!(KraMainDoc, &self.main_doc, MAIN_DOC_PATH);
+        let document_info_data = kra_xml!(KraDocumentInfo, &self.document_info, DOCUMENT_INFO_PATH);
+
+        // Other applications recognize documents by the uncompressed mimetype at the start of the archive
+        let mimetype_data = match zip_archive.by_name(MIMETYPE_PATH) {
+            Ok(zip_file) if zip_file.compression() != CompressionMethod::Stored => Some(io::read_to_string(zip_file)?),
+            Ok(_) | Err(ZipError::FileNotFound) => None,
+            Err(err) => return Err(err.into()),
+        };
+
+        // Writing into a temporary file first, the source archive may be the same file
+        let temp_path = path.with_file_name(format!(".{}.tmp", path.file_name().unwrap_or_default()));
+
+        let temp_file =
+            File::create(&temp_path).map_err(|source| KraError::ArchiveCannotCreate { path: path.into(), source })?;
+
+        let mut zip_writer = ZipWriter::new(temp_file);
+
+        let write_result = (|| -> Result<(), KraError> {
+            let mut file_names = Vec::with_capacity(zip_archive.len());
+            for file_index in 0..zip_archive.len() {
+                file_names.push(zip_archive.by_index_raw(file_index)?.name().to_owned());
+            }
+
+            // The mimetype entry has to come first
+            let mut file_indices = (0..zip_archive.len()).collect::<Vec<_>>();
+            file_indices.sort_by_key(|&file_index| file_names[file_index] != MIMETYPE_PATH);
+
+            for file_index in file_indices {
+                let zip_file = zip_archive.by_index_raw(file_index)?;
+
+                let replaced_data = match zip_file.name() {
+                    MIMETYPE_PATH => mimetype_data.as_ref(),
+                    MAIN_DOC_PATH => main_doc_data.as_ref(),
+                    DOCUMENT_INFO_PATH => document_info_data.as_ref(),
+                    _ => None,
+                };
+
+                if let Some(replaced_data) = replaced_data {
+                    // Keeping the metadata of the replaced entry, only its contents change
+                    let mut file_options = FileOptions::default()
+                        .compression_method(match (zip_file.name(), zip_file.compression()) {
+                            (MIMETYPE_PATH, _) | (_, CompressionMethod::Stored) => CompressionMethod::Stored,
+                            _ => CompressionMethod::Deflated,
+                        })
+                        .last_modified_time(zip_file.last_modified());
+
+                    if let Some(unix_mode) = zip_file.unix_mode() {
+                        file_options = file_options.unix_permissions(unix_mode);
+                    }
+
+                    let file_name = zip_file.name().to_owned();
+                    drop(zip_file);
+
+                    zip_writer.start_file(file_name, file_options)?;
+                    zip_writer.write_all(replaced_data.as_bytes())?;
+                } else {
+                    zip_writer.raw_copy_file(zip_file)?;
+                }
+            }
+
+            zip_writer.finish()?;
+            Ok(())
+        })();
+
+        if let Err(err) = write_result {
+            let _ = fs::remove_file(&temp_path);
+
+            return Err(match err {
+                KraError::ZipError(source) => KraError::ArchiveCannotWrite { path: path.into(), source },
+                err => err,
+            });
+        }
+
+        fs::rename(&temp_path, path).map_err(|source| KraError::ArchiveCannotCreate { path: path.into(), source })
+    }
 }
@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumString};
 
-#[derive(Debug, PartialEq, Deserialize, Serialize, Display, EnumString)]
+use crate::kra_xml_fields::KraXmlAttribute;
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Display, EnumString)]
 #[serde(rename_all = "snake_case")]
 pub enum KraColorLabel {
     #[strum(serialize = "0", to_string = "none")]
@@ -31,3 +33,21 @@ pub enum KraColorLabel {
     #[strum(serialize = "8", to_string = "black")]
     Black,
 }
+
+impl KraXmlAttribute for KraColorLabel {
+    #[rustfmt::skip]
+    fn to_xml_attribute(&self) -> String {
+        match self {
+            KraColorLabel::None   => "0",
+            KraColorLabel::Blue   => "1",
+            KraColorLabel::Green  => "2",
+            KraColorLabel::Yellow => "3",
+            KraColorLabel::Orange => "4",
+            KraColorLabel::Brown  => "5",
+            KraColorLabel::Red    => "6",
+            KraColorLabel::Purple => "7",
+            KraColorLabel::Black  => "8",
+        }
+        .to_owned()
+    }
+}
@@ -1,8 +1,14 @@
-use strong_xml::XmlRead;
+use std::io::Write;
 
-#[derive(Debug, XmlRead)]
+use serde::Serialize;
+use strong_xml::{XmlRead, XmlResult, XmlWrite, XmlWriter};
+
+#[derive(Debug, XmlRead, Serialize)]
 #[xml(tag = "document-info")]
 pub struct KraDocumentInfo {
+    #[xml(attr = "xmlns")]
+    pub xmlns: Option<String>,
+
     #[xml(child = "about")]
     pub about: KraDocumentInfoAbout,
 
@@ -10,7 +16,7 @@ pub struct KraDocumentInfo {
     pub author: KraDocumentInfoAuthor,
 }
 
-#[derive(Debug, XmlRead)]
+#[derive(Debug, XmlRead, XmlWrite, Serialize)]
 #[xml(tag = "about")]
 pub struct KraDocumentInfoAbout {
     #[xml(flatten_text = "title")]
@@ -22,7 +28,7 @@ pub struct KraDocumentInfoAbout {
     #[xml(flatten_text = "subject")]
     pub subject: String,
 
-    #[xml(flatten_text = "abstract")]
+    #[xml(flatten_text = "abstract", cdata)]
     pub r#abstract: String,
 
     #[xml(flatten_text = "keyword")]
@@ -50,7 +56,7 @@ pub struct KraDocumentInfoAbout {
     pub license: String,
 }
 
-#[derive(Debug, XmlRead)]
+#[derive(Debug, XmlRead, XmlWrite, Serialize)]
 #[xml(tag = "author")]
 pub struct KraDocumentInfoAuthor {
     #[xml(flatten_text = "full-name")]
@@ -73,4 +79,35 @@ pub struct KraDocumentInfoAuthor {
 
     #[xml(flatten_text = "company")]
     pub company: String,
+
+    #[xml(child = "contact")]
+    pub contacts: Vec<KraDocumentInfoContact>,
+}
+
+#[derive(Debug, XmlRead, XmlWrite, Serialize)]
+#[xml(tag = "contact")]
+pub struct KraDocumentInfoContact {
+    #[xml(attr = "type")]
+    pub r#type: String,
+
+    #[xml(text)]
+    pub value: String,
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+// `#[derive(XmlWrite)]` emits an unused borrow for non-optional children
+impl XmlWrite for KraDocumentInfo {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        writer.write_element_start("document-info")?;
+        if let Some(xmlns) = self.xmlns.as_ref() {
+            writer.write_attribute("xmlns", xmlns)?;
+        }
+        writer.write_element_end_open()?;
+
+        self.about.to_writer(writer)?;
+        self.author.to_writer(writer)?;
+
+        Ok(writer.write_element_end_close("document-info")?)
+    }
 }
@@ -5,6 +5,8 @@ use derive_more::{Display, Error, From};
 use strong_xml::XmlError;
 use zip::result::ZipError;
 
+use crate::kra_xml_patch::KraXmlPatchError;
+
 #[rustfmt::skip]
 #[non_exhaustive]
 #[derive(Debug, Display, Error, From)]
@@ -21,6 +23,18 @@ pub enum KraError {
         source: ZipError,
     },
 
+    #[display(fmt = "Cannot write KRA document \"{path:}\"")]
+    ArchiveCannotWrite {
+        path: FormattedPathBuf,
+        source: ZipError,
+    },
+
+    #[display(fmt = "Cannot create KRA document \"{path:}\"")]
+    ArchiveCannotCreate {
+        path: FormattedPathBuf,
+        source: io::Error,
+    },
+
     #[display(fmt = "Cannot find '{xml_path:}' in '{path:}'")]
     XmlNotFound {
         path: FormattedPathBuf,
@@ -42,6 +56,20 @@ pub enum KraError {
         source: XmlError,
     },
 
+    #[display(fmt = "Cannot serialize '{xml_path:}' for '{path:}'")]
+    XmlCannotSerialize {
+        path: FormattedPathBuf,
+        xml_path: String,
+        source: XmlError,
+    },
+
+    #[display(fmt = "Cannot patch '{xml_path:}' for '{path:}'")]
+    XmlCannotPatch {
+        path: FormattedPathBuf,
+        xml_path: String,
+        source: KraXmlPatchError,
+    },
+
     #[display(fmt = "Vector layer contents not found at '{svg_path:}'")]
     ContentSvgNotFound {
         svg_path: String,
@@ -52,6 +80,37 @@ pub enum KraError {
         color_profile_path: String,
     },
 
+    #[display(fmt = "Layer pixel data not found at '{pixel_data_path:}'")]
+    PixelDataNotFound {
+        pixel_data_path: String,
+    },
+
+    #[display(fmt = "Malformed layer pixel data at '{pixel_data_path:}'")]
+    PixelDataMalformed {
+        pixel_data_path: String,
+    },
+
+    #[display(fmt = "Layer pixel data too large at '{pixel_data_path:}'")]
+    PixelDataTooLarge {
+        pixel_data_path: String,
+    },
+
+    #[display(fmt = "Unsupported colorspace '{colorspace_name:}'")]
+    UnsupportedColorspace {
+        colorspace_name: String,
+    },
+
+    #[display(fmt = "Image not found at '{image_path:}'")]
+    ImageNotFound {
+        image_path: String,
+    },
+
+    #[display(fmt = "Cannot decode image at '{image_path:}'")]
+    ImageCannotDecode {
+        image_path: String,
+        source: png::DecodingError,
+    },
+
     #[display(fmt = "Mask file name field not found")]
     MaskFileNameFieldNotFound,
 
@@ -1,11 +1,11 @@
 #![allow(clippy::needless_late_init)]
 
 use derive_more::IntoIterator;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use strong_xml::XmlRead;
 use strum::EnumString;
 
-#[derive(Debug, XmlRead, IntoIterator)]
+#[derive(Debug, XmlRead, IntoIterator, Serialize)]
 #[xml(tag = "params")]
 pub struct KraFilterParamsContainer {
     #[xml(attr = "version")]
@@ -16,7 +16,7 @@ pub struct KraFilterParamsContainer {
     pub params: Vec<KraFilterParam>,
 }
 
-#[derive(Debug, XmlRead)]
+#[derive(Debug, XmlRead, Serialize)]
 #[xml(tag = "param")]
 pub struct KraFilterParam {
     #[xml(attr = "name")]
@@ -29,7 +29,8 @@ pub struct KraFilterParam {
     pub value: String,
 }
 
-#[derive(Debug, PartialEq, Deserialize, EnumString)]
+#[derive(Debug, PartialEq, Deserialize, Serialize, EnumString)]
+#[serde(rename_all = "snake_case")]
 pub enum KraFilterParamType {
     #[strum(serialize = "internal")]
     Internal,
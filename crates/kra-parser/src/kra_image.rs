@@ -0,0 +1,51 @@
+use png::{ColorType, Decoder, DecodingError, Transformations};
+
+/// Decoded PNG image, always converted to 8-bit RGBA.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KraImage {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+impl KraImage {
+    pub fn from_png(png_data: &[u8]) -> Result<KraImage, DecodingError> {
+        let mut decoder = Decoder::new(png_data);
+        decoder.set_transformations(Transformations::normalize_to_color8());
+
+        let mut reader = decoder.read_info()?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let frame_info = reader.next_frame(&mut buffer)?;
+        buffer.truncate(frame_info.buffer_size());
+
+        #[rustfmt::skip]
+        let pixels = match frame_info.color_type {
+            ColorType::Rgba => {
+                buffer
+            }
+            ColorType::Rgb => {
+                buffer.chunks_exact(3).flat_map(|px| [px[0], px[1], px[2], 0xFF]).collect()
+            }
+            ColorType::GrayscaleAlpha => {
+                buffer.chunks_exact(2).flat_map(|px| [px[0], px[0], px[0], px[1]]).collect()
+            }
+            ColorType::Grayscale => {
+                buffer.iter().flat_map(|&px| [px, px, px, 0xFF]).collect()
+            }
+            // Palette images are expanded to RGB(A) by `normalize_to_color8`
+            ColorType::Indexed => {
+                unreachable!()
+            }
+        };
+
+        Ok(KraImage { width: frame_info.width as usize, height: frame_info.height as usize, pixels })
+    }
+
+    /// Width and height of a PNG image, only reading its header.
+    pub fn size_from_png(png_data: &[u8]) -> Result<(usize, usize), DecodingError> {
+        let reader = Decoder::new(png_data).read_info()?;
+        let (width, height) = reader.info().size();
+
+        Ok((width as usize, height as usize))
+    }
+}
@@ -6,7 +6,7 @@ use strum::{Display, EnumString};
 use crate::kra_color_label::KraColorLabel;
 use crate::kra_xml_fields::KraXmlPoint;
 
-#[derive(Debug, XmlRead, IntoIterator)]
+#[derive(Debug, XmlRead, IntoIterator, Serialize)]
 #[xml(tag = "keyframes")]
 pub struct KraKeyframesDocument {
     #[into_iterator(ref)]
@@ -14,7 +14,7 @@ pub struct KraKeyframesDocument {
     pub channels: Vec<KraKeyframeChannel>,
 }
 
-#[derive(Debug, XmlRead, IntoIterator)]
+#[derive(Debug, XmlRead, IntoIterator, Serialize)]
 #[xml(tag = "channel")]
 pub struct KraKeyframeChannel {
     #[xml(attr = "name")]
@@ -25,7 +25,7 @@ pub struct KraKeyframeChannel {
     pub keyframes: Vec<KraKeyframe>,
 }
 
-#[derive(Debug, XmlRead)]
+#[derive(Debug, XmlRead, Serialize)]
 #[xml(tag = "keyframe")]
 pub struct KraKeyframe {
     #[xml(attr = "time")]
@@ -1,14 +1,19 @@
+use std::io::Write;
+
 use derive_more::IntoIterator;
 use serde::{Deserialize, Serialize};
-use strong_xml::XmlRead;
+use strong_xml::{XmlRead, XmlResult, XmlWrite, XmlWriter};
 use strum::{Display, EnumString};
 
 use crate::kra_color_label::KraColorLabel;
-use crate::kra_xml_fields::{KraXmlTimeRange, KraXmlValue};
+use crate::kra_xml_fields::{KraXmlAttribute, KraXmlTimeRange, KraXmlValue, KraXmlWriterExt};
 
-#[derive(Debug, XmlRead)]
+#[derive(Debug, XmlRead, Serialize)]
 #[xml(tag = "DOC")]
 pub struct KraMainDoc {
+    #[xml(attr = "xmlns")]
+    pub xmlns: Option<String>,
+
     #[xml(attr = "kritaVersion")]
     pub software_version: String,
 
@@ -22,7 +27,7 @@ pub struct KraMainDoc {
     pub image: KraMainDocImage,
 }
 
-#[derive(Debug, XmlRead)]
+#[derive(Debug, XmlRead, Serialize)]
 #[xml(tag = "IMAGE")]
 pub struct KraMainDocImage {
     #[xml(attr = "name")]
@@ -69,7 +74,7 @@ pub struct KraMainDocImage {
 }
 
 #[rustfmt::skip]
-#[derive(Debug, XmlRead, Default, IntoIterator)]
+#[derive(Debug, XmlRead, Default, IntoIterator, Serialize)]
 #[xml(tag = "layers")]
 pub struct KraMainDocLayerContainer (
     #[into_iterator(ref)]
@@ -77,7 +82,7 @@ pub struct KraMainDocLayerContainer (
     pub Vec<KraMainDocLayer>,
 );
 
-#[derive(Debug, XmlRead)]
+#[derive(Debug, XmlRead, Serialize)]
 #[xml(tag = "layer")]
 pub struct KraMainDocLayer {
     #[xml(attr = "channelflags")]
@@ -174,7 +179,7 @@ pub struct KraMainDocLayer {
     pub mask_container: Option<KraMainDocMaskContainer>,
 }
 
-#[derive(Debug, XmlRead)]
+#[derive(Debug, XmlRead, Serialize)]
 #[xml(tag = "animation")]
 pub struct KraMainDocAnimation {
     #[xml(child = "framerate")]
@@ -187,7 +192,7 @@ pub struct KraMainDocAnimation {
     pub current_time: KraXmlValue<usize>,
 }
 
-#[derive(Debug, XmlRead)]
+#[derive(Debug, XmlRead, Serialize)]
 #[xml(tag = "audio")]
 pub struct KraMainDocAudio {
     #[xml(child = "masterChannelPath")]
@@ -201,7 +206,7 @@ pub struct KraMainDocAudio {
 }
 
 #[rustfmt::skip]
-#[derive(Debug, XmlRead, Default, IntoIterator)]
+#[derive(Debug, XmlRead, Default, IntoIterator, Serialize)]
 #[xml(tag = "masks")]
 pub struct KraMainDocMaskContainer (
     #[into_iterator(ref)]
@@ -209,7 +214,7 @@ pub struct KraMainDocMaskContainer (
     pub Vec<KraMainDocMask>,
 );
 
-#[derive(Debug, XmlRead)]
+#[derive(Debug, XmlRead, Serialize)]
 #[xml(tag = "mask")]
 pub struct KraMainDocMask {
     #[xml(attr = "active")]
@@ -283,7 +288,7 @@ pub struct KraMainDocMask {
 }
 
 #[rustfmt::skip]
-#[derive(Debug, XmlRead, IntoIterator)]
+#[derive(Debug, XmlRead, IntoIterator, Serialize)]
 #[xml(tag = "Palettes")]
 pub struct KraMainDocPaletteContainer (
     #[into_iterator(ref)]
@@ -291,7 +296,7 @@ pub struct KraMainDocPaletteContainer (
     pub Vec<KraMainDocResource>,
 );
 
-#[derive(Debug, XmlRead)]
+#[derive(Debug, XmlRead, Serialize)]
 #[xml(tag = "resource")]
 pub struct KraMainDocResource {
     #[xml(attr = "filename")]
@@ -308,7 +313,7 @@ pub struct KraMainDocResource {
 }
 
 #[rustfmt::skip]
-#[derive(Debug, XmlRead, IntoIterator)]
+#[derive(Debug, XmlRead, IntoIterator, Serialize)]
 #[xml(tag = "compositions")]
 pub struct KraMainDocCompositionContainer (
     #[into_iterator(ref)]
@@ -316,7 +321,7 @@ pub struct KraMainDocCompositionContainer (
     pub Vec<KraMainDocComposition>,
 );
 
-#[derive(Debug, XmlRead)]
+#[derive(Debug, XmlRead, Serialize)]
 #[xml(tag = "composition")]
 pub struct KraMainDocComposition {
     #[xml(attr = "exportEnabled")]
@@ -329,7 +334,7 @@ pub struct KraMainDocComposition {
     pub values: Vec<KraMainDocCompositionValue>,
 }
 
-#[derive(Debug, XmlRead)]
+#[derive(Debug, XmlRead, Serialize)]
 #[xml(tag = "value")]
 pub struct KraMainDocCompositionValue {
     #[xml(attr = "collapsed")]
@@ -342,7 +347,7 @@ pub struct KraMainDocCompositionValue {
     pub uuid: String,
 }
 
-#[derive(Debug, PartialEq, Deserialize, Serialize, Display, EnumString)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Display, EnumString)]
 #[serde(rename_all = "snake_case")]
 pub enum KraLayerType {
     #[strum(serialize = "paintlayer", to_string = "paint_layer")]
@@ -367,7 +372,7 @@ pub enum KraLayerType {
     FileLayer,
 }
 
-#[derive(Debug, PartialEq, Deserialize, Serialize, Display, EnumString)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Display, EnumString)]
 #[serde(rename_all = "snake_case")]
 pub enum KraMaskType {
     #[strum(serialize = "transparencymask", to_string = "transparency_mask")]
@@ -386,7 +391,7 @@ pub enum KraMaskType {
     LocalSelection,
 }
 
-#[derive(Debug, PartialEq, Deserialize, Serialize, Display, EnumString)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Display, EnumString)]
 #[serde(rename_all = "snake_case")]
 pub enum KraScalingMethod {
     #[strum(serialize = "0", to_string = "none")]
@@ -398,3 +403,295 @@ pub enum KraScalingMethod {
     #[strum(serialize = "2", to_string = "adapt_resolution")]
     AdaptResolution,
 }
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+// Hand-written because Krita stores booleans and enums in a different format
+// than the one `#[derive(XmlWrite)]` would produce. Elements and attributes
+// not modelled above are not written, `KraArchive::save_to_path` patches the
+// differences into the original XML instead.
+
+impl XmlWrite for KraMainDoc {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        writer.write_element_start("DOC")?;
+        writer.write_kra_attribute_opt("xmlns", &self.xmlns)?;
+        writer.write_kra_attribute("editor", &self.editor)?;
+        writer.write_kra_attribute("kritaVersion", &self.software_version)?;
+        writer.write_kra_attribute("syntaxVersion", &self.syntax_version)?;
+        writer.write_element_end_open()?;
+
+        self.image.to_writer(writer)?;
+
+        Ok(writer.write_element_end_close("DOC")?)
+    }
+}
+
+impl XmlWrite for KraMainDocImage {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        writer.write_element_start("IMAGE")?;
+        writer.write_kra_attribute("name", &self.name)?;
+        writer.write_kra_attribute("description", &self.description)?;
+        writer.write_kra_attribute("mime", &self.mime)?;
+        writer.write_kra_attribute("width", &self.width)?;
+        writer.write_kra_attribute("height", &self.height)?;
+        writer.write_kra_attribute("x-res", &self.x_res)?;
+        writer.write_kra_attribute("y-res", &self.y_res)?;
+        writer.write_kra_attribute("profile", &self.profile)?;
+        writer.write_kra_attribute("colorspacename", &self.colorspace_name)?;
+        writer.write_element_end_open()?;
+
+        self.layer_container.to_writer(writer)?;
+
+        if let Some(palette_container) = self.palette_container.as_ref() {
+            palette_container.to_writer(writer)?;
+        }
+
+        if let Some(composition_container) = self.composition_container.as_ref() {
+            composition_container.to_writer(writer)?;
+        }
+
+        if let Some(animation) = self.animation.as_ref() {
+            animation.to_writer(writer)?;
+        }
+
+        if let Some(audio) = self.audio.as_ref() {
+            audio.to_writer(writer)?;
+        }
+
+        Ok(writer.write_element_end_close("IMAGE")?)
+    }
+}
+
+fn write_children<W, T>(writer: &mut XmlWriter<W>, tag: &str, children: &[T]) -> XmlResult<()>
+where
+    W: Write,
+    T: XmlWrite,
+{
+    writer.write_element_start(tag)?;
+
+    if children.is_empty() {
+        writer.write_element_end_empty()?;
+    } else {
+        writer.write_element_end_open()?;
+        for child in children {
+            child.to_writer(writer)?;
+        }
+        writer.write_element_end_close(tag)?;
+    }
+
+    Ok(())
+}
+
+impl XmlWrite for KraMainDocLayerContainer {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        write_children(writer, "layers", &self.0)
+    }
+}
+
+impl XmlWrite for KraMainDocLayer {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        writer.write_element_start("layer")?;
+        writer.write_kra_attribute("channelflags", &self.channel_flags)?;
+        writer.write_kra_attribute_opt("channellockflags", &self.channel_lock_flags)?;
+        writer.write_kra_attribute("collapsed", &self.collapsed)?;
+        writer.write_kra_attribute("colorlabel", &self.color_label)?;
+        writer.write_kra_attribute_opt("colorspacename", &self.colorspace_name)?;
+        writer.write_kra_attribute("compositeop", &self.composite_op)?;
+        writer.write_kra_attribute("filename", &self.file_name)?;
+        writer.write_kra_attribute_opt("generatorname", &self.generator_name)?;
+        writer.write_kra_attribute_opt("generatorversion", &self.generator_version)?;
+        writer.write_kra_attribute("intimeline", &self.in_timeline)?;
+        writer.write_kra_attribute("locked", &self.locked)?;
+        writer.write_kra_attribute("name", &self.name)?;
+        writer.write_kra_attribute("nodetype", &self.layer_type)?;
+        writer.write_kra_attribute_opt("onionskin", &self.onion_skin)?;
+        writer.write_kra_attribute("opacity", &self.opacity)?;
+        writer.write_kra_attribute_opt("selected", &self.selected)?;
+        writer.write_kra_attribute("uuid", &self.uuid)?;
+        writer.write_kra_attribute("visible", &self.visible)?;
+        writer.write_kra_attribute("x", &self.x)?;
+        writer.write_kra_attribute("y", &self.y)?;
+        writer.write_kra_attribute_opt("clonetype", &self.clone_type)?;
+        writer.write_kra_attribute_opt("clonefrom", &self.clone_from)?;
+        writer.write_kra_attribute_opt("clonefromuuid", &self.clone_from_uuid)?;
+        writer.write_kra_attribute_opt("source", &self.source)?;
+        writer.write_kra_attribute_opt("keyframes", &self.keyframes)?;
+        writer.write_kra_attribute_opt("layerstyle", &self.layer_style)?;
+        writer.write_kra_attribute_opt("scalingmethod", &self.scaling_method)?;
+        writer.write_kra_attribute_opt("filtername", &self.filter_name)?;
+        writer.write_kra_attribute_opt("filterversion", &self.filter_version)?;
+
+        if self.layer_container.is_none() && self.mask_container.is_none() {
+            return Ok(writer.write_element_end_empty()?);
+        }
+
+        writer.write_element_end_open()?;
+
+        if let Some(layer_container) = self.layer_container.as_ref() {
+            layer_container.to_writer(writer)?;
+        }
+
+        if let Some(mask_container) = self.mask_container.as_ref() {
+            mask_container.to_writer(writer)?;
+        }
+
+        Ok(writer.write_element_end_close("layer")?)
+    }
+}
+
+impl XmlWrite for KraMainDocAnimation {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        writer.write_element_start("animation")?;
+        writer.write_element_end_open()?;
+
+        self.framerate.to_writer_with_tag(writer, "framerate")?;
+        self.range.to_writer_with_tag(writer, "range")?;
+        self.current_time.to_writer_with_tag(writer, "currentTime")?;
+
+        Ok(writer.write_element_end_close("animation")?)
+    }
+}
+
+impl XmlWrite for KraMainDocAudio {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        writer.write_element_start("audio")?;
+        writer.write_element_end_open()?;
+
+        self.master_channel_path.to_writer_with_tag(writer, "masterChannelPath")?;
+        self.audio_muted.to_writer_with_tag(writer, "audioMuted")?;
+        self.audio_volume.to_writer_with_tag(writer, "audioVolume")?;
+
+        Ok(writer.write_element_end_close("audio")?)
+    }
+}
+
+impl XmlWrite for KraMainDocMaskContainer {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        write_children(writer, "masks", &self.0)
+    }
+}
+
+impl XmlWrite for KraMainDocMask {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        writer.write_element_start("mask")?;
+        writer.write_kra_attribute_opt("active", &self.active)?;
+        writer.write_kra_attribute_opt("cleanup", &self.cleanup)?;
+        writer.write_kra_attribute_opt("colorlabel", &self.color_label)?;
+        writer.write_kra_attribute_opt("colorspacename", &self.colorspace_name)?;
+        writer.write_kra_attribute_opt("compositeop", &self.composite_op)?;
+        writer.write_kra_attribute_opt("edge-detection-size", &self.edge_detection_size)?;
+        writer.write_kra_attribute_opt("edit-keystrokes", &self.edit_keystrokes)?;
+        writer.write_kra_attribute_opt("filename", &self.file_name)?;
+        writer.write_kra_attribute_opt("filtername", &self.filter_name)?;
+        writer.write_kra_attribute_opt("filterversion", &self.filter_version)?;
+        writer.write_kra_attribute_opt("fuzzy-radius", &self.fuzzy_radius)?;
+        writer.write_kra_attribute_opt("intimeline", &self.in_timeline)?;
+        writer.write_kra_attribute_opt("limit-to-device", &self.limit_to_device)?;
+        writer.write_kra_attribute("locked", &self.locked)?;
+        writer.write_kra_attribute("name", &self.name)?;
+        writer.write_kra_attribute("nodetype", &self.mask_type)?;
+        writer.write_kra_attribute_opt("show-coloring", &self.show_coloring)?;
+        writer.write_kra_attribute_opt("use-edge-detection", &self.use_edge_detection)?;
+        writer.write_kra_attribute("uuid", &self.uuid)?;
+        writer.write_kra_attribute("visible", &self.visible)?;
+        writer.write_kra_attribute("x", &self.x)?;
+        writer.write_kra_attribute("y", &self.y)?;
+        writer.write_kra_attribute_opt("keyframes", &self.keyframes)?;
+        Ok(writer.write_element_end_empty()?)
+    }
+}
+
+impl XmlWrite for KraMainDocPaletteContainer {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        write_children(writer, "Palettes", &self.0)
+    }
+}
+
+impl XmlWrite for KraMainDocResource {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        writer.write_element_start("resource")?;
+        writer.write_kra_attribute("filename", &self.file_name)?;
+        writer.write_kra_attribute("md5sum", &self.md5_sum)?;
+        writer.write_kra_attribute("name", &self.name)?;
+        writer.write_kra_attribute("type", &self.r#type)?;
+        Ok(writer.write_element_end_empty()?)
+    }
+}
+
+impl XmlWrite for KraMainDocCompositionContainer {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        write_children(writer, "compositions", &self.0)
+    }
+}
+
+impl XmlWrite for KraMainDocComposition {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        writer.write_element_start("composition")?;
+        writer.write_kra_attribute("exportEnabled", &self.export_enabled)?;
+        writer.write_kra_attribute("name", &self.name)?;
+
+        if self.values.is_empty() {
+            return Ok(writer.write_element_end_empty()?);
+        }
+
+        writer.write_element_end_open()?;
+        for value in &self.values {
+            value.to_writer(writer)?;
+        }
+        Ok(writer.write_element_end_close("composition")?)
+    }
+}
+
+impl XmlWrite for KraMainDocCompositionValue {
+    fn to_writer<W: Write>(&self, writer: &mut XmlWriter<W>) -> XmlResult<()> {
+        writer.write_element_start("value")?;
+        writer.write_kra_attribute("collapsed", &self.collapsed)?;
+        writer.write_kra_attribute("visible", &self.visible)?;
+        writer.write_kra_attribute("uuid", &self.uuid)?;
+        Ok(writer.write_element_end_empty()?)
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+impl KraXmlAttribute for KraLayerType {
+    #[rustfmt::skip]
+    fn to_xml_attribute(&self) -> String {
+        match self {
+            KraLayerType::PaintLayer  => "paintlayer",
+            KraLayerType::GroupLayer  => "grouplayer",
+            KraLayerType::CloneLayer  => "clonelayer",
+            KraLayerType::VectorLayer => "shapelayer",
+            KraLayerType::FilterLayer => "adjustmentlayer",
+            KraLayerType::FillLayer   => "generatorlayer",
+            KraLayerType::FileLayer   => "filelayer",
+        }
+        .to_owned()
+    }
+}
+
+impl KraXmlAttribute for KraMaskType {
+    #[rustfmt::skip]
+    fn to_xml_attribute(&self) -> String {
+        match self {
+            KraMaskType::TransparencyMask => "transparencymask",
+            KraMaskType::FilterMask       => "filtermask",
+            KraMaskType::ColorizeMask     => "colorizemask",
+            KraMaskType::TransformMask    => "transformmask",
+            KraMaskType::LocalSelection   => "selectionmask",
+        }
+        .to_owned()
+    }
+}
+
+impl KraXmlAttribute for KraScalingMethod {
+    #[rustfmt::skip]
+    fn to_xml_attribute(&self) -> String {
+        match self {
+            KraScalingMethod::None            => "0",
+            KraScalingMethod::ScaleToImage    => "1",
+            KraScalingMethod::AdaptResolution => "2",
+        }
+        .to_owned()
+    }
+}
@@ -0,0 +1,369 @@
+use std::str::FromStr;
+
+use half::f16;
+
+use crate::kra_error::KraError;
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KraColorModel {
+    Alpha,
+    Gray,
+    Rgb,
+    Cmyk,
+    Lab,
+    Xyz,
+    YCbCr,
+}
+
+impl KraColorModel {
+    pub fn channel_count(&self) -> usize {
+        match self {
+            KraColorModel::Alpha => 1,
+            KraColorModel::Gray => 2,
+            KraColorModel::Rgb => 4,
+            KraColorModel::Cmyk => 5,
+            KraColorModel::Lab => 4,
+            KraColorModel::Xyz => 4,
+            KraColorModel::YCbCr => 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KraColorDepth {
+    U8,
+    U16,
+    F16,
+    F32,
+}
+
+impl KraColorDepth {
+    pub fn channel_size(&self) -> usize {
+        match self {
+            KraColorDepth::U8 => 1,
+            KraColorDepth::U16 => 2,
+            KraColorDepth::F16 => 2,
+            KraColorDepth::F32 => 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KraColorspace {
+    pub color_model: KraColorModel,
+    pub color_depth: KraColorDepth,
+}
+
+impl KraColorspace {
+    pub fn pixel_size(&self) -> usize {
+        self.color_model.channel_count() * self.color_depth.channel_size()
+    }
+}
+
+impl FromStr for KraColorspace {
+    type Err = KraError;
+
+    /// Parses the colorspace IDs Krita writes, which do not follow a single naming scheme
+    /// (e.g. "LABA" is 16-bit Lab, while 8-bit Lab is "LABAU8").
+    #[rustfmt::skip]
+    fn from_str(colorspace_name: &str) -> Result<Self, Self::Err> {
+        const COLORSPACES: &[(&str, KraColorModel, KraColorDepth)] = &[
+            ("ALPHA",     KraColorModel::Alpha, KraColorDepth::U8 ),
+            ("ALPHAU16",  KraColorModel::Alpha, KraColorDepth::U16),
+            ("ALPHAF16",  KraColorModel::Alpha, KraColorDepth::F16),
+            ("ALPHAF32",  KraColorModel::Alpha, KraColorDepth::F32),
+            ("GRAYA",     KraColorModel::Gray,  KraColorDepth::U8 ),
+            ("GRAYAU16",  KraColorModel::Gray,  KraColorDepth::U16),
+            ("GRAYAF16",  KraColorModel::Gray,  KraColorDepth::F16),
+            ("GRAYAF32",  KraColorModel::Gray,  KraColorDepth::F32),
+            ("RGBA",      KraColorModel::Rgb,   KraColorDepth::U8 ),
+            ("RGBA16",    KraColorModel::Rgb,   KraColorDepth::U16),
+            ("RgbAF16",   KraColorModel::Rgb,   KraColorDepth::F16),
+            ("RgbAF32",   KraColorModel::Rgb,   KraColorDepth::F32),
+            ("CMYK",      KraColorModel::Cmyk,  KraColorDepth::U8 ),
+            ("CMYKA16",   KraColorModel::Cmyk,  KraColorDepth::U16),
+            ("CMYKAF32",  KraColorModel::Cmyk,  KraColorDepth::F32),
+            ("LABAU8",    KraColorModel::Lab,   KraColorDepth::U8 ),
+            ("LABA",      KraColorModel::Lab,   KraColorDepth::U16),
+            ("LABAF32",   KraColorModel::Lab,   KraColorDepth::F32),
+            ("XYZAU8",    KraColorModel::Xyz,   KraColorDepth::U8 ),
+            ("XYZAU16",   KraColorModel::Xyz,   KraColorDepth::U16),
+            ("XYZAF16",   KraColorModel::Xyz,   KraColorDepth::F16),
+            ("XYZAF32",   KraColorModel::Xyz,   KraColorDepth::F32),
+            ("YCbCrAU8",  KraColorModel::YCbCr, KraColorDepth::U8 ),
+            ("YCbCrAU16", KraColorModel::YCbCr, KraColorDepth::U16),
+            ("YCbCrAF32", KraColorModel::YCbCr, KraColorDepth::F32),
+        ];
+
+        COLORSPACES
+            .iter()
+            .find(|(name, ..)| *name == colorspace_name)
+            .map(|&(_, color_model, color_depth)| KraColorspace { color_model, color_depth })
+            .ok_or_else(|| KraError::UnsupportedColorspace { colorspace_name: colorspace_name.to_owned() })
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct KraRect {
+    pub x: isize,
+    pub y: isize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl KraRect {
+    pub fn is_empty(&self) -> bool {
+        (self.width == 0) || (self.height == 0)
+    }
+}
+
+/// Channel values are interleaved in the order Krita keeps them in memory
+/// (e.g. BGRA for 8-bit and 16-bit RGB colorspaces, RGBA for floating-point ones).
+#[derive(Debug, Clone, PartialEq)]
+pub enum KraPixelBuffer {
+    U8(Vec<u8>),
+    U16(Vec<u16>),
+    F16(Vec<f16>),
+    F32(Vec<f32>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KraPixelData {
+    pub rect: KraRect,
+    pub colorspace: KraColorspace,
+    pub buffer: KraPixelBuffer,
+}
+
+impl KraPixelData {
+    pub fn channel_count(&self) -> usize {
+        self.colorspace.color_model.channel_count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rect.is_empty()
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+struct KraTileReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> KraTileReader<'a> {
+    fn read_line(&mut self) -> Option<&'a str> {
+        let remaining = self.data.get(self.position..)?;
+        let line_length = remaining.iter().position(|&b| b == b'\n')?;
+
+        self.position += line_length + 1;
+        std::str::from_utf8(&remaining[..line_length]).ok()
+    }
+
+    fn read_header<T: FromStr>(&mut self, header_name: &str) -> Option<T> {
+        self.read_line()?.strip_prefix(header_name)?.strip_prefix(' ')?.parse().ok()
+    }
+
+    fn read_bytes(&mut self, length: usize) -> Option<&'a [u8]> {
+        let bytes = self.data.get(self.position..self.position.checked_add(length)?)?;
+
+        self.position += length;
+        Some(bytes)
+    }
+}
+
+struct KraTile {
+    x: isize,
+    y: isize,
+    data: Vec<u8>,
+}
+
+const TILE_COMPRESSED_DATA_FLAG: u8 = 1;
+
+fn lzf_decompress(input: &[u8], output_size: usize) -> Option<Vec<u8>> {
+    let mut output = Vec::with_capacity(output_size);
+    let mut input_position = 0;
+
+    while input_position < input.len() {
+        let control = input[input_position] as usize;
+        input_position += 1;
+
+        if control < 32 {
+            let literal_length = control + 1;
+            output.extend_from_slice(input.get(input_position..input_position + literal_length)?);
+            input_position += literal_length;
+        } else {
+            let mut backref_length = control >> 5;
+
+            if backref_length == 7 {
+                backref_length += *input.get(input_position)? as usize;
+                input_position += 1;
+            }
+
+            let backref_offset = ((control & 0x1F) << 8) + *input.get(input_position)? as usize + 1;
+            input_position += 1;
+
+            let backref_start = output.len().checked_sub(backref_offset)?;
+
+            // Back-references may overlap the bytes being written
+            for index in backref_start..backref_start + backref_length + 2 {
+                output.push(output[index]);
+            }
+        }
+
+        if output.len() > output_size {
+            return None;
+        }
+    }
+
+    (output.len() == output_size).then_some(output)
+}
+
+fn delinearize_colors(input: &[u8], pixel_size: usize) -> Vec<u8> {
+    let pixel_count = input.len() / pixel_size;
+    let mut output = vec![0; input.len()];
+
+    for pixel_index in 0..pixel_count {
+        for byte_index in 0..pixel_size {
+            output[pixel_index * pixel_size + byte_index] = input[byte_index * pixel_count + pixel_index];
+        }
+    }
+
+    output
+}
+
+/// Upper bound for the size of decoded tiles and pixel buffers, layer headers are not trusted.
+const MAX_PIXEL_DATA_SIZE: usize = 1 << 30;
+
+impl KraPixelData {
+    /// Decodes the "VERSION 2" tiled paint device format Krita uses for storing layer pixels.
+    pub(crate) fn from_tile_data(
+        tile_data: &[u8],
+        default_pixel: Option<&[u8]>,
+        colorspace: KraColorspace,
+        (offset_x, offset_y): (isize, isize),
+        pixel_data_path: &str,
+    ) -> Result<KraPixelData, KraError> {
+        let malformed = || KraError::PixelDataMalformed { pixel_data_path: pixel_data_path.to_owned() };
+        let too_large = || KraError::PixelDataTooLarge { pixel_data_path: pixel_data_path.to_owned() };
+
+        // Sizes derived from the headers are checked before anything is allocated for them
+        let checked_size = |factors: &[usize]| {
+            factors
+                .iter()
+                .try_fold(1_usize, |size, &factor| size.checked_mul(factor))
+                .filter(|&size| size <= MAX_PIXEL_DATA_SIZE)
+                .ok_or_else(too_large)
+        };
+
+        let mut reader = KraTileReader { data: tile_data, position: 0 };
+
+        if reader.read_header::<usize>("VERSION").ok_or_else(malformed)? != 2 {
+            return Err(malformed());
+        }
+
+        let tile_width = reader.read_header::<usize>("TILEWIDTH").ok_or_else(malformed)?;
+        let tile_height = reader.read_header::<usize>("TILEHEIGHT").ok_or_else(malformed)?;
+        let pixel_size = reader.read_header::<usize>("PIXELSIZE").ok_or_else(malformed)?;
+        let tile_count = reader.read_header::<usize>("DATA").ok_or_else(malformed)?;
+
+        if pixel_size != colorspace.pixel_size() || tile_width == 0 || tile_height == 0 {
+            return Err(malformed());
+        }
+
+        let tile_data_size = checked_size(&[tile_width, tile_height, pixel_size])?;
+        let row_size = tile_width * pixel_size;
+
+        let tile_extent = |tile_size: usize| isize::try_from(tile_size).map_err(|_| too_large());
+        let (tile_extent_x, tile_extent_y) = (tile_extent(tile_width)?, tile_extent(tile_height)?);
+
+        let mut tiles = vec![];
+
+        for _ in 0..tile_count {
+            let tile_header = reader.read_line().ok_or_else(malformed)?.split(',').collect::<Vec<_>>();
+
+            let [tile_x, tile_y, "LZF", tile_length] = tile_header.as_slice() else {
+                return Err(malformed());
+            };
+
+            let tile_length = tile_length.parse().map_err(|_| malformed())?;
+            let tile_bytes = reader.read_bytes(tile_length).ok_or_else(malformed)?;
+            let (&tile_flag, tile_payload) = tile_bytes.split_first().ok_or_else(malformed)?;
+
+            let tile_data = if tile_flag == TILE_COMPRESSED_DATA_FLAG {
+                let decompressed = lzf_decompress(tile_payload, tile_data_size).ok_or_else(malformed)?;
+                delinearize_colors(&decompressed, pixel_size)
+            } else {
+                tile_payload.get(..tile_data_size).ok_or_else(malformed)?.to_vec()
+            };
+
+            let tile_x = tile_x.parse::<isize>().map_err(|_| malformed())?;
+            let tile_y = tile_y.parse::<isize>().map_err(|_| malformed())?;
+
+            // Tile corners and their positions in the document must not overflow either
+            tile_x.checked_add(tile_extent_x).zip(tile_x.checked_add(offset_x)).ok_or_else(too_large)?;
+            tile_y.checked_add(tile_extent_y).zip(tile_y.checked_add(offset_y)).ok_or_else(too_large)?;
+
+            tiles.push(KraTile { x: tile_x, y: tile_y, data: tile_data });
+        }
+
+        let rect = if tiles.is_empty() {
+            KraRect { x: offset_x, y: offset_y, width: 0, height: 0 }
+        } else {
+            let min_x = tiles.iter().map(|tile| tile.x).min().ok_or_else(malformed)?;
+            let min_y = tiles.iter().map(|tile| tile.y).min().ok_or_else(malformed)?;
+            let max_x = tiles.iter().map(|tile| tile.x + tile_extent_x).max().ok_or_else(malformed)?;
+            let max_y = tiles.iter().map(|tile| tile.y + tile_extent_y).max().ok_or_else(malformed)?;
+
+            let extent = |min: isize, max: isize| {
+                max.checked_sub(min).and_then(|extent| usize::try_from(extent).ok()).ok_or_else(too_large)
+            };
+
+            KraRect {
+                x: min_x + offset_x,
+                y: min_y + offset_y,
+                width: extent(min_x, max_x)?,
+                height: extent(min_y, max_y)?,
+            }
+        };
+
+        let default_pixel = match default_pixel {
+            Some(default_pixel) if default_pixel.len() == pixel_size => default_pixel.to_vec(),
+            Some(_) => return Err(malformed()),
+            None => vec![0; pixel_size],
+        };
+
+        checked_size(&[rect.width, rect.height, pixel_size])?;
+        let mut pixels = default_pixel.repeat(rect.width * rect.height);
+
+        for tile in &tiles {
+            let tile_left = (tile.x + offset_x - rect.x) as usize;
+            let tile_top = (tile.y + offset_y - rect.y) as usize;
+
+            for (row_index, row) in tile.data.chunks_exact(row_size).enumerate() {
+                let start = ((tile_top + row_index) * rect.width + tile_left) * pixel_size;
+                pixels[start..start + row_size].copy_from_slice(row);
+            }
+        }
+
+        #[rustfmt::skip]
+        let buffer = match colorspace.color_depth {
+            KraColorDepth::U8 => KraPixelBuffer::U8(pixels),
+            KraColorDepth::U16 => KraPixelBuffer::U16(
+                pixels.chunks_exact(2).map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]])).collect(),
+            ),
+            KraColorDepth::F16 => KraPixelBuffer::F16(
+                pixels.chunks_exact(2).map(|bytes| f16::from_le_bytes([bytes[0], bytes[1]])).collect(),
+            ),
+            KraColorDepth::F32 => KraPixelBuffer::F32(
+                pixels.chunks_exact(4).map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])).collect(),
+            ),
+        };
+
+        Ok(KraPixelData { rect, colorspace, buffer })
+    }
+}
@@ -7,10 +7,12 @@ use ziparchive_ext::ZipArchiveExt;
 use crate::kra_archive::KraArchive;
 use crate::kra_error::KraError;
 use crate::kra_filter_params::KraFilterParamsContainer;
+use crate::kra_image::KraImage;
 use crate::kra_keyframes::KraKeyframesDocument;
 use crate::kra_main_doc::{
     KraLayerType, KraMainDocImage, KraMainDocLayer, KraMainDocLayerContainer, KraMainDocMask, KraMaskType,
 };
+use crate::kra_pixel_data::{KraColorspace, KraPixelData};
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 
@@ -46,6 +48,44 @@ impl KraMainDocLayer {
     }
 }
 
+impl KraMainDocLayerContainer {
+    pub fn find_layer_mut(&mut self, layer_uuid: &str) -> Option<&mut KraMainDocLayer> {
+        for layer in self.0.iter_mut() {
+            if layer.uuid == layer_uuid {
+                return Some(layer);
+            }
+
+            if let Some(layer) =
+                layer.layer_container.as_mut().and_then(|layer_container| layer_container.find_layer_mut(layer_uuid))
+            {
+                return Some(layer);
+            }
+        }
+
+        None
+    }
+
+    pub fn find_mask_mut(&mut self, mask_uuid: &str) -> Option<&mut KraMainDocMask> {
+        for layer in self.0.iter_mut() {
+            if let Some(mask) = layer
+                .mask_container
+                .as_mut()
+                .and_then(|mask_container| mask_container.0.iter_mut().find(|mask| mask.uuid == mask_uuid))
+            {
+                return Some(mask);
+            }
+
+            if let Some(mask) =
+                layer.layer_container.as_mut().and_then(|layer_container| layer_container.find_mask_mut(mask_uuid))
+            {
+                return Some(mask);
+            }
+        }
+
+        None
+    }
+}
+
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 
 impl KraArchive {
@@ -77,14 +117,61 @@ impl KraArchive {
     ) -> impl Iterator<Item = (&KraMainDocLayer, &KraMainDocMask)> {
         self.all_masks().filter(move |(_, kra_mask)| kra_mask.mask_type == mask_type)
     }
+
+    pub fn layer_by_uuid_mut(&mut self, layer_uuid: &str) -> Option<&mut KraMainDocLayer> {
+        self.main_doc.image.layer_container.find_layer_mut(layer_uuid)
+    }
+
+    pub fn mask_by_uuid_mut(&mut self, mask_uuid: &str) -> Option<&mut KraMainDocMask> {
+        self.main_doc.image.layer_container.find_mask_mut(mask_uuid)
+    }
 }
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 
+impl KraArchive {
+    fn image_data(&self, image_path: &str) -> Result<Vec<u8>, KraError> {
+        self.zip_archive().read(image_path)?.ok_or(KraError::ImageNotFound { image_path: image_path.to_owned() })
+    }
+
+    fn image(&self, image_path: &str) -> Result<KraImage, KraError> {
+        KraImage::from_png(&self.image_data(image_path)?)
+            .map_err(|source| KraError::ImageCannotDecode { image_path: image_path.to_owned(), source })
+    }
+
+    fn image_size(&self, image_path: &str) -> Result<(usize, usize), KraError> {
+        KraImage::size_from_png(&self.image_data(image_path)?)
+            .map_err(|source| KraError::ImageCannotDecode { image_path: image_path.to_owned(), source })
+    }
+
+    pub fn merged_image(&self) -> Result<KraImage, KraError> {
+        self.image("mergedimage.png")
+    }
+
+    /// Width and height of the merged image, without decoding its pixels.
+    pub fn merged_image_size(&self) -> Result<(usize, usize), KraError> {
+        self.image_size("mergedimage.png")
+    }
+
+    /// PNG data of the preview image, as stored in the document.
+    pub fn preview_image_data(&self) -> Result<Vec<u8>, KraError> {
+        self.image_data("preview.png")
+    }
+
+    pub fn preview_image(&self) -> Result<KraImage, KraError> {
+        self.image("preview.png")
+    }
+
+    /// Width and height of the preview image, without decoding its pixels.
+    pub fn preview_image_size(&self) -> Result<(usize, usize), KraError> {
+        self.image_size("preview.png")
+    }
+}
+
 impl KraMainDocLayer {
     pub fn content_svg(&self, kra_archive: &KraArchive) -> Result<String, KraError> {
         assert_eq!(self.layer_type, KraLayerType::VectorLayer);
-        let mut zip_archive = kra_archive.zip_archive.borrow_mut();
+        let mut zip_archive = kra_archive.zip_archive();
 
         let svg_path = format!("{}/layers/{}.shapelayer/content.svg", kra_archive.main_doc.image.name, self.file_name);
 
@@ -93,7 +180,7 @@ impl KraMainDocLayer {
 
     pub fn color_profile(&self, kra_archive: &KraArchive) -> Result<Vec<u8>, KraError> {
         assert_eq!(self.layer_type, KraLayerType::PaintLayer);
-        let mut zip_archive = kra_archive.zip_archive.borrow_mut();
+        let mut zip_archive = kra_archive.zip_archive();
 
         let color_profile_path = format!(
             "{document_name:}/layers/{layer_name:}.icc",
@@ -104,9 +191,38 @@ impl KraMainDocLayer {
         zip_archive.read(&color_profile_path)?.ok_or(KraError::ColorProfileNotFound { color_profile_path })
     }
 
+    pub fn pixel_data(&self, kra_archive: &KraArchive) -> Result<KraPixelData, KraError> {
+        assert_eq!(self.layer_type, KraLayerType::PaintLayer);
+        let mut zip_archive = kra_archive.zip_archive();
+
+        let pixel_data_path = format!(
+            "{document_name:}/layers/{layer_name:}",
+            document_name = kra_archive.main_doc.image.name,
+            layer_name = self.file_name
+        );
+
+        let default_pixel_path = format!("{pixel_data_path:}.defaultpixel");
+
+        let colorspace_name = self.colorspace_name.as_ref().unwrap_or(&kra_archive.main_doc.image.colorspace_name);
+        let colorspace = KraColorspace::from_str(colorspace_name)?;
+
+        let tile_data = zip_archive
+            .read(&pixel_data_path)?
+            .ok_or(KraError::PixelDataNotFound { pixel_data_path: pixel_data_path.clone() })?;
+        let default_pixel = zip_archive.read(&default_pixel_path)?;
+
+        KraPixelData::from_tile_data(
+            &tile_data,
+            default_pixel.as_deref(),
+            colorspace,
+            (self.x, self.y),
+            &pixel_data_path,
+        )
+    }
+
     pub fn filter_params(&self, kra_archive: &KraArchive) -> Result<Option<KraFilterParamsContainer>, KraError> {
         assert_eq!(self.layer_type, KraLayerType::FilterLayer);
-        let mut zip_archive = kra_archive.zip_archive.borrow_mut();
+        let mut zip_archive = kra_archive.zip_archive();
 
         let filter_params_path = format!(
             "{document_name:}/layers/{layer_name:}.filterconfig",
@@ -131,7 +247,7 @@ impl KraMainDocLayer {
 
     pub fn keyframes(&self, kra_archive: &KraArchive) -> Result<Option<KraKeyframesDocument>, KraError> {
         if let Some(keyframes_xml_filename) = self.keyframes.as_ref() {
-            let mut zip_archive = kra_archive.zip_archive.borrow_mut();
+            let mut zip_archive = kra_archive.zip_archive();
 
             let keyframes_xml_path = format!(
                 "{document_name:}/layers/{keyframes_xml_filename:}",
@@ -154,7 +270,7 @@ impl KraMainDocLayer {
 impl KraMainDocMask {
     pub fn colorize_color_profile(&self, kra_archive: &KraArchive) -> Result<Vec<u8>, KraError> {
         assert_eq!(self.mask_type, KraMaskType::ColorizeMask);
-        let mut zip_archive = kra_archive.zip_archive.borrow_mut();
+        let mut zip_archive = kra_archive.zip_archive();
 
         let color_profile_path = format!(
             "{document_name:}/layers/{mask_name:}.colorizemask/{document_name:}/layers/{mask_name:}.icc",
@@ -167,7 +283,7 @@ impl KraMainDocMask {
 
     pub fn filter_params(&self, kra_archive: &KraArchive) -> Result<Option<KraFilterParamsContainer>, KraError> {
         assert_eq!(self.mask_type, KraMaskType::FilterMask);
-        let mut zip_archive = kra_archive.zip_archive.borrow_mut();
+        let mut zip_archive = kra_archive.zip_archive();
 
         let filter_params_path = format!(
             "{document_name:}/layers/{mask_name:}.filterconfig",
@@ -192,7 +308,7 @@ impl KraMainDocMask {
 
     pub fn keyframes(&self, kra_archive: &KraArchive) -> Result<Option<KraKeyframesDocument>, KraError> {
         if let Some(keyframes_xml_filename) = self.keyframes.as_ref() {
-            let mut zip_archive = kra_archive.zip_archive.borrow_mut();
+            let mut zip_archive = kra_archive.zip_archive();
 
             let keyframes_xml_path = format!(
                 "{document_name:}/layers/{keyframes_xml_filename:}",
@@ -214,7 +330,7 @@ impl KraMainDocMask {
 
 impl KraMainDocImage {
     pub fn color_profile(&self, kra_archive: &KraArchive) -> Result<Vec<u8>, KraError> {
-        let mut zip_archive = kra_archive.zip_archive.borrow_mut();
+        let mut zip_archive = kra_archive.zip_archive();
 
         let color_profile_path =
             format!("{document_name:}/annotations/icc", document_name = kra_archive.main_doc.image.name);
@@ -1,24 +1,27 @@
 use std::collections::HashMap;
 use std::error::Error;
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
+use std::io::Write;
 use std::str::FromStr;
 
-use strong_xml::{XmlError, XmlRead, XmlReader, XmlResult};
+use serde::Serialize;
+use strong_xml::{XmlError, XmlRead, XmlReader, XmlResult, XmlWriter};
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(transparent)]
 pub struct KraXmlValue<T> {
     pub value: T,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct KraXmlTimeRange<T> {
     pub from: T,
     pub to: T,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct KraXmlPoint<T> {
     pub x: T,
     pub y: T,
@@ -124,3 +127,77 @@ where
         }
     }
 }
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+pub(crate) trait KraXmlAttribute {
+    fn to_xml_attribute(&self) -> String;
+}
+
+macro_rules! kra_xml_attribute_display {
+    ($($attribute_type:ty),*) => {
+        $(
+            impl KraXmlAttribute for $attribute_type {
+                fn to_xml_attribute(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+kra_xml_attribute_display!(String, usize, isize, f64);
+
+impl KraXmlAttribute for bool {
+    fn to_xml_attribute(&self) -> String {
+        // Krita expects numeric booleans, "true"/"false" are read back as false
+        if *self {
+            "1".to_owned()
+        } else {
+            "0".to_owned()
+        }
+    }
+}
+
+pub(crate) trait KraXmlWriterExt {
+    fn write_kra_attribute<T: KraXmlAttribute>(&mut self, key: &str, value: &T) -> XmlResult<()>;
+
+    fn write_kra_attribute_opt<T: KraXmlAttribute>(&mut self, key: &str, value: &Option<T>) -> XmlResult<()>;
+}
+
+impl<W: Write> KraXmlWriterExt for XmlWriter<W> {
+    fn write_kra_attribute<T: KraXmlAttribute>(&mut self, key: &str, value: &T) -> XmlResult<()> {
+        Ok(self.write_attribute(key, &value.to_xml_attribute())?)
+    }
+
+    fn write_kra_attribute_opt<T: KraXmlAttribute>(&mut self, key: &str, value: &Option<T>) -> XmlResult<()> {
+        if let Some(value) = value.as_ref() {
+            self.write_kra_attribute(key, value)?;
+        }
+        Ok(())
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+// These wrappers are shared by differently named elements, so the parent
+// element has to provide the tag name when writing them.
+
+impl<T: Display> KraXmlValue<T> {
+    pub(crate) fn to_writer_with_tag<W: Write>(&self, writer: &mut XmlWriter<W>, tag: &str) -> XmlResult<()> {
+        writer.write_element_start(tag)?;
+        writer.write_attribute("value", &self.value.to_string())?;
+        writer.write_attribute("type", "value")?;
+        Ok(writer.write_element_end_empty()?)
+    }
+}
+
+impl<T: Display> KraXmlTimeRange<T> {
+    pub(crate) fn to_writer_with_tag<W: Write>(&self, writer: &mut XmlWriter<W>, tag: &str) -> XmlResult<()> {
+        writer.write_element_start(tag)?;
+        writer.write_attribute("from", &self.from.to_string())?;
+        writer.write_attribute("to", &self.to.to_string())?;
+        writer.write_attribute("type", "timerange")?;
+        Ok(writer.write_element_end_empty()?)
+    }
+}
@@ -0,0 +1,321 @@
+use std::ops::Range;
+
+use derive_more::{Display, Error, From};
+use strong_xml::utils::{xml_escape, xml_unescape};
+use strong_xml::xmlparser::{ElementEnd, Token, Tokenizer};
+use strong_xml::XmlError;
+
+// The models only cover part of what Krita writes, so modified documents are
+// not serialized from scratch. Instead the serializations of the original and
+// of the modified model are compared, and only the attributes and texts which
+// differ are patched into the original XML. Everything else, including
+// unmodelled elements, comments and attribute order, is kept byte for byte.
+
+#[rustfmt::skip]
+#[derive(Debug, Display, Error, From)]
+pub enum KraXmlPatchError {
+    #[display(fmt = "Cannot parse XML")]
+    Xml(XmlError),
+
+    #[display(fmt = "Cannot match element '{_0:}' with the original XML")]
+    UnmatchedElement(#[error(not(source))] String),
+}
+
+/// Patches `original_xml` with the differences between `original_model_xml` and `current_model_xml`.
+///
+/// Both model serializations have to be written by the same `XmlWrite` implementation.
+pub fn patch_xml(
+    original_xml: &str,
+    original_model_xml: &str,
+    current_model_xml: &str,
+) -> Result<String, KraXmlPatchError> {
+    let original = XmlDocument::parse(original_xml)?;
+    let original_model = XmlDocument::parse(original_model_xml)?;
+    let current_model = XmlDocument::parse(current_model_xml)?;
+
+    let mut xml_patcher = XmlPatcher {
+        original: &original,
+        original_model: &original_model,
+        current_model: &current_model,
+        patches: vec![],
+    };
+
+    match (&original.root, &original_model.root, &current_model.root) {
+        (Some(original_root), Some(original_model_root), Some(current_model_root))
+            if original_root.name == original_model_root.name =>
+        {
+            xml_patcher.patch_element(original_root, original_model_root, current_model_root, "")?;
+        }
+        _ => return Err(KraXmlPatchError::UnmatchedElement("/".to_owned())),
+    }
+
+    let mut patches = xml_patcher.patches;
+
+    // Patches never overlap, as each one covers a distinct attribute or element content
+    patches.sort_by_key(|patch| patch.range.start);
+
+    let mut patched_xml = String::with_capacity(original_xml.len());
+    let mut position = 0;
+
+    for patch in patches {
+        patched_xml.push_str(&original_xml[position..patch.range.start]);
+        patched_xml.push_str(&patch.replacement);
+        position = patch.range.end;
+    }
+
+    patched_xml.push_str(&original_xml[position..]);
+
+    Ok(patched_xml)
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+struct XmlDocument<'a> {
+    text: &'a str,
+    root: Option<XmlElement>,
+}
+
+struct XmlElement {
+    name: String,
+    attributes: Vec<XmlAttribute>,
+    /// Position right after the last attribute, or after the tag name.
+    attributes_end: usize,
+    /// The closing `>` or `/>` of the start tag.
+    start_tag_end: Range<usize>,
+    /// Everything between the start and end tags, `None` for empty elements.
+    content: Option<Range<usize>>,
+    /// Unescaped text and CDATA contents.
+    text: String,
+    has_cdata: bool,
+    children: Vec<XmlElement>,
+}
+
+struct XmlAttribute {
+    name: String,
+    value: Range<usize>,
+    /// The attribute along with the whitespace preceding it.
+    range: Range<usize>,
+}
+
+struct XmlPatch {
+    range: Range<usize>,
+    replacement: String,
+}
+
+struct XmlPatcher<'a, 'd> {
+    original: &'d XmlDocument<'a>,
+    original_model: &'d XmlDocument<'a>,
+    current_model: &'d XmlDocument<'a>,
+    patches: Vec<XmlPatch>,
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+impl<'a> XmlDocument<'a> {
+    fn parse(text: &'a str) -> Result<Self, XmlError> {
+        let mut open_elements: Vec<XmlElement> = vec![];
+        let mut root = None;
+
+        for token in Tokenizer::from(text) {
+            match token? {
+                Token::ElementStart { span, .. } => {
+                    open_elements.push(XmlElement {
+                        name: span.as_str()[1..].to_owned(),
+                        attributes: vec![],
+                        attributes_end: span.end(),
+                        start_tag_end: 0..0,
+                        content: None,
+                        text: String::new(),
+                        has_cdata: false,
+                        children: vec![],
+                    });
+                }
+
+                Token::Attribute { span, prefix, local, value } => {
+                    let element = open_elements.last_mut().ok_or(XmlError::UnexpectedEof)?;
+                    let name = if prefix.is_empty() { local.to_string() } else { format!("{}:{}", prefix, local) };
+
+                    element.attributes.push(XmlAttribute {
+                        name,
+                        value: value.start()..value.end(),
+                        range: element.attributes_end..span.end(),
+                    });
+                    element.attributes_end = span.end();
+                }
+
+                Token::ElementEnd { end, span } => {
+                    let element = open_elements.last_mut().ok_or(XmlError::UnexpectedEof)?;
+
+                    match end {
+                        ElementEnd::Open => {
+                            element.start_tag_end = span.start()..span.end();
+                            element.content = Some(span.end()..span.end());
+                            continue;
+                        }
+                        ElementEnd::Empty => {
+                            element.start_tag_end = span.start()..span.end();
+                        }
+                        ElementEnd::Close(..) => {
+                            if let Some(content) = element.content.as_mut() {
+                                content.end = span.start();
+                            }
+                        }
+                    }
+
+                    let element = open_elements.pop().ok_or(XmlError::UnexpectedEof)?;
+
+                    match open_elements.last_mut() {
+                        Some(parent) => parent.children.push(element),
+                        None => root = Some(element),
+                    }
+                }
+
+                Token::Text { text } => {
+                    if let Some(element) = open_elements.last_mut() {
+                        element.text.push_str(&xml_unescape(text.as_str())?);
+                    }
+                }
+
+                Token::Cdata { text, .. } => {
+                    if let Some(element) = open_elements.last_mut() {
+                        element.text.push_str(text.as_str());
+                        element.has_cdata = true;
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        Ok(XmlDocument { text, root })
+    }
+
+    fn str(&self, range: &Range<usize>) -> &'a str {
+        &self.text[range.clone()]
+    }
+}
+
+impl XmlElement {
+    fn attribute(&self, name: &str) -> Option<&XmlAttribute> {
+        self.attributes.iter().find(|attribute| attribute.name == name)
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+impl<'a, 'd> XmlPatcher<'a, 'd> {
+    fn patch_element(
+        &mut self,
+        original: &'d XmlElement,
+        original_model: &'d XmlElement,
+        current_model: &'d XmlElement,
+        parent_path: &str,
+    ) -> Result<(), KraXmlPatchError> {
+        let element_path = format!("{}/{}", parent_path, original.name);
+
+        if original_model.name != current_model.name || original_model.children.len() != current_model.children.len() {
+            return Err(KraXmlPatchError::UnmatchedElement(element_path));
+        }
+
+        self.patch_attributes(original, original_model, current_model);
+
+        if original_model.children.is_empty() {
+            return self.patch_text(original, original_model, current_model, &element_path);
+        }
+
+        // Models read their children in document order, skipping unmodelled
+        // ones, so matching children can be searched for front to back
+        let mut original_children = original.children.iter();
+
+        for (original_model_child, current_model_child) in original_model.children.iter().zip(&current_model.children) {
+            let model_uuid = original_model_child.attribute("uuid").map(|uuid| self.original_model.str(&uuid.value));
+
+            let original_child = original_children
+                .find(|original_child| {
+                    original_child.name == original_model_child.name
+                        && model_uuid.map_or(true, |model_uuid| {
+                            original_child.attribute("uuid").map(|uuid| self.original.str(&uuid.value))
+                                == Some(model_uuid)
+                        })
+                })
+                .ok_or_else(|| {
+                    KraXmlPatchError::UnmatchedElement(format!("{}/{}", element_path, original_model_child.name))
+                })?;
+
+            self.patch_element(original_child, original_model_child, current_model_child, &element_path)?;
+        }
+
+        Ok(())
+    }
+
+    fn patch_attributes(&mut self, original: &XmlElement, original_model: &XmlElement, current_model: &XmlElement) {
+        for current_attribute in &current_model.attributes {
+            let current_value = self.current_model.str(&current_attribute.value);
+
+            let original_model_value = original_model
+                .attribute(&current_attribute.name)
+                .map(|attribute| self.original_model.str(&attribute.value));
+
+            if original_model_value == Some(current_value) {
+                continue;
+            }
+
+            let patch = match original.attribute(&current_attribute.name) {
+                Some(original_attribute) => {
+                    XmlPatch { range: original_attribute.value.clone(), replacement: current_value.to_owned() }
+                }
+                None => XmlPatch {
+                    range: original.attributes_end..original.attributes_end,
+                    replacement: format!(" {}=\"{}\"", current_attribute.name, current_value),
+                },
+            };
+
+            self.patches.push(patch);
+        }
+
+        for original_model_attribute in &original_model.attributes {
+            if current_model.attribute(&original_model_attribute.name).is_some() {
+                continue;
+            }
+
+            if let Some(original_attribute) = original.attribute(&original_model_attribute.name) {
+                self.patches.push(XmlPatch { range: original_attribute.range.clone(), replacement: String::new() });
+            }
+        }
+    }
+
+    fn patch_text(
+        &mut self,
+        original: &XmlElement,
+        original_model: &XmlElement,
+        current_model: &XmlElement,
+        element_path: &str,
+    ) -> Result<(), KraXmlPatchError> {
+        if original_model.text == current_model.text {
+            return Ok(());
+        }
+
+        // Replacing the text would drop unmodelled children
+        if !original.children.is_empty() {
+            return Err(KraXmlPatchError::UnmatchedElement(element_path.to_owned()));
+        }
+
+        // Keeping CDATA sections when the text can be put in one
+        let replacement = if original.has_cdata && !current_model.text.contains("]]>") {
+            format!("<![CDATA[{}]]>", current_model.text)
+        } else {
+            xml_escape(&current_model.text).into_owned()
+        };
+
+        let patch = match original.content.as_ref() {
+            Some(content) => XmlPatch { range: content.clone(), replacement },
+            None => XmlPatch {
+                range: original.start_tag_end.clone(),
+                replacement: format!(">{}</{}>", replacement, original.name),
+            },
+        };
+
+        self.patches.push(patch);
+        Ok(())
+    }
+}
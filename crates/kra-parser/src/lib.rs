@@ -4,7 +4,10 @@ pub mod kra_document_info;
 pub mod kra_error;
 pub mod kra_filter_config;
 pub mod kra_filter_params;
+pub mod kra_image;
 pub mod kra_keyframes;
 pub mod kra_main_doc;
+pub mod kra_pixel_data;
 pub mod kra_utils;
 pub mod kra_xml_fields;
+pub mod kra_xml_patch;
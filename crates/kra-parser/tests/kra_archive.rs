@@ -0,0 +1,156 @@
+use std::fs::{self, File};
+use std::io;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use zip::{CompressionMethod, ZipArchive};
+
+use kra_parser::kra_archive::KraArchive;
+
+const LAYER_UUID: &str = "{b69b42fc-fecf-48a9-929c-666acaa64fac}";
+
+fn fixture_copy(file_name: &str, copy_name: &str) -> Utf8PathBuf {
+    let fixture_path = Utf8PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(file_name);
+    let copy_path = Utf8PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(copy_name);
+
+    fs::copy(fixture_path, &copy_path).unwrap();
+    copy_path
+}
+
+fn read_entry(path: &Utf8Path, entry_name: &str) -> String {
+    let mut zip_archive = ZipArchive::new(File::open(path).unwrap()).unwrap();
+    io::read_to_string(zip_archive.by_name(entry_name).unwrap()).unwrap()
+}
+
+/// Lines of `modified` which differ from the same lines of `original`.
+fn changed_lines<'a>(original: &str, modified: &'a str) -> Vec<&'a str> {
+    assert_eq!(original.lines().count(), modified.lines().count());
+
+    original
+        .lines()
+        .zip(modified.lines())
+        .filter(|(original, modified)| original != modified)
+        .map(|(_, modified)| modified)
+        .collect()
+}
+
+#[test]
+fn unmodified_document_keeps_xml() {
+    let original_path = fixture_copy("paint_layer.kra", "unmodified_original.kra");
+    let saved_path = Utf8PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("unmodified_saved.kra");
+
+    KraArchive::from_path(&original_path).unwrap().save_to_path(&saved_path).unwrap();
+
+    for entry_name in ["maindoc.xml", "documentinfo.xml"] {
+        assert_eq!(read_entry(&original_path, entry_name), read_entry(&saved_path, entry_name));
+    }
+}
+
+#[test]
+fn modified_document_keeps_unmodelled_xml() {
+    let path = fixture_copy("paint_layer.kra", "modified.kra");
+
+    let original_main_doc = read_entry(&path, "maindoc.xml");
+    let original_document_info = read_entry(&path, "documentinfo.xml");
+
+    let mut kra_archive = KraArchive::from_path(&path).unwrap();
+
+    let layer = kra_archive.main_doc.image.layer_container.find_layer_mut(LAYER_UUID).unwrap();
+    layer.opacity = 128;
+    layer.onion_skin = Some(true);
+    layer.selected = Some("true".to_owned());
+    layer.channel_lock_flags = None;
+
+    kra_archive.document_info.about.title = "Renamed & <saved>".to_owned();
+    kra_archive.document_info.about.r#abstract = "Abstract".to_owned();
+    kra_archive.document_info.about.license = "CC-BY-4.0".to_owned();
+
+    kra_archive.save_to_path(&path).unwrap();
+
+    let main_doc = read_entry(&path, "maindoc.xml");
+    let document_info = read_entry(&path, "documentinfo.xml");
+
+    // Only the modified layer changes, every other element is kept as it was
+    assert_eq!(
+        changed_lines(&original_main_doc, &main_doc),
+        [
+            r#"   <layer opacity="128" x="0" visible="1" intimeline="1" onionskin="1" y="0" uuid="{b69b42fc-fecf-48a9-929c-666acaa64fac}" colorlabel="0" name="Paint Layer 1" filename="layer2" locked="0" nodetype="paintlayer" collapsed="0" compositeop="normal" colorspacename="RGBA" channelflags="" selected="true"/>"#,
+        ]
+    );
+
+    for unmodelled_element in ["<ProjectionBackgroundColor ", "<GlobalAssistantsColor ", "<MirrorAxis>", "<resources/>"]
+    {
+        assert!(main_doc.contains(unmodelled_element), "{} was dropped", unmodelled_element);
+    }
+
+    assert_eq!(
+        changed_lines(&original_document_info, &document_info),
+        [
+            "  <title>Renamed &amp; &lt;saved&gt;</title>",
+            "  <abstract><![CDATA[Abstract]]></abstract>",
+            "  <license>CC-BY-4.0</license>",
+        ]
+    );
+
+    let kra_archive = KraArchive::from_path(&path).unwrap();
+    assert_eq!(kra_archive.document_info.about.title, "Renamed & <saved>");
+    assert_eq!(kra_archive.document_info.about.r#abstract, "Abstract");
+}
+
+#[test]
+fn modified_document_keeps_entry_metadata() {
+    let original_path = fixture_copy("paint_layer.kra", "metadata_original.kra");
+    let saved_path = Utf8PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("metadata_saved.kra");
+
+    let mut kra_archive = KraArchive::from_path(&original_path).unwrap();
+    kra_archive.document_info.about.license = "CC-BY-4.0".to_owned();
+    kra_archive.main_doc.image.layer_container.find_layer_mut(LAYER_UUID).unwrap().opacity = 128;
+    kra_archive.save_to_path(&saved_path).unwrap();
+
+    let mut original_zip_archive = ZipArchive::new(File::open(&original_path).unwrap()).unwrap();
+    let mut saved_zip_archive = ZipArchive::new(File::open(&saved_path).unwrap()).unwrap();
+
+    assert_eq!(saved_zip_archive.by_index(0).unwrap().name(), "mimetype");
+    assert_eq!(original_zip_archive.len(), saved_zip_archive.len());
+
+    for entry_name in ["mimetype", "maindoc.xml", "documentinfo.xml"] {
+        let original_entry = original_zip_archive.by_name(entry_name).unwrap();
+        let (original_time, original_compression) = (original_entry.last_modified(), original_entry.compression());
+        drop(original_entry);
+
+        let saved_entry = saved_zip_archive.by_name(entry_name).unwrap();
+
+        assert_eq!(format!("{:?}", saved_entry.last_modified()), format!("{:?}", original_time), "{}", entry_name);
+        assert_eq!(saved_entry.compression(), original_compression, "{}", entry_name);
+    }
+}
+
+#[test]
+fn image_sizes_match_decoded_images() {
+    let path = fixture_copy("paint_layer.kra", "image_sizes.kra");
+    let kra_archive = KraArchive::from_path(&path).unwrap();
+
+    let merged_image = kra_archive.merged_image().unwrap();
+    assert_eq!(kra_archive.merged_image_size().unwrap(), (merged_image.width, merged_image.height));
+    assert_eq!(kra_archive.merged_image_size().unwrap(), (256, 256));
+
+    let preview_image = kra_archive.preview_image().unwrap();
+    assert_eq!(kra_archive.preview_image_size().unwrap(), (preview_image.width, preview_image.height));
+}
+
+#[test]
+fn saved_mimetype_is_stored_uncompressed() {
+    let original_path = fixture_copy("deflated_mimetype.kra", "deflated_mimetype.kra");
+    let saved_path = Utf8PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("deflated_mimetype_saved.kra");
+
+    let mut original_zip_archive = ZipArchive::new(File::open(&original_path).unwrap()).unwrap();
+    assert_eq!(original_zip_archive.by_name("mimetype").unwrap().compression(), CompressionMethod::Deflated);
+
+    KraArchive::from_path(&original_path).unwrap().save_to_path(&saved_path).unwrap();
+
+    let mut saved_zip_archive = ZipArchive::new(File::open(&saved_path).unwrap()).unwrap();
+    let saved_entry = saved_zip_archive.by_index(0).unwrap();
+
+    assert_eq!(saved_entry.name(), "mimetype");
+    assert_eq!(saved_entry.compression(), CompressionMethod::Stored);
+    assert_eq!(io::read_to_string(saved_entry).unwrap(), read_entry(&original_path, "mimetype"));
+}
@@ -0,0 +1,170 @@
+use camino::Utf8PathBuf;
+
+use kra_parser::kra_archive::KraArchive;
+use kra_parser::kra_error::KraError;
+use kra_parser::kra_main_doc::KraMainDocLayer;
+use kra_parser::kra_pixel_data::{KraColorDepth, KraColorModel, KraColorspace, KraPixelBuffer, KraPixelData};
+
+fn fixture_path(file_name: &str) -> Utf8PathBuf {
+    Utf8PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(file_name)
+}
+
+fn layer_by_name<'a>(kra_archive: &'a KraArchive, layer_name: &str) -> &'a KraMainDocLayer {
+    kra_archive.all_layers().find(|layer| layer.name == layer_name).expect("Layer not found")
+}
+
+/// RGBA value of a canvas pixel, read from 8-bit BGRA layer pixels.
+fn layer_pixel(pixel_data: &KraPixelData, x: usize, y: usize) -> Option<[u8; 4]> {
+    let KraPixelBuffer::U8(pixels) = &pixel_data.buffer else {
+        panic!("Unexpected color depth");
+    };
+
+    let layer_x = usize::try_from(x as isize - pixel_data.rect.x).ok().filter(|&x| x < pixel_data.rect.width)?;
+    let layer_y = usize::try_from(y as isize - pixel_data.rect.y).ok().filter(|&y| y < pixel_data.rect.height)?;
+
+    let start = (layer_y * pixel_data.rect.width + layer_x) * 4;
+    let [b, g, r, a] = pixels[start..start + 4] else { unreachable!() };
+    Some([r, g, b, a])
+}
+
+/// The paint layer covers parts of an empty white background, so wherever it is fully opaque
+/// (or fully transparent) the merged image Krita saved shows exactly the layer (or the background).
+#[test]
+fn decodes_paint_layer_like_merged_image() {
+    let kra_archive = KraArchive::from_path(&fixture_path("paint_layer.kra")).unwrap();
+    let merged_image = kra_archive.merged_image().unwrap();
+
+    let paint_layer = layer_by_name(&kra_archive, "Paint Layer 1").pixel_data(&kra_archive).unwrap();
+    let background = layer_by_name(&kra_archive, "Background").pixel_data(&kra_archive).unwrap();
+
+    assert!(!paint_layer.is_empty());
+    assert!(background.is_empty());
+
+    let background_pixel = [0xFF, 0xFF, 0xFF, 0xFF];
+    let (mut opaque_pixels, mut transparent_pixels) = (0, 0);
+
+    for y in 0..merged_image.height {
+        for x in 0..merged_image.width {
+            let start = (y * merged_image.width + x) * 4;
+            let merged_pixel = &merged_image.pixels[start..start + 4];
+
+            match layer_pixel(&paint_layer, x, y) {
+                Some(pixel @ [_, _, _, 0xFF]) => {
+                    assert_eq!(merged_pixel, pixel, "Pixel ({}, {})", x, y);
+                    opaque_pixels += 1;
+                }
+                None | Some([_, _, _, 0x00]) => {
+                    assert_eq!(merged_pixel, background_pixel, "Pixel ({}, {})", x, y);
+                    transparent_pixels += 1;
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    assert!(opaque_pixels > 0);
+    assert!(transparent_pixels > 0);
+}
+
+/// Pixels of the paint layer in the 8-bit fixture, the other fixtures hold the same pixels at other depths.
+fn paint_layer_u8_pixels() -> (KraPixelData, Vec<u8>) {
+    let kra_archive = KraArchive::from_path(&fixture_path("paint_layer.kra")).unwrap();
+    let pixel_data = layer_by_name(&kra_archive, "Paint Layer 1").pixel_data(&kra_archive).unwrap();
+
+    let KraPixelBuffer::U8(pixels) = pixel_data.buffer.clone() else {
+        panic!("Unexpected color depth");
+    };
+
+    (pixel_data, pixels)
+}
+
+fn paint_layer_pixel_data(file_name: &str) -> KraPixelData {
+    let kra_archive = KraArchive::from_path(&fixture_path(file_name)).unwrap();
+    layer_by_name(&kra_archive, "Paint Layer 1").pixel_data(&kra_archive).unwrap()
+}
+
+#[test]
+fn decodes_16_bit_layers() {
+    let (u8_pixel_data, u8_pixels) = paint_layer_u8_pixels();
+
+    for (file_name, color_model) in
+        [("paint_layer_rgba16.kra", KraColorModel::Rgb), ("paint_layer_laba.kra", KraColorModel::Lab)]
+    {
+        let pixel_data = paint_layer_pixel_data(file_name);
+
+        assert_eq!(pixel_data.colorspace, KraColorspace { color_model, color_depth: KraColorDepth::U16 });
+        assert_eq!(pixel_data.rect, u8_pixel_data.rect);
+
+        let KraPixelBuffer::U16(pixels) = &pixel_data.buffer else {
+            panic!("Unexpected color depth in {}", file_name);
+        };
+
+        let expected_pixels = u8_pixels.iter().map(|&value| u16::from(value) * 257).collect::<Vec<_>>();
+        assert!(*pixels == expected_pixels, "Pixels of {} differ", file_name);
+    }
+}
+
+#[test]
+fn decodes_floating_point_layers() {
+    let (u8_pixel_data, u8_pixels) = paint_layer_u8_pixels();
+
+    // Floating-point pixels are RGBA instead of BGRA
+    let expected_pixels = u8_pixels
+        .chunks_exact(4)
+        .flat_map(|pixel| [pixel[2], pixel[1], pixel[0], pixel[3]])
+        .map(|value| f32::from(value) / 255.0)
+        .collect::<Vec<_>>();
+
+    let assert_pixels_eq = |file_name: &str, pixels: Vec<f32>, tolerance: f32| {
+        assert_eq!(pixels.len(), expected_pixels.len(), "Pixels of {} differ", file_name);
+
+        for (index, (value, expected_value)) in pixels.iter().zip(&expected_pixels).enumerate() {
+            assert!((value - expected_value).abs() <= tolerance, "Value #{} of {} differs", index, file_name);
+        }
+    };
+
+    let pixel_data = paint_layer_pixel_data("paint_layer_rgbaf16.kra");
+    assert_eq!(pixel_data.colorspace.color_depth, KraColorDepth::F16);
+    assert_eq!(pixel_data.rect, u8_pixel_data.rect);
+
+    let KraPixelBuffer::F16(pixels) = pixel_data.buffer else {
+        panic!("Unexpected color depth");
+    };
+    assert_pixels_eq("paint_layer_rgbaf16.kra", pixels.into_iter().map(f32::from).collect(), 1e-3);
+
+    let pixel_data = paint_layer_pixel_data("paint_layer_rgbaf32.kra");
+    assert_eq!(pixel_data.colorspace.color_depth, KraColorDepth::F32);
+    assert_eq!(pixel_data.rect, u8_pixel_data.rect);
+
+    let KraPixelBuffer::F32(pixels) = pixel_data.buffer else {
+        panic!("Unexpected color depth");
+    };
+    assert_pixels_eq("paint_layer_rgbaf32.kra", pixels, 0.0);
+}
+
+#[test]
+fn parses_krita_colorspace_ids() {
+    let colorspace = |color_model, color_depth| KraColorspace { color_model, color_depth };
+
+    assert_eq!("RGBA".parse().ok(), Some(colorspace(KraColorModel::Rgb, KraColorDepth::U8)));
+    assert_eq!("RgbAF32".parse().ok(), Some(colorspace(KraColorModel::Rgb, KraColorDepth::F32)));
+    assert_eq!("CMYK".parse().ok(), Some(colorspace(KraColorModel::Cmyk, KraColorDepth::U8)));
+    assert_eq!("LABA".parse().ok(), Some(colorspace(KraColorModel::Lab, KraColorDepth::U16)));
+    assert_eq!("LABAU8".parse().ok(), Some(colorspace(KraColorModel::Lab, KraColorDepth::U8)));
+    assert_eq!("GRAYAU16".parse().ok(), Some(colorspace(KraColorModel::Gray, KraColorDepth::U16)));
+    assert_eq!("XYZAU8".parse().ok(), Some(colorspace(KraColorModel::Xyz, KraColorDepth::U8)));
+    assert_eq!("YCbCrAU16".parse().ok(), Some(colorspace(KraColorModel::YCbCr, KraColorDepth::U16)));
+
+    for colorspace_name in ["CMYKA", "LABA16", "RGBAF32", "rgba"] {
+        let result = colorspace_name.parse::<KraColorspace>();
+        assert!(matches!(result, Err(KraError::UnsupportedColorspace { .. })), "{:?}", result);
+    }
+}
+
+#[test]
+fn rejects_oversized_tile_headers() {
+    let kra_archive = KraArchive::from_path(&fixture_path("oversized_layer.kra")).unwrap();
+
+    let result = layer_by_name(&kra_archive, "Paint Layer 1").pixel_data(&kra_archive);
+    assert!(matches!(result, Err(KraError::PixelDataTooLarge { .. })), "{:?}", result);
+}
@@ -0,0 +1,33 @@
+# Every kind of change between two documents
+for changed_path in document.kra added_removed.kra renamed.kra moved.kra attributes.kra; do
+    $KRA_DIFF base.kra "$changed_path"
+done
+
+$KRA_DIFF keyframes_base.kra keyframes.kra
+$KRA_DIFF base.kra base.kra
+
+# Added and deleted documents
+$KRA_DIFF /dev/null base.kra
+$KRA_DIFF base.kra /dev/null
+
+# Git external diff driver: path old-file old-hex old-mode new-file new-hex new-mode
+$KRA_DIFF page.kra base.kra 1111111 100644 renamed.kra 2222222 100644
+$KRA_DIFF page.kra /dev/null . . base.kra 2222222 100644
+$KRA_DIFF page.kra base.kra 1111111 100644 /dev/null . .
+
+# Renamed documents are followed by the new path and the rename header
+$KRA_DIFF page.kra base.kra 1111111 100644 moved.kra 2222222 100644 cover.kra \
+    "similarity index 90%
+rename from page.kra
+rename to cover.kra
+"
+
+for changed_path in document.kra added_removed.kra renamed.kra moved.kra attributes.kra; do
+    $KRA_DIFF -F json base.kra "$changed_path"
+done
+
+$KRA_DIFF -F json keyframes_base.kra keyframes.kra
+
+# Neither two paths nor the arguments of a git diff driver
+$KRA_DIFF base.kra document.kra renamed.kra
+echo "exit status: $?"
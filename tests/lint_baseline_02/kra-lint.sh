@@ -0,0 +1,13 @@
+# The baseline records every issue by path, code, layer UUID and count, and suppresses them when read back
+baseline_dir=$(mktemp -d)
+trap 'rm -rf "$baseline_dir"' EXIT
+
+$KRA_LINT -C .kra-lint -F plain-text --write-baseline "$baseline_dir/baseline.json" *.kr[az] 2> stderr
+echo "exit status: $?"
+cat "$baseline_dir/baseline.json"
+
+$KRA_LINT -C .kra-lint -F plain-text --baseline "$baseline_dir/baseline.json" *.kr[az] 2>> stderr
+echo "exit status: $?"
+
+sed "s|$baseline_dir|<baseline>|g" stderr >&2
+rm stderr
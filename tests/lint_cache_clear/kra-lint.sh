@@ -0,0 +1,45 @@
+# Cached results are only written with --cache, and removed with --clear-cache
+cache_dir=$(mktemp -d)
+trap 'rm -rf "$cache_dir"' EXIT
+
+$KRA_LINT -C .kra-lint -F plain-text --cache-dir "$cache_dir" lint_success.kra unrelated_directive.kra
+echo "Cached entries: $(ls "$cache_dir" | wc -l)"
+
+$KRA_LINT -C .kra-lint -F plain-text --cache --cache-dir "$cache_dir" lint_success.kra unrelated_directive.kra
+echo "Cached entries: $(ls "$cache_dir" | wc -l)"
+
+$KRA_LINT -C .kra-lint -F plain-text --cache-dir "$cache_dir" --clear-cache lint_success.kra unrelated_directive.kra \
+    2> stderr
+sed "s|$cache_dir|<cache>|g" stderr >&2
+rm stderr
+echo "Cached entries: $(ls "$cache_dir" 2> /dev/null | wc -l)"
+
+# Cache hits report the same messages as linting the document
+work_dir=$(mktemp -d)
+trap 'rm -rf "$cache_dir" "$work_dir"' EXIT
+
+cp .kra-lint "$work_dir"
+cp unrelated_directive.kra "$work_dir/document.kra"
+cd "$work_dir" || exit 1
+
+$KRA_LINT -F plain-text --cache --cache-dir "$cache_dir" document.kra > linted 2> /dev/null
+$KRA_LINT -F plain-text --cache --cache-dir "$cache_dir" document.kra > cached 2> /dev/null
+echo "Cached entries: $(ls "$cache_dir" | wc -l)"
+diff linted cached && echo "Cached messages match the linted ones"
+
+# Editing cached entries shows which results come from the cache
+sed -i "s/Prohibited hidden mask/Cached hidden mask/" "$cache_dir"/*.json
+echo "# Cache hit"
+$KRA_LINT -F plain-text --cache --cache-dir "$cache_dir" document.kra 2> /dev/null
+
+echo "# --no-cache"
+KRALINT_CACHE=true $KRA_LINT -F plain-text --no-cache --cache-dir "$cache_dir" document.kra 2> /dev/null
+
+echo "# Edited config"
+echo "# Edited" >> .kra-lint
+$KRA_LINT -F plain-text --cache --cache-dir "$cache_dir" document.kra 2> /dev/null
+
+echo "# Edited document"
+sed -i "s/Prohibited hidden mask/Cached hidden mask/" "$cache_dir"/*.json
+cp "$OLDPWD/lint_success.kra" document.kra
+$KRA_LINT -F plain-text --cache --cache-dir "$cache_dir" document.kra 2> /dev/null
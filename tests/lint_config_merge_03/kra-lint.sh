@@ -0,0 +1,5 @@
+# Override paths are resolved against the config file defining them
+$KRA_LINT -C .kra-lint --print-effective-config | sed "s|$(pwd -P)|<test>|g"
+echo
+
+$KRA_LINT -C .kra-lint -F plain-text root.kra comics/issue_01/page_01.kra web/banner.kra
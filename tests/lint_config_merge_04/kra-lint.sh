@@ -0,0 +1,5 @@
+# Path-scoped configs and later configs are layered in load order
+$KRA_LINT -C .kra-lint --print-effective-config | sed "s|$(pwd -P)|<test>|g"
+echo
+
+$KRA_LINT -C .kra-lint -F plain-text root.kra comics/issue_01/page_01.kra
@@ -0,0 +1,27 @@
+# Applies the fixes to copies of the documents, lints the copies again and
+# shows which lines of their maindoc.xml were changed by the fixes.
+
+work_directory=$(mktemp -d)
+trap 'rm -rf "$work_directory"' EXIT
+
+cp .kra-lint fix_*.kra "$work_directory"
+cd "$work_directory" || exit 1
+
+for document in fix_*.kra; do
+    unzip -p "$document" maindoc.xml > "${document%.kra}.xml"
+done
+
+echo "\$ kra-lint --fix"
+$KRA_LINT -C .kra-lint -F plain-text --fix fix_*.kra
+echo "exit status: $?"
+echo
+
+echo "\$ kra-lint"
+$KRA_LINT -C .kra-lint -F plain-text fix_*.kra
+echo "exit status: $?"
+echo
+
+echo "\$ diff maindoc.xml"
+for document in fix_*.kra; do
+    unzip -p "$document" maindoc.xml | diff "${document%.kra}.xml" - | sed "s|^|$document: |"
+done
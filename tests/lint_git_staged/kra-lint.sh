@@ -0,0 +1,69 @@
+# Only the documents changed since a revision are linted, with --staged in their staged contents
+work_dir=$(mktemp -d)
+staging_tmp_dir=$(mktemp -d)
+trap 'rm -rf "$work_dir" "$staging_tmp_dir"' EXIT
+
+cp .kra-lint "$work_dir"
+mkdir "$work_dir/pages"
+cp page_256.kra "$work_dir/pages/unchanged.kra"
+cp page_256.kra "$work_dir/pages/unstaged.kra"
+cp page_256.kra "$work_dir/pages/staged.kra"
+cd "$work_dir" || exit 1
+
+git init --quiet
+git config user.name kra-lint
+git config user.email kra-lint@example.com
+git add .
+git commit --quiet --message "Initial pages"
+
+# Modified in the working tree only
+cp "$OLDPWD/page_512.kra" pages/unstaged.kra
+
+# Staged at 512x512, then reverted in the working tree
+cp "$OLDPWD/page_512.kra" pages/staged.kra
+git add pages/staged.kra
+cp "$OLDPWD/page_256.kra" pages/staged.kra
+
+# Staged, then removed from the working tree
+cp "$OLDPWD/page_512.kra" pages/removed.kra
+git add pages/removed.kra
+rm pages/removed.kra
+
+# Staged, then removed from the working tree with their directories, matched by the print pages override
+mkdir -p pages/print/chapter_1
+cp "$OLDPWD/page_512.kra" pages/print/chapter_1/removed.kra
+git add pages/print
+rm -r pages/print
+
+# Staged, then removed from the working tree, ignored by .kra-lintignore
+echo "drafts/" > pages/.kra-lintignore
+mkdir pages/drafts
+cp "$OLDPWD/page_512.kra" pages/drafts/removed.kra
+git add pages/drafts
+rm -r pages/drafts
+
+# Untracked
+cp "$OLDPWD/page_512.kra" pages/untracked.kra
+
+echo "# --changed-since HEAD"
+$KRA_LINT -F plain-text --changed-since HEAD 2> stderr
+echo "exit status: $?"
+
+echo "# --changed-since HEAD --staged"
+TMPDIR="$staging_tmp_dir" $KRA_LINT -F plain-text --changed-since HEAD --staged 2>> stderr
+echo "exit status: $?"
+
+echo "# --changed-since HEAD --staged pages/removed.kra"
+TMPDIR="$staging_tmp_dir" $KRA_LINT -F plain-text --changed-since HEAD --staged pages/removed.kra 2>> stderr
+echo "exit status: $?"
+
+echo "# --changed-since HEAD --staged -F html pages/staged.kra"
+staged_preview=$(unzip -p "$OLDPWD/page_512.kra" preview.png | base64 -w 0)
+working_tree_preview=$(unzip -p "$OLDPWD/page_256.kra" preview.png | base64 -w 0)
+TMPDIR="$staging_tmp_dir" $KRA_LINT -F html --changed-since HEAD --staged pages/staged.kra > report.html 2>> stderr
+echo "Previews of the staged contents: $(grep -c "base64,$staged_preview\"" report.html)"
+echo "Previews of the working tree: $(grep -c "base64,$working_tree_preview\"" report.html)"
+
+echo "# Staging directories left behind: $(ls "$staging_tmp_dir" | wc -l)"
+
+sed "s|$(pwd -P)|<work>|g" stderr >&2
@@ -0,0 +1,5 @@
+# Serialized outputs are an array of every document, --verbose adds a summary of the run
+$KRA_LINT -C .kra-lint -F json broken.kra lint_success.kra unrelated_directive.kra
+echo
+
+$KRA_LINT -C .kra-lint -F json --verbose broken.kra lint_success.kra unrelated_directive.kra
@@ -0,0 +1,7 @@
+# Patterns of the ignore file next to the config file are relative to its directory
+echo "# From the config directory"
+$KRA_LINT -C .kra-lint -F plain-text --verbose pages drafts 2>&1
+
+echo "# From a subdirectory"
+cd pages || exit 1
+$KRA_LINT -C ../.kra-lint -F plain-text --verbose . ../drafts 2>&1
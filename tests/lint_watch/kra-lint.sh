@@ -0,0 +1,87 @@
+# Watch mode lints documents again when a save replaces them, and reloads configs when they are replaced
+work_dir=$(mktemp -d)
+trap 'kill $watch_pid 2>/dev/null; rm -rf "$work_dir"' EXIT
+
+cp sub_pass_01_01.kra sub_pass_01_02.kra "$work_dir"
+cp document_name.toml "$work_dir/.kra-lint"
+cd "$work_dir" || exit 1
+
+# Waits until the watcher has linted documents $1 times
+wait_for_lint() {
+    tries=0
+    until [ "$(cat stderr 2> /dev/null | grep -c "Watching for changes")" -ge "$1" ]; do
+        tries=$((tries + 1))
+        if [ $tries -ge 600 ]; then
+            echo "Timed out waiting for lint #$1"
+            return 1
+        fi
+        sleep 0.1
+    done
+}
+
+# Saves a file like Krita does, by renaming a temporary file over it
+save() {
+    cp "$1" "$1.tmp" && mv "$1.tmp" "$2"
+}
+
+$KRA_LINT -F plain-text --watch sub_pass_01_01.kra > stdout 2> stderr &
+watch_pid=$!
+wait_for_lint 1
+
+# Saves in quick succession are linted once
+save sub_pass_01_01.kra sub_pass_01_01.kra
+save sub_pass_01_01.kra sub_pass_01_01.kra
+wait_for_lint 2
+
+# Documents which are not watched are not linted, only the watched one saved after them
+save sub_pass_01_02.kra sub_pass_01_02.kra
+save sub_pass_01_01.kra sub_pass_01_01.kra
+wait_for_lint 3
+
+save "$OLDPWD/document_size.toml" .kra-lint
+wait_for_lint 4
+
+kill $watch_pid
+wait $watch_pid 2>/dev/null
+
+cat stdout
+sed "s|$(pwd -P)|<work>|g" stderr
+
+# Without paths, the current directory is watched
+rm stdout stderr
+$KRA_LINT -F plain-text --watch > stdout 2> stderr &
+watch_pid=$!
+wait_for_lint 1
+kill $watch_pid
+wait $watch_pid 2>/dev/null
+
+echo
+cat stdout
+
+# Directories are watched recursively, changed documents are linted unless they are ignored
+mkdir -p pages/chapter_01
+cp sub_pass_01_02.kra pages/chapter_01/page_01.kra
+echo "ignored_*.kra" > pages/.kra-lintignore
+rm stdout stderr
+
+$KRA_LINT -F plain-text --watch pages > stdout 2> stderr &
+watch_pid=$!
+wait_for_lint 1
+
+save sub_pass_01_02.kra pages/chapter_01/ignored_01.kra
+save sub_pass_01_02.kra pages/chapter_01/notes.txt
+save sub_pass_01_02.kra pages/chapter_01/page_02.kra
+wait_for_lint 2
+
+# Unreadable documents are reported like any other document, the watch goes on
+echo "Not a document" > pages/broken.kra.tmp && mv pages/broken.kra.tmp pages/broken.kra
+wait_for_lint 3
+
+save sub_pass_01_01.kra pages/chapter_01/page_01.kra
+wait_for_lint 4
+
+kill $watch_pid
+wait $watch_pid 2>/dev/null
+
+echo
+cat stdout